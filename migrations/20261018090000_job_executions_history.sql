@@ -0,0 +1,5 @@
+CREATE INDEX IF NOT EXISTS idx_job_executions_started_at
+    ON job_executions (started_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_job_executions_type_started_at
+    ON job_executions (job_type, started_at DESC);
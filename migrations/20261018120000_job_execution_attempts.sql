@@ -0,0 +1,3 @@
+ALTER TABLE job_executions
+    ADD COLUMN IF NOT EXISTS triggered_by VARCHAR(20) NOT NULL DEFAULT 'schedule',
+    ADD COLUMN IF NOT EXISTS attempts INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,25 @@
+ALTER TABLE cars
+    ADD COLUMN IF NOT EXISTS safety_stock INTEGER NOT NULL DEFAULT 0 CHECK (safety_stock >= 0),
+    ADD COLUMN IF NOT EXISTS lead_time_days INTEGER CHECK (lead_time_days > 0),
+    ADD COLUMN IF NOT EXISTS reorder_point_override INTEGER CHECK (reorder_point_override >= 0),
+    ADD COLUMN IF NOT EXISTS economic_order_qty_override INTEGER CHECK (economic_order_qty_override > 0),
+    ADD COLUMN IF NOT EXISTS reorder_params_updated_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS reorder_parameter_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    source VARCHAR(20) NOT NULL,
+    previous_reorder_point INTEGER NOT NULL,
+    reorder_point INTEGER NOT NULL,
+    previous_economic_order_qty INTEGER NOT NULL,
+    economic_order_qty INTEGER NOT NULL,
+    previous_safety_stock INTEGER NOT NULL,
+    safety_stock INTEGER NOT NULL,
+    avg_daily_demand DOUBLE PRECISION,
+    demand_std_dev DOUBLE PRECISION,
+    lead_time_days INTEGER,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reorder_parameter_history_car
+    ON reorder_parameter_history (car_id, changed_at DESC);
@@ -0,0 +1,64 @@
+CREATE TYPE purchase_order_status AS ENUM ('Draft', 'Submitted', 'PartiallyReceived', 'Received', 'Cancelled');
+
+CREATE TABLE IF NOT EXISTS suppliers (
+    supplier_id VARCHAR(20) PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    contact_email VARCHAR(255),
+    lead_time_days INTEGER NOT NULL CHECK (lead_time_days > 0),
+    min_order_qty INTEGER NOT NULL DEFAULT 1 CHECK (min_order_qty > 0),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Per-car price list; lead time and minimum fall back to the supplier's.
+CREATE TABLE IF NOT EXISTS supplier_prices (
+    supplier_id VARCHAR(20) NOT NULL REFERENCES suppliers(supplier_id),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    unit_cost DECIMAL(15, 2) NOT NULL CHECK (unit_cost >= 0),
+    min_order_qty INTEGER CHECK (min_order_qty > 0),
+    lead_time_days INTEGER CHECK (lead_time_days > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (supplier_id, car_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_supplier_prices_car ON supplier_prices (car_id);
+
+CREATE TABLE IF NOT EXISTS purchase_orders (
+    po_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    supplier_id VARCHAR(20) NOT NULL REFERENCES suppliers(supplier_id),
+    warehouse_id VARCHAR(20) NOT NULL REFERENCES warehouses(warehouse_id),
+    status purchase_order_status NOT NULL DEFAULT 'Draft',
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    submitted_at TIMESTAMPTZ,
+    expected_at TIMESTAMPTZ,
+    received_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_purchase_orders_status ON purchase_orders (status, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_purchase_orders_supplier ON purchase_orders (supplier_id);
+
+CREATE TABLE IF NOT EXISTS purchase_order_lines (
+    line_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    po_id UUID NOT NULL REFERENCES purchase_orders(po_id) ON DELETE CASCADE,
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    unit_cost DECIMAL(15, 2) NOT NULL CHECK (unit_cost >= 0),
+    UNIQUE (po_id, car_id),
+    CONSTRAINT check_received_quantity CHECK (received_quantity BETWEEN 0 AND quantity)
+);
+
+CREATE INDEX IF NOT EXISTS idx_purchase_order_lines_car ON purchase_order_lines (car_id);
+
+CREATE TABLE IF NOT EXISTS goods_receipts (
+    receipt_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    po_id UUID NOT NULL REFERENCES purchase_orders(po_id),
+    warehouse_id VARCHAR(20) NOT NULL REFERENCES warehouses(warehouse_id),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_goods_receipts_po ON goods_receipts (po_id, received_at);
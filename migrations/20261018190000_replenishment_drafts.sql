@@ -0,0 +1,10 @@
+ALTER TABLE purchase_orders
+    ADD COLUMN IF NOT EXISTS origin VARCHAR(20) NOT NULL DEFAULT 'manual';
+
+ALTER TABLE purchase_order_lines
+    ADD COLUMN IF NOT EXISTS note TEXT;
+
+-- The replenishment job keeps adding to one open draft per supplier and warehouse.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_purchase_orders_open_replenishment
+    ON purchase_orders (supplier_id, warehouse_id)
+    WHERE status = 'Draft' AND origin = 'replenishment';
@@ -0,0 +1,20 @@
+CREATE TYPE rebalancing_status AS ENUM ('Proposed', 'Accepted', 'Rejected', 'Superseded');
+
+CREATE TABLE IF NOT EXISTS rebalancing_proposals (
+    proposal_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    from_warehouse_id VARCHAR(20) NOT NULL REFERENCES warehouses(warehouse_id),
+    to_warehouse_id VARCHAR(20) NOT NULL REFERENCES warehouses(warehouse_id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    distance_km DOUBLE PRECISION,
+    destination_cover_days DOUBLE PRECISION NOT NULL,
+    destination_cover_days_after DOUBLE PRECISION NOT NULL,
+    status rebalancing_status NOT NULL DEFAULT 'Proposed',
+    transfer_id UUID REFERENCES transfer_orders(transfer_id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    decided_at TIMESTAMPTZ,
+    CONSTRAINT check_rebalancing_different_warehouses CHECK (from_warehouse_id != to_warehouse_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rebalancing_proposals_status
+    ON rebalancing_proposals (status, created_at DESC);
@@ -0,0 +1,25 @@
+CREATE TYPE abc_class AS ENUM ('A', 'B', 'C');
+CREATE TYPE xyz_class AS ENUM ('X', 'Y', 'Z');
+
+ALTER TABLE cars
+    ADD COLUMN IF NOT EXISTS abc_class abc_class,
+    ADD COLUMN IF NOT EXISTS xyz_class xyz_class,
+    ADD COLUMN IF NOT EXISTS classification_updated_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_cars_classification
+    ON cars (abc_class, xyz_class)
+    WHERE deleted_at IS NULL;
+
+-- Stock alert levels as multiples of the reorder point, per ABC class.
+-- Unclassified cars keep the historical 1.0 / 1.5.
+CREATE TABLE IF NOT EXISTS alert_thresholds (
+    abc_class abc_class PRIMARY KEY,
+    critical_ratio DOUBLE PRECISION NOT NULL CHECK (critical_ratio > 0),
+    warning_ratio DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT check_warning_ratio CHECK (warning_ratio >= critical_ratio)
+);
+
+INSERT INTO alert_thresholds (abc_class, critical_ratio, warning_ratio)
+VALUES ('A', 1.0, 1.5), ('B', 1.0, 1.5), ('C', 1.0, 1.5)
+ON CONFLICT (abc_class) DO NOTHING;
@@ -0,0 +1,3 @@
+-- Odometer reading used by the depreciation engine; NULL when unknown.
+ALTER TABLE cars
+    ADD COLUMN IF NOT EXISTS mileage_km INTEGER CHECK (mileage_km >= 0);
@@ -0,0 +1,56 @@
+CREATE TYPE price_change_status AS ENUM ('Pending', 'Applied', 'Cancelled');
+
+CREATE TABLE IF NOT EXISTS scheduled_price_changes (
+    change_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    new_price DECIMAL(12, 2) NOT NULL CHECK (new_price > 0),
+    effective_at TIMESTAMPTZ NOT NULL,
+    reason VARCHAR(200),
+    status price_change_status NOT NULL DEFAULT 'Pending',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    applied_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_scheduled_price_changes_due
+    ON scheduled_price_changes (effective_at)
+    WHERE status = 'Pending';
+CREATE INDEX IF NOT EXISTS idx_scheduled_price_changes_car
+    ON scheduled_price_changes (car_id, effective_at);
+
+CREATE TABLE IF NOT EXISTS price_history (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    car_id VARCHAR(20) NOT NULL REFERENCES cars(car_id),
+    old_price DECIMAL(12, 2) NOT NULL,
+    new_price DECIMAL(12, 2) NOT NULL,
+    source VARCHAR(20) NOT NULL,
+    scheduled_change_id UUID REFERENCES scheduled_price_changes(change_id),
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_price_history_car ON price_history (car_id, changed_at);
+
+-- Every price change lands here, whichever statement made it. The scheduled
+-- price job sets app.scheduled_price_change_id for its transaction so those
+-- rows can be told apart from manual edits.
+CREATE OR REPLACE FUNCTION record_car_price_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    change_id UUID := NULLIF(current_setting('app.scheduled_price_change_id', true), '')::UUID;
+BEGIN
+    INSERT INTO price_history (car_id, old_price, new_price, source, scheduled_change_id)
+    VALUES (
+        NEW.car_id,
+        OLD.price,
+        NEW.price,
+        CASE WHEN change_id IS NULL THEN 'manual' ELSE 'scheduled' END,
+        change_id
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE 'plpgsql';
+
+CREATE TRIGGER cars_price_history_trigger
+    AFTER UPDATE OF price ON cars
+    FOR EACH ROW
+    WHEN (OLD.price IS DISTINCT FROM NEW.price)
+    EXECUTE FUNCTION record_car_price_change();
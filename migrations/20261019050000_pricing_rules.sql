@@ -0,0 +1,47 @@
+CREATE TABLE IF NOT EXISTS pricing_rules (
+    rule_id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name VARCHAR(100) NOT NULL,
+    -- Rules with a code are promotions and only apply when the code is given.
+    promo_code VARCHAR(40),
+    brand VARCHAR(50),
+    engine_type engine_type,
+    min_year INTEGER,
+    max_year INTEGER,
+    aging_bucket VARCHAR(10) CHECK (aging_bucket IN ('0-30', '31-60', '61-90', '90+')),
+    min_quantity INTEGER CHECK (min_quantity > 0),
+    discount_percent DOUBLE PRECISION CHECK (discount_percent > 0 AND discount_percent <= 100),
+    discount_amount DECIMAL(12, 2) CHECK (discount_amount > 0),
+    floor_price DECIMAL(12, 2) CHECK (floor_price > 0),
+    stackable BOOLEAN NOT NULL DEFAULT true,
+    priority INTEGER NOT NULL DEFAULT 0,
+    starts_at TIMESTAMPTZ,
+    ends_at TIMESTAMPTZ,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT check_pricing_rule_discount
+        CHECK ((discount_percent IS NULL) <> (discount_amount IS NULL)),
+    CONSTRAINT check_pricing_rule_years
+        CHECK (min_year IS NULL OR max_year IS NULL OR min_year <= max_year),
+    CONSTRAINT check_pricing_rule_window
+        CHECK (starts_at IS NULL OR ends_at IS NULL OR starts_at < ends_at)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_pricing_rules_promo_code
+    ON pricing_rules (promo_code)
+    WHERE promo_code IS NOT NULL AND is_active;
+CREATE INDEX IF NOT EXISTS idx_pricing_rules_active
+    ON pricing_rules (priority DESC)
+    WHERE is_active;
+
+ALTER TABLE sales_history
+    ADD COLUMN IF NOT EXISTS list_price DECIMAL(15, 2),
+    ADD COLUMN IF NOT EXISTS promo_code VARCHAR(40);
+
+CREATE TABLE IF NOT EXISTS sale_price_adjustments (
+    sale_id UUID NOT NULL REFERENCES sales_history(id),
+    position SMALLINT NOT NULL,
+    rule_id UUID NOT NULL REFERENCES pricing_rules(rule_id),
+    rule_name VARCHAR(100) NOT NULL,
+    unit_discount DECIMAL(12, 2) NOT NULL,
+    PRIMARY KEY (sale_id, position)
+);
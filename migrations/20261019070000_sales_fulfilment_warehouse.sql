@@ -0,0 +1,7 @@
+-- Warehouse the sold units were taken from. NULL for sales recorded before
+-- fulfilment was tracked, or when no location had the car available.
+ALTER TABLE sales_history
+    ADD COLUMN IF NOT EXISTS warehouse_id VARCHAR(20) REFERENCES warehouses(warehouse_id);
+
+CREATE INDEX IF NOT EXISTS idx_sales_history_warehouse
+    ON sales_history (car_id, warehouse_id, sold_at);
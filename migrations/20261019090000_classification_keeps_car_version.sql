@@ -0,0 +1,16 @@
+-- Classes are derived data refreshed by the inventory_classification job.
+-- Writing them must not bump `version` / `updated_at`, or clients using
+-- optimistic concurrency would see conflicts they never caused.
+CREATE OR REPLACE FUNCTION increment_car_version()
+    RETURNS TRIGGER AS $$
+    DECLARE
+        derived TEXT[] := ARRAY['abc_class', 'xyz_class', 'classification_updated_at'];
+    BEGIN
+        IF to_jsonb(NEW) - derived = to_jsonb(OLD) - derived THEN
+            RETURN NEW;
+        END IF;
+        NEW.version = OLD.version + 1;
+        NEW.updated_at = NOW();
+        RETURN NEW;
+    END;
+    $$ language 'plpgsql';
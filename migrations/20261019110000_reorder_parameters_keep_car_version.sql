@@ -0,0 +1,18 @@
+-- Reorder parameters recomputed by the reorder_planning job are derived data
+-- too. Manual overrides still bump the version.
+CREATE OR REPLACE FUNCTION increment_car_version()
+    RETURNS TRIGGER AS $$
+    DECLARE
+        derived TEXT[] := ARRAY[
+            'abc_class', 'xyz_class', 'classification_updated_at',
+            'reorder_point', 'economic_order_qty', 'safety_stock', 'reorder_params_updated_at'
+        ];
+    BEGIN
+        IF to_jsonb(NEW) - derived = to_jsonb(OLD) - derived THEN
+            RETURN NEW;
+        END IF;
+        NEW.version = OLD.version + 1;
+        NEW.updated_at = NOW();
+        RETURN NEW;
+    END;
+    $$ language 'plpgsql';
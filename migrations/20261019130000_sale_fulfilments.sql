@@ -0,0 +1,21 @@
+-- A sale can be taken from several warehouses when no single one holds
+-- enough free stock. Each part is recorded here; sales without rows predate
+-- fulfilment tracking or are for cars not held in any warehouse.
+CREATE TABLE IF NOT EXISTS sale_fulfilments (
+    sale_id UUID NOT NULL REFERENCES sales_history(id),
+    warehouse_id VARCHAR(20) NOT NULL REFERENCES warehouses(warehouse_id),
+    quantity INTEGER NOT NULL CHECK (quantity > 0),
+    PRIMARY KEY (sale_id, warehouse_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sale_fulfilments_warehouse
+    ON sale_fulfilments (warehouse_id);
+
+INSERT INTO sale_fulfilments (sale_id, warehouse_id, quantity)
+SELECT id, warehouse_id, quantity
+FROM sales_history
+WHERE warehouse_id IS NOT NULL AND quantity > 0
+ON CONFLICT DO NOTHING;
+
+DROP INDEX IF EXISTS idx_sales_history_warehouse;
+ALTER TABLE sales_history DROP COLUMN IF EXISTS warehouse_id;
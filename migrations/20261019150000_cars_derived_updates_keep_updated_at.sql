@@ -0,0 +1,4 @@
+-- increment_car_version() already stamps updated_at on every change that
+-- bumps the version. update_cars_modtime ran after it (triggers fire in name
+-- order) and stamped updated_at even on writes of derived columns only.
+DROP TRIGGER IF EXISTS update_cars_modtime ON cars;
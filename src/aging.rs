@@ -0,0 +1,322 @@
+use bigdecimal::{BigDecimal, FromPrimitive, Zero};
+use chrono::{DateTime, Utc};
+
+use crate::config::AgingConfig;
+use crate::models::{
+    ActionType, AgingBucketStock, AgingInput, CarAging, SlowMover, SuggestedAction,
+};
+
+/// Labels and lower bounds in days of the aging buckets, youngest first.
+const AGING_BUCKETS: [(&str, i64); 4] = [("0-30", 0), ("31-60", 31), ("61-90", 61), ("90+", 91)];
+
+const DAYS_PER_YEAR: i64 = 365;
+
+/// Units on hand assumed to be the newest receipts, since older units sell
+/// first. Anything not covered by a goods receipt dates from `stocked_since`.
+fn layers(input: &AgingInput) -> Vec<(DateTime<Utc>, i64)> {
+    let mut remaining = input.units_on_hand.max(0) as i64;
+    let mut layers = Vec::new();
+
+    for (received_at, quantity) in input.receipt_dates.iter().zip(&input.receipt_quantities) {
+        if remaining == 0 {
+            break;
+        }
+        let units = remaining.min(*quantity as i64);
+        layers.push((*received_at, units));
+        remaining -= units;
+    }
+    if remaining > 0 {
+        layers.push((input.stocked_since, remaining));
+    }
+
+    layers
+}
+
+fn bucket_index(days: i64) -> usize {
+    AGING_BUCKETS
+        .iter()
+        .rposition(|(_, min_days)| days >= *min_days)
+        .unwrap_or(0)
+}
+
+/// Label of the aging bucket `days` in stock falls into.
+pub fn bucket_label(days: i64) -> &'static str {
+    AGING_BUCKETS[bucket_index(days)].0
+}
+
+pub fn is_bucket_label(label: &str) -> bool {
+    AGING_BUCKETS.iter().any(|(bucket, _)| *bucket == label)
+}
+
+/// Days in stock of the oldest unit on hand; zero without stock.
+pub fn oldest_unit_days(input: &AgingInput, now: DateTime<Utc>) -> i64 {
+    layers(input)
+        .into_iter()
+        .map(|(received_at, _)| (now - received_at).num_days().max(0))
+        .max()
+        .unwrap_or(0)
+}
+
+fn empty_buckets() -> Vec<AgingBucketStock> {
+    AGING_BUCKETS
+        .iter()
+        .map(|(label, _)| AgingBucketStock {
+            bucket: label.to_string(),
+            units: 0,
+            inventory_value: BigDecimal::zero(),
+            holding_cost: BigDecimal::zero(),
+        })
+        .collect()
+}
+
+/// Days in stock, bucket totals and accrued holding cost of one car.
+/// Holding cost accrues at `holding_cost_rate` of the unit price per year.
+pub fn age_car(
+    input: AgingInput,
+    now: DateTime<Utc>,
+    holding_cost_rate: f64,
+    sales_days: u32,
+) -> CarAging {
+    let rate = BigDecimal::from_f64(holding_cost_rate).unwrap_or_else(BigDecimal::zero);
+    let mut buckets = empty_buckets();
+    let mut total_units = 0i64;
+    let mut unit_days = 0i64;
+    let mut oldest_unit_days = 0i64;
+
+    for (received_at, units) in layers(&input) {
+        let days = (now - received_at).num_days().max(0);
+        let bucket = &mut buckets[bucket_index(days)];
+        let value = &input.price * BigDecimal::from(units);
+
+        bucket.holding_cost +=
+            &value * &rate * BigDecimal::from(days) / BigDecimal::from(DAYS_PER_YEAR);
+        bucket.inventory_value += value;
+        bucket.units += units;
+
+        total_units += units;
+        unit_days += units * days;
+        oldest_unit_days = oldest_unit_days.max(days);
+    }
+
+    for bucket in &mut buckets {
+        bucket.holding_cost = bucket.holding_cost.round(2).with_scale(2);
+    }
+
+    let avg_daily_sales = input.units_sold as f64 / sales_days as f64;
+    CarAging {
+        car_id: input.car_id,
+        brand: input.brand,
+        model: input.model,
+        units_on_hand: input.units_on_hand,
+        oldest_unit_days,
+        avg_days_in_stock: if total_units > 0 {
+            unit_days as f64 / total_units as f64
+        } else {
+            0.0
+        },
+        avg_daily_sales,
+        days_of_supply: (avg_daily_sales > 0.0).then(|| total_units as f64 / avg_daily_sales),
+        holding_cost: buckets.iter().map(|b| &b.holding_cost).sum(),
+        buckets,
+    }
+}
+
+/// Sums the per-car buckets into inventory-wide totals.
+pub fn total_buckets(cars: &[CarAging]) -> Vec<AgingBucketStock> {
+    let mut totals = empty_buckets();
+    for car in cars {
+        for (total, bucket) in totals.iter_mut().zip(&car.buckets) {
+            total.units += bucket.units;
+            total.inventory_value += &bucket.inventory_value;
+            total.holding_cost += &bucket.holding_cost;
+        }
+    }
+    totals
+}
+
+/// Cars whose oldest unit is at least `slow_mover_age_days` old and whose
+/// stock lasts at least `slow_mover_supply_days` at current sales, costliest
+/// to hold first. Each gets a `MarketingPush` suggestion.
+pub fn slow_movers(cars: Vec<CarAging>, config: &AgingConfig) -> Vec<SlowMover> {
+    let mut slow: Vec<SlowMover> = cars
+        .into_iter()
+        .filter(|car| {
+            car.oldest_unit_days >= config.slow_mover_age_days as i64
+                && car
+                    .days_of_supply
+                    .is_none_or(|days| days >= config.slow_mover_supply_days as f64)
+        })
+        .map(|aging| {
+            let suggested_action = marketing_push(&aging, config);
+            SlowMover {
+                aging,
+                suggested_action,
+            }
+        })
+        .collect();
+
+    slow.sort_by(|a, b| b.aging.holding_cost.cmp(&a.aging.holding_cost));
+    slow
+}
+
+fn marketing_push(car: &CarAging, config: &AgingConfig) -> SuggestedAction {
+    let supply = match car.days_of_supply {
+        Some(days) => format!("{days:.0} days of supply"),
+        None => format!("no sales in the last {} days", config.sales_days),
+    };
+    let over_90_days = car.buckets.last().map_or(0, |bucket| bucket.units);
+
+    SuggestedAction {
+        action_type: ActionType::MarketingPush,
+        description: format!(
+            "Oldest of {} units in stock for {} days with {supply} and {} holding cost accrued, \
+             promote before reordering",
+            car.units_on_hand, car.oldest_unit_days, car.holding_cost
+        ),
+        priority: if over_90_days * 2 >= car.units_on_hand as i64 {
+            2
+        } else {
+            3
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use crate::models::CarId;
+
+    use super::*;
+
+    const SALES_DAYS: u32 = 30;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn days_ago(days: i64) -> DateTime<Utc> {
+        now() - Duration::days(days)
+    }
+
+    /// `receipts` are `(days ago, quantity)`, newest first.
+    fn input(
+        car_id: &str,
+        units_on_hand: i32,
+        receipts: &[(i64, i32)],
+        stocked_days_ago: i64,
+        units_sold: i64,
+    ) -> AgingInput {
+        AgingInput {
+            car_id: CarId::new(car_id.to_string()).unwrap(),
+            brand: "Toyota".to_string(),
+            model: "Corolla".to_string(),
+            price: BigDecimal::from(10_000),
+            units_on_hand,
+            stocked_since: days_ago(stocked_days_ago),
+            receipt_dates: receipts.iter().map(|(days, _)| days_ago(*days)).collect(),
+            receipt_quantities: receipts.iter().map(|(_, quantity)| *quantity).collect(),
+            units_sold,
+        }
+    }
+
+    fn bucket_units(car: &CarAging) -> Vec<i64> {
+        car.buckets.iter().map(|bucket| bucket.units).collect()
+    }
+
+    fn dec(value: &str) -> BigDecimal {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn units_on_hand_come_from_the_newest_receipts() {
+        let receipts = [(10, 4), (40, 5)];
+
+        let partial = input("C0001", 7, &receipts, 200, 0);
+        assert_eq!(layers(&partial), [(days_ago(10), 4), (days_ago(40), 3)]);
+        assert_eq!(oldest_unit_days(&partial, now()), 40);
+
+        // Two units more than the receipts cover date from `stocked_since`.
+        let beyond = input("C0001", 11, &receipts, 200, 0);
+        assert_eq!(
+            layers(&beyond),
+            [(days_ago(10), 4), (days_ago(40), 5), (days_ago(200), 2)]
+        );
+
+        let empty = input("C0001", 0, &receipts, 200, 0);
+        assert!(layers(&empty).is_empty());
+        assert_eq!(oldest_unit_days(&empty, now()), 0);
+    }
+
+    #[test]
+    fn bucket_edges() {
+        let labels: Vec<_> = [-1, 0, 30, 31, 60, 61, 90, 91, 400]
+            .into_iter()
+            .map(bucket_label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "0-30", "0-30", "0-30", "31-60", "31-60", "61-90", "61-90", "90+", "90+"
+            ]
+        );
+
+        let car = age_car(
+            input("C0001", 3, &[(90, 1), (91, 1)], 30, 0),
+            now(),
+            0.0,
+            SALES_DAYS,
+        );
+        assert_eq!(bucket_units(&car), [1, 0, 1, 1]);
+        assert!(is_bucket_label("90+") && !is_bucket_label("90-120"));
+    }
+
+    #[test]
+    fn holding_cost_accrues_per_day_and_rounds_to_cents() {
+        // 2 units of 10,000 at 25% a year for 73 days (a fifth of a year).
+        let car = age_car(input("C0001", 2, &[], 73, 0), now(), 0.25, SALES_DAYS);
+        assert_eq!(car.buckets[2].holding_cost, dec("1000.00"));
+        assert_eq!(car.buckets[2].inventory_value, dec("20000"));
+        assert_eq!(car.holding_cost, dec("1000.00"));
+
+        // 10,000 * 0.2 / 365 = 5.479... a day, per bucket then summed.
+        let car = age_car(input("C0001", 2, &[(1, 1)], 35, 0), now(), 0.2, SALES_DAYS);
+        assert_eq!(car.buckets[0].holding_cost, dec("5.48"));
+        assert_eq!(car.buckets[1].holding_cost, dec("191.78"));
+        assert_eq!(car.holding_cost, dec("197.26"));
+        assert_eq!(car.avg_days_in_stock, 18.0);
+    }
+
+    #[test]
+    fn slow_movers_are_old_and_slow_selling_costliest_first() {
+        let config = AgingConfig::default();
+        let cars = [
+            // Old and unsold: qualifies without days of supply.
+            input("C0001", 2, &[], 120, 0),
+            // Old but 30 days of supply.
+            input("C0002", 10, &[], 150, 10),
+            // Not old enough.
+            input("C0003", 10, &[], 60, 0),
+            // Old, 300 days of supply, more units, so costlier to hold.
+            input("C0004", 10, &[(20, 6)], 100, 1),
+        ]
+        .into_iter()
+        .map(|input| age_car(input, now(), 0.25, config.sales_days))
+        .collect();
+
+        let slow = slow_movers(cars, &config);
+
+        let ids: Vec<_> = slow.iter().map(|s| s.aging.car_id.as_str()).collect();
+        assert_eq!(ids, ["C0004", "C0001"]);
+        assert!(slow[0].aging.holding_cost > slow[1].aging.holding_cost);
+        // 4 of 10 units past 90 days, against all of C0001's.
+        assert_eq!(slow[0].suggested_action.priority, 3);
+        assert_eq!(slow[1].suggested_action.priority, 2);
+        assert!(
+            slow[1]
+                .suggested_action
+                .description
+                .contains("no sales in the last 30 days")
+        );
+    }
+}
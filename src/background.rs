@@ -1,5 +1,25 @@
-use sqlx::PgPool;
-use tokio::time::{Duration, Instant, interval};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bigdecimal::{BigDecimal, ToPrimitive};
+use tokio::time::{Duration, Instant};
+
+use crate::cache::QueryCache;
+use crate::config::ReorderPlanningConfig;
+use crate::error::{AppError, AppResult};
+use crate::forecasting;
+use crate::models::{
+    AlertLevel, CarId, NewPurchaseOrderLine, REORDER_SOURCE_JOB, ReorderParameterUpdate,
+    ReorderPlanningInput, ReplenishmentCandidate, StockAlert, SupplierId, WarehouseId,
+};
+use crate::pool_manager::PoolHandle;
+use crate::repositories::{
+    InventoryAnalyticsRepository, PgPurchasingRepository, PgReorderRepository,
+    PurchasingRepository, ReorderRepository,
+};
+use crate::scheduler::{Job, JobContext};
+use crate::services::{ClassificationService, InventoryAnalyticsService, PriceHistoryService};
 
 #[derive(Debug, Clone)]
 pub struct BatchConfig {
@@ -44,111 +64,34 @@ impl BatchConfig {
     }
 }
 
-pub struct BackgroundWorker {
-    pool: PgPool,
-    interval_secs: u64,
-    shutdown_rx: tokio::sync::watch::Receiver<bool>,
+/// Expires overdue pending reservations in batches and returns their units to
+/// stock.
+pub struct ExpiredReservationsJob {
+    pool: PoolHandle,
     batch_config: BatchConfig,
 }
 
-pub struct BatchMetrics {
-    pub total_processed: u64,
-    pub total_batches: u64,
-    pub avg_batch_duration_ms: f64,
-    pub throughput_per_second: f64,
-    pub errors: u64,
-    pub last_batch_size: usize,
-}
-
-impl BackgroundWorker {
-    pub fn new(
-        pool: PgPool,
-        interval_secs: u64,
-        shutdown_rx: tokio::sync::watch::Receiver<bool>,
-    ) -> Self {
-        Self {
-            pool,
-            interval_secs,
-            shutdown_rx,
-            batch_config: BatchConfig::default(),
-        }
+impl ExpiredReservationsJob {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self::with_config(pool, BatchConfig::default())
     }
 
-    pub fn with_config(
-        pool: PgPool,
-        interval_secs: u64,
-        shutdown_rx: tokio::sync::watch::Receiver<bool>,
-        batch_config: BatchConfig,
-    ) -> Self {
-        Self {
-            pool,
-            interval_secs,
-            shutdown_rx,
-            batch_config,
-        }
+    pub fn with_config(pool: PoolHandle, batch_config: BatchConfig) -> Self {
+        Self { pool, batch_config }
     }
 
-    pub async fn start(mut self) {
-        let mut interval = interval(Duration::from_secs(self.interval_secs));
-
-        tracing::info!(
-            worker = "background",
-            interval_secs = self.interval_secs,
-            batch_size = self.batch_config.batch_size,
-            max_concurrent = self.batch_config.max_concurrent_batches,
-            "Background worker started with batch processing"
-        );
-
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    let start = Instant::now();
-
-                    if let Err(e) = self.process_expired_reservations_batched().await {
-                        tracing::error!(
-                            error = %e,
-                            task = "expired_reservations",
-                            "Background task failed"
-                        );
-
-                        self.apply_backoff(1).await;
-                    } else {
-                        let elapsed = start.elapsed();
-                        tracing::info!(
-                            task = "expired_reservations",
-                            elapsed_ms = elapsed.as_millis(),
-                            "Batch processing cycle completed"
-                        );
-                    }
-
-                    if let Err(e) = self.update_inventory_metrics().await {
-                        tracing::error!(
-                            error = %e,
-                            task = "inventory_metrics",
-                            "Background task failed"
-                        );
-                    }
-                }
-                _ = self.shutdown_rx.changed() => {
-                    if *self.shutdown_rx.borrow() {
-                        tracing::info!("Background worker received shutdown signal");
-                        break;
-                    }
-                }
-            }
-        }
-
-        tracing::info!("Background worker stopped gracefully");
+    pub fn batch_config(&self) -> &BatchConfig {
+        &self.batch_config
     }
 
-    async fn process_expired_reservations_batched(&self) -> Result<(), crate::error::AppError> {
+    async fn process_expired_reservations_batched(&self, ctx: &JobContext) -> AppResult<()> {
         let start = Instant::now();
         let mut total_processed: u64 = 0;
         let mut total_batches: u64 = 0;
         let mut consecutive_errors: u32 = 0;
 
         loop {
-            if *self.shutdown_rx.borrow() {
+            if ctx.is_shutting_down() {
                 tracing::info!(
                     processed = total_processed,
                     batches = total_batches,
@@ -165,6 +108,7 @@ impl BackgroundWorker {
                     consecutive_errors = 0;
                     total_processed += processed as u64;
                     total_batches += 1;
+                    ctx.record_processed(processed as u64);
 
                     let batch_duration = batch_start.elapsed();
 
@@ -244,7 +188,7 @@ impl BackgroundWorker {
         Ok(())
     }
 
-    async fn process_single_batch(&self) -> Result<(usize, bool), crate::error::AppError> {
+    async fn process_single_batch(&self) -> AppResult<(usize, bool)> {
         let batch_size = self.batch_config.batch_size as i64;
 
         let tx_result = tokio::time::timeout(
@@ -293,7 +237,7 @@ impl BackgroundWorker {
                 "#,
                 batch_size
             )
-            .fetch_one(&self.pool),
+            .fetch_one(&self.pool.get()),
         )
         .await;
 
@@ -305,11 +249,11 @@ impl BackgroundWorker {
             }
             Ok(Err(e)) => {
                 tracing::error!(error = %e, "Database error in batch transaction");
-                Err(crate::error::AppError::DatabaseError(e))
+                Err(AppError::DatabaseError(e))
             }
             Err(_) => {
                 tracing::error!("Batch transaction timeout (>10s)");
-                Err(crate::error::AppError::ServiceUnavailable)
+                Err(AppError::ServiceUnavailable { retry_after: None })
             }
         }
     }
@@ -330,11 +274,42 @@ impl BackgroundWorker {
 
         tokio::time::sleep(Duration::from_millis(delay_ms)).await;
     }
+}
+
+#[async_trait]
+impl Job for ExpiredReservationsJob {
+    fn name(&self) -> &'static str {
+        "expired_reservations"
+    }
 
-    async fn update_inventory_metrics(&self) -> Result<(), crate::error::AppError> {
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        self.process_expired_reservations_batched(ctx).await
+    }
+}
+
+/// Snapshots stock and reservation totals into `inventory_metrics_history`
+/// and refreshes the inventory gauges.
+pub struct InventoryMetricsJob {
+    pool: PoolHandle,
+}
+
+impl InventoryMetricsJob {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the number of cars in the snapshot.
+    async fn update_inventory_metrics(&self) -> AppResult<u64> {
         let start = Instant::now();
 
-        sqlx::query(
+        let (total_cars, total_value, active_reservations, reserved_units, low_stock_count, available_value): (
+            i64,
+            BigDecimal,
+            i64,
+            i64,
+            i64,
+            BigDecimal,
+        ) = sqlx::query_as(
             r#"
             INSERT INTO inventory_metrics_history (
                 metric_hour, total_cars, total_value, active_reservations,
@@ -365,11 +340,21 @@ impl BackgroundWorker {
                 low_stock_count = EXCLUDED.low_stock_count,
                 available_stock_value = EXCLUDED.available_stock_value,
                 updated_at = NOW()
+            RETURNING total_cars, total_value, active_reservations,
+                reserved_units, low_stock_count, available_stock_value
             "#
         )
-        .execute(&self.pool)
+        .fetch_one(&self.pool.get())
         .await
-        .map_err(crate::error::AppError::DatabaseError)?;
+        .map_err(AppError::DatabaseError)?;
+
+        metrics::gauge!("inventory_total_cars").set(total_cars as f64);
+        metrics::gauge!("inventory_stock_value").set(total_value.to_f64().unwrap_or(0.0));
+        metrics::gauge!("inventory_available_stock_value")
+            .set(available_value.to_f64().unwrap_or(0.0));
+        metrics::gauge!("inventory_active_reservations").set(active_reservations as f64);
+        metrics::gauge!("inventory_reserved_units").set(reserved_units as f64);
+        metrics::gauge!("inventory_low_stock_items").set(low_stock_count as f64);
 
         let elapsed = start.elapsed();
         tracing::debug!(
@@ -377,10 +362,331 @@ impl BackgroundWorker {
             "Inventory metrics history updated"
         );
 
+        Ok(total_cars as u64)
+    }
+}
+
+#[async_trait]
+impl Job for InventoryMetricsJob {
+    fn name(&self) -> &'static str {
+        "inventory_metrics"
+    }
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        let total_cars = self.update_inventory_metrics().await?;
+        ctx.record_processed(total_cars);
         Ok(())
     }
+}
 
-    pub fn batch_config(&self) -> &BatchConfig {
-        &self.batch_config
+/// Recomputes safety stock, reorder point and EOQ for every active car from
+/// its recent demand. Manual overrides win over the computed values.
+pub struct ReorderPlanningJob {
+    reorder_repo: Arc<dyn ReorderRepository>,
+    analytics_repo: Arc<dyn InventoryAnalyticsRepository>,
+    cache: QueryCache,
+    config: ReorderPlanningConfig,
+}
+
+const REORDER_PLANNING_CHUNK: usize = 200;
+
+impl ReorderPlanningJob {
+    pub fn new(
+        pool: PoolHandle,
+        analytics_repo: Arc<dyn InventoryAnalyticsRepository>,
+        cache: QueryCache,
+        config: ReorderPlanningConfig,
+    ) -> Self {
+        Self {
+            reorder_repo: Arc::new(PgReorderRepository::new(pool)),
+            analytics_repo,
+            cache,
+            config,
+        }
+    }
+
+    fn plan(&self, input: &ReorderPlanningInput, history: &[f64]) -> ReorderParameterUpdate {
+        let lead_time_days = input
+            .lead_time_days
+            .map(|days| days as u32)
+            .unwrap_or(self.config.default_lead_time_days);
+        let plan =
+            forecasting::plan_reorder(history, lead_time_days, input.unit_price, &self.config);
+
+        ReorderParameterUpdate {
+            car_id: input.car_id.clone(),
+            reorder_point: input.reorder_point_override.unwrap_or(plan.reorder_point),
+            economic_order_qty: input
+                .economic_order_qty_override
+                .unwrap_or(plan.economic_order_qty),
+            safety_stock: plan.safety_stock,
+            avg_daily_demand: Some(plan.avg_daily_demand),
+            demand_std_dev: Some(plan.demand_std_dev),
+            lead_time_days: Some(lead_time_days as i32),
+            source: REORDER_SOURCE_JOB,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for ReorderPlanningJob {
+    fn name(&self) -> &'static str {
+        "reorder_planning"
+    }
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        let inputs = self
+            .reorder_repo
+            .list_planning_inputs()
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let mut changed = 0u64;
+        for chunk in inputs.chunks(REORDER_PLANNING_CHUNK) {
+            if ctx.is_shutting_down() {
+                tracing::info!(changed, "Shutdown requested, stopping reorder planning");
+                break;
+            }
+
+            let car_ids: Vec<CarId> = chunk.iter().map(|input| input.car_id.clone()).collect();
+            let mut demand = forecasting::demand_by_car(
+                self.analytics_repo
+                    .get_daily_sales(&car_ids, self.config.history_days as i32)
+                    .await
+                    .map_err(AppError::DatabaseError)?,
+            );
+
+            for input in chunk {
+                let history = demand.remove(input.car_id.as_str()).unwrap_or_default();
+                let update = self.plan(input, &history);
+
+                if self
+                    .reorder_repo
+                    .apply_parameters(&update)
+                    .await
+                    .map_err(AppError::DatabaseError)?
+                {
+                    changed += 1;
+                    ctx.record_processed(1);
+                }
+            }
+        }
+
+        tracing::info!(
+            cars = inputs.len(),
+            changed,
+            "Reorder parameters recalculated"
+        );
+
+        // Cached cars carry the old reorder point and EOQ.
+        if changed > 0 {
+            self.cache.invalidate_all_cars().await;
+        }
+        Ok(())
+    }
+}
+
+/// Turns critical stock alerts into draft purchase order lines, one open
+/// draft per supplier and receiving warehouse. A car is skipped while its
+/// stock plus open orders and inbound transfers is above the reorder point.
+/// Drafts are only submitted once approved.
+pub struct ReplenishmentDraftJob {
+    analytics_service: Arc<InventoryAnalyticsService>,
+    purchasing_repo: Arc<dyn PurchasingRepository>,
+}
+
+impl ReplenishmentDraftJob {
+    pub fn new(pool: PoolHandle, analytics_service: Arc<InventoryAnalyticsService>) -> Self {
+        Self {
+            analytics_service,
+            purchasing_repo: Arc::new(PgPurchasingRepository::new(pool)),
+        }
+    }
+}
+
+/// EOQ rounded up to whole multiples of the supplier's minimum order, which
+/// doubles as its pack size.
+fn order_quantity(economic_order_qty: i32, min_order_qty: i32) -> i32 {
+    let pack = min_order_qty.max(1);
+    let packs = (economic_order_qty.max(1) + pack - 1) / pack;
+    packs * pack
+}
+
+#[async_trait]
+impl Job for ReplenishmentDraftJob {
+    fn name(&self) -> &'static str {
+        "replenishment_drafts"
+    }
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        let summary = self.analytics_service.get_stock_alerts().await?;
+        let critical: Vec<&StockAlert> = summary
+            .alerts
+            .iter()
+            .filter(|alert| matches!(alert.alert_level, AlertLevel::Critical))
+            .collect();
+
+        if critical.is_empty() {
+            tracing::info!("No critical stock alerts to replenish");
+            return Ok(());
+        }
+
+        let car_ids: Vec<CarId> = critical.iter().map(|alert| alert.car_id.clone()).collect();
+        let candidates: HashMap<String, ReplenishmentCandidate> = self
+            .purchasing_repo
+            .list_replenishment_candidates(&car_ids)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .into_iter()
+            .map(|candidate| (candidate.car_id.as_str().to_string(), candidate))
+            .collect();
+
+        let mut drafts: HashMap<(String, String), (SupplierId, WarehouseId, Vec<_>)> =
+            HashMap::new();
+        let (mut covered, mut without_supplier) = (0usize, 0usize);
+        for alert in &critical {
+            let Some(candidate) = candidates.get(alert.car_id.as_str()) else {
+                tracing::warn!(car_id = %alert.car_id, "No active supplier price, cannot replenish");
+                without_supplier += 1;
+                continue;
+            };
+
+            let inbound = candidate.on_order + candidate.in_transit;
+            if alert.current_stock as i64 + inbound > alert.reorder_point as i64 {
+                covered += 1;
+                continue;
+            }
+
+            let line = NewPurchaseOrderLine {
+                car_id: alert.car_id.clone(),
+                quantity: order_quantity(alert.economic_order_qty, candidate.min_order_qty),
+                unit_cost: Some(candidate.unit_cost.clone()),
+                note: Some(format!(
+                    "Critical alert: {} in stock, {} inbound, reorder point {}",
+                    alert.current_stock, inbound, alert.reorder_point
+                )),
+            };
+
+            drafts
+                .entry((
+                    candidate.supplier_id.as_str().to_string(),
+                    candidate.warehouse_id.as_str().to_string(),
+                ))
+                .or_insert_with(|| {
+                    (
+                        candidate.supplier_id.clone(),
+                        candidate.warehouse_id.clone(),
+                        Vec::new(),
+                    )
+                })
+                .2
+                .push(line);
+        }
+
+        let mut drafted = 0u64;
+        for (supplier_id, warehouse_id, lines) in drafts.values() {
+            if ctx.is_shutting_down() {
+                tracing::info!(
+                    drafted,
+                    "Shutdown requested, stopping replenishment drafting"
+                );
+                break;
+            }
+
+            let added = self
+                .purchasing_repo
+                .draft_replenishment_order(supplier_id, warehouse_id, lines)
+                .await
+                .map_err(AppError::DatabaseError)?;
+            drafted += added;
+            ctx.record_processed(added);
+        }
+
+        tracing::info!(
+            critical = critical.len(),
+            drafted,
+            covered,
+            without_supplier,
+            "Replenishment drafts updated"
+        );
+        Ok(())
+    }
+}
+
+/// Stores each car's ABC/XYZ class so listings can filter on it and stock
+/// alerts pick up the per-class thresholds.
+pub struct InventoryClassificationJob {
+    classification_service: Arc<ClassificationService>,
+}
+
+impl InventoryClassificationJob {
+    pub fn new(classification_service: Arc<ClassificationService>) -> Self {
+        Self {
+            classification_service,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for InventoryClassificationJob {
+    fn name(&self) -> &'static str {
+        "inventory_classification"
+    }
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        let (cars, changed) = self.classification_service.refresh_classes().await?;
+        ctx.record_processed(changed);
+
+        tracing::info!(cars, changed, "Inventory classes refreshed");
+        Ok(())
+    }
+}
+
+/// Applies scheduled price changes once their effective time has passed.
+pub struct ScheduledPriceChangesJob {
+    price_history_service: Arc<PriceHistoryService>,
+}
+
+impl ScheduledPriceChangesJob {
+    pub fn new(price_history_service: Arc<PriceHistoryService>) -> Self {
+        Self {
+            price_history_service,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for ScheduledPriceChangesJob {
+    fn name(&self) -> &'static str {
+        "scheduled_price_changes"
+    }
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()> {
+        let processed = self.price_history_service.apply_due_changes().await?;
+        ctx.record_processed(processed as u64);
+
+        if processed > 0 {
+            tracing::info!(processed, "Scheduled price changes applied");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_quantity_rounds_up_to_supplier_multiple() {
+        assert_eq!(order_quantity(7, 5), 10);
+        assert_eq!(order_quantity(10, 5), 10);
+        assert_eq!(order_quantity(11, 5), 15);
+    }
+
+    #[test]
+    fn order_quantity_orders_at_least_one_pack() {
+        assert_eq!(order_quantity(3, 12), 12);
+        assert_eq!(order_quantity(0, 4), 4);
+        assert_eq!(order_quantity(9, 1), 9);
     }
 }
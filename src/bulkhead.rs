@@ -0,0 +1,152 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tokio::sync::Semaphore;
+
+use crate::config::{BulkheadConfig, LoadSheddingConfig};
+use crate::error::AppError;
+use crate::pool_manager::PoolManager;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    Catalogue,
+    Reservations,
+    Analytics,
+}
+
+impl RouteGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Catalogue => "catalogue",
+            Self::Reservations => "reservations",
+            Self::Analytics => "analytics",
+        }
+    }
+
+    /// Multiple of the pool wait threshold at which this group starts shedding.
+    /// Analytics goes first so checkout keeps its connections longest.
+    fn shed_multiplier(&self) -> u128 {
+        match self {
+            Self::Analytics => 1,
+            Self::Catalogue => 2,
+            Self::Reservations => 4,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct LoadShedder {
+    pool_manager: Arc<PoolManager>,
+    config: LoadSheddingConfig,
+}
+
+impl LoadShedder {
+    pub fn new(pool_manager: Arc<PoolManager>, config: LoadSheddingConfig) -> Self {
+        Self {
+            pool_manager,
+            config,
+        }
+    }
+
+    async fn should_shed(&self, group: RouteGroup) -> Option<u128> {
+        let wait_time_ms = self.pool_manager.wait_time_ms().await;
+        let threshold = self.config.pool_wait_threshold_ms as u128 * group.shed_multiplier();
+
+        (wait_time_ms >= threshold).then_some(wait_time_ms)
+    }
+}
+
+pub struct Bulkhead {
+    group: RouteGroup,
+    permits: Semaphore,
+    max_concurrent: usize,
+    max_wait: Duration,
+    shedder: Option<LoadShedder>,
+    retry_after_secs: u64,
+}
+
+impl Bulkhead {
+    pub fn new(group: RouteGroup, config: &BulkheadConfig) -> Self {
+        Self {
+            group,
+            permits: Semaphore::new(config.max_concurrent),
+            max_concurrent: config.max_concurrent,
+            max_wait: config.max_wait(),
+            shedder: None,
+            retry_after_secs: 1,
+        }
+    }
+
+    pub fn with_load_shedding(mut self, shedder: LoadShedder) -> Self {
+        self.retry_after_secs = shedder.config.retry_after_secs;
+        self.shedder = Some(shedder);
+        self
+    }
+
+    pub fn group(&self) -> RouteGroup {
+        self.group
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent - self.permits.available_permits()
+    }
+
+    fn reject(&self, reason: &'static str) -> Response {
+        metrics::counter!(
+            "bulkhead_rejected_total",
+            "group" => self.group.as_str(),
+            "reason" => reason
+        )
+        .increment(1);
+
+        AppError::ServiceUnavailable {
+            retry_after: Some(self.retry_after_secs),
+        }
+        .into_response()
+    }
+}
+
+pub async fn bulkhead_middleware(
+    State(bulkhead): State<Arc<Bulkhead>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let group = bulkhead.group.as_str();
+
+    if let Some(shedder) = &bulkhead.shedder
+        && let Some(wait_time_ms) = shedder.should_shed(bulkhead.group).await
+    {
+        tracing::warn!(
+            group = group,
+            pool_wait_ms = wait_time_ms as u64,
+            threshold_ms = shedder.config.pool_wait_threshold_ms,
+            "Shedding request: database pool wait time above threshold"
+        );
+        return bulkhead.reject("load_shed");
+    }
+
+    let permit = match tokio::time::timeout(bulkhead.max_wait, bulkhead.permits.acquire()).await {
+        Ok(Ok(permit)) => permit,
+        _ => {
+            tracing::warn!(
+                group = group,
+                max_concurrent = bulkhead.max_concurrent,
+                max_wait_ms = bulkhead.max_wait.as_millis() as u64,
+                "Bulkhead full, rejecting request"
+            );
+            return bulkhead.reject("bulkhead_full");
+        }
+    };
+
+    metrics::gauge!("bulkhead_in_flight", "group" => group).set(bulkhead.in_flight() as f64);
+    let response = next.run(request).await;
+    drop(permit);
+    metrics::gauge!("bulkhead_in_flight", "group" => group).set(bulkhead.in_flight() as f64);
+
+    response
+}
@@ -1,169 +1,589 @@
-use moka::future::Cache;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
-use crate::models::{CarResponse, DashboardStats};
+use async_trait::async_trait;
+use moka::future::Cache;
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use secrecy::ExposeSecret;
+use serde::{Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+use crate::config::{CacheBackendKind, CacheConfig};
+use crate::error::{AppError, AppResult};
+use crate::models::{CarDepreciation, CarResponse, DashboardStats};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheNamespace {
+    DashboardStats,
+    CarById,
+    LowStock,
+    Depreciation,
+    StaleReads,
+}
+
+impl CacheNamespace {
+    pub const ALL: [CacheNamespace; 5] = [
+        CacheNamespace::DashboardStats,
+        CacheNamespace::CarById,
+        CacheNamespace::LowStock,
+        CacheNamespace::Depreciation,
+        CacheNamespace::StaleReads,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DashboardStats => "dashboard_stats",
+            Self::CarById => "car_by_id",
+            Self::LowStock => "low_stock",
+            Self::Depreciation => "depreciation",
+            Self::StaleReads => "stale_reads",
+        }
+    }
+
+    pub fn max_capacity(&self) -> u64 {
+        match self {
+            Self::DashboardStats => 100,
+            Self::CarById => 1000,
+            Self::LowStock => 10,
+            Self::Depreciation => 10,
+            Self::StaleReads => 10_000,
+        }
+    }
+
+    pub fn time_to_live(&self) -> Duration {
+        match self {
+            Self::DashboardStats => Duration::from_secs(30),
+            Self::CarById => Duration::from_secs(60),
+            Self::LowStock => Duration::from_secs(10),
+            Self::Depreciation => Duration::from_secs(300),
+            Self::StaleReads => Duration::from_secs(3600),
+        }
+    }
+
+    pub fn time_to_idle(&self) -> Option<Duration> {
+        match self {
+            Self::CarById => Some(Duration::from_secs(300)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CacheError {
+    #[error("Cache backend error: {0}")]
+    Backend(String),
+
+    #[error("Cache serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<redis::RedisError> for CacheError {
+    fn from(err: redis::RedisError) -> Self {
+        CacheError::Backend(err.to_string())
+    }
+}
+
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn get(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, CacheError>;
+
+    async fn set(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), CacheError>;
+
+    async fn invalidate(&self, namespace: CacheNamespace, key: &str) -> Result<(), CacheError>;
+
+    async fn invalidate_namespace(&self, namespace: CacheNamespace) -> Result<(), CacheError>;
+
+    async fn entry_count(&self, namespace: CacheNamespace) -> Result<u64, CacheError>;
+}
+
+pub struct MokaCacheBackend {
+    caches: HashMap<CacheNamespace, Cache<String, Arc<[u8]>>>,
+}
+
+impl MokaCacheBackend {
+    pub fn new() -> Self {
+        let caches = CacheNamespace::ALL
+            .into_iter()
+            .map(|namespace| {
+                let mut builder = Cache::builder()
+                    .max_capacity(namespace.max_capacity())
+                    .time_to_live(namespace.time_to_live())
+                    .name(namespace.as_str());
+
+                if let Some(tti) = namespace.time_to_idle() {
+                    builder = builder.time_to_idle(tti);
+                }
+
+                (namespace, builder.build())
+            })
+            .collect();
+
+        Self { caches }
+    }
+
+    fn cache(&self, namespace: CacheNamespace) -> &Cache<String, Arc<[u8]>> {
+        &self.caches[&namespace]
+    }
+}
+
+impl Default for MokaCacheBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MokaCacheBackend {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        Ok(self
+            .cache(namespace)
+            .get(key)
+            .await
+            .map(|value| value.to_vec()))
+    }
+
+    async fn set(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), CacheError> {
+        self.cache(namespace)
+            .insert(key.to_string(), Arc::from(value))
+            .await;
+        Ok(())
+    }
+
+    async fn invalidate(&self, namespace: CacheNamespace, key: &str) -> Result<(), CacheError> {
+        self.cache(namespace).invalidate(key).await;
+        Ok(())
+    }
+
+    async fn invalidate_namespace(&self, namespace: CacheNamespace) -> Result<(), CacheError> {
+        self.cache(namespace).invalidate_all();
+        Ok(())
+    }
+
+    async fn entry_count(&self, namespace: CacheNamespace) -> Result<u64, CacheError> {
+        Ok(self.cache(namespace).entry_count())
+    }
+}
+
+pub struct RedisCacheBackend {
+    connection: ConnectionManager,
+    key_prefix: String,
+}
+
+impl RedisCacheBackend {
+    pub async fn connect(config: &CacheConfig) -> Result<Self, CacheError> {
+        let url = config
+            .redis_url
+            .as_ref()
+            .ok_or_else(|| CacheError::Backend("cache.redis_url is not configured".into()))?;
+
+        let client = redis::Client::open(url.expose_secret())?;
+
+        let manager_config = ConnectionManagerConfig::new()
+            .set_connection_timeout(config.connect_timeout())
+            .set_response_timeout(config.response_timeout());
+
+        let connection = client
+            .get_connection_manager_with_config(manager_config)
+            .await?;
+
+        tracing::info!(
+            key_prefix = %config.key_prefix,
+            "Connected to Redis cache backend"
+        );
+
+        Ok(Self {
+            connection,
+            key_prefix: config.key_prefix.clone(),
+        })
+    }
+
+    fn key(&self, namespace: CacheNamespace, key: &str) -> String {
+        format!("{}:{}:{}", self.key_prefix, namespace.as_str(), key)
+    }
+
+    fn namespace_pattern(&self, namespace: CacheNamespace) -> String {
+        format!("{}:{}:*", self.key_prefix, namespace.as_str())
+    }
+
+    async fn scan_namespace(&self, namespace: CacheNamespace) -> Result<Vec<String>, CacheError> {
+        let pattern = self.namespace_pattern(namespace);
+        let mut connection = self.connection.clone();
+        let mut cursor: u64 = 0;
+        let mut keys = Vec::new();
+
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut connection)
+                .await?;
+
+            keys.extend(batch);
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        Ok(keys)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisCacheBackend {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+    ) -> Result<Option<Vec<u8>>, CacheError> {
+        let mut connection = self.connection.clone();
+
+        let value: Option<Vec<u8>> = redis::cmd("GET")
+            .arg(self.key(namespace, key))
+            .query_async(&mut connection)
+            .await?;
+
+        Ok(value)
+    }
+
+    async fn set(
+        &self,
+        namespace: CacheNamespace,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), CacheError> {
+        let mut connection = self.connection.clone();
+
+        redis::cmd("SET")
+            .arg(self.key(namespace, key))
+            .arg(value)
+            .arg("PX")
+            .arg(namespace.time_to_live().as_millis() as u64)
+            .query_async::<()>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn invalidate(&self, namespace: CacheNamespace, key: &str) -> Result<(), CacheError> {
+        let mut connection = self.connection.clone();
+
+        redis::cmd("DEL")
+            .arg(self.key(namespace, key))
+            .query_async::<()>(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn invalidate_namespace(&self, namespace: CacheNamespace) -> Result<(), CacheError> {
+        let keys = self.scan_namespace(namespace).await?;
+        let mut connection = self.connection.clone();
+
+        for chunk in keys.chunks(500) {
+            redis::cmd("DEL")
+                .arg(chunk)
+                .query_async::<()>(&mut connection)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn entry_count(&self, namespace: CacheNamespace) -> Result<u64, CacheError> {
+        Ok(self.scan_namespace(namespace).await?.len() as u64)
+    }
+}
+
+/// Minimum gap between stale snapshot writes for the same key.
+const STALE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct QueryCache {
-    dashboard_stats: Cache<String, DashboardStats>,
-    car_by_id: Cache<String, CarResponse>,
-    low_stock: Cache<String, Vec<CarResponse>>,
-    depreciation: Cache<String, Vec<CarResponse>>,
+    backend: Arc<dyn CacheBackend>,
+    recent_stale_writes: Cache<String, ()>,
 }
 
 impl QueryCache {
     pub fn new() -> Self {
-        tracing::info!("Initializing query cache");
+        Self::with_backend(Arc::new(MokaCacheBackend::new()))
+    }
 
+    pub fn with_backend(backend: Arc<dyn CacheBackend>) -> Self {
+        tracing::info!(backend = backend.name(), "Initializing query cache");
         Self {
-            dashboard_stats: Cache::builder()
-                .max_capacity(100)
-                .time_to_live(Duration::from_secs(30))
-                .name("dashboard_stats")
-                .build(),
-
-            car_by_id: Cache::builder()
-                .max_capacity(1000)
-                .time_to_live(Duration::from_secs(60))
-                .time_to_idle(Duration::from_secs(300))
-                .name("car_by_id")
-                .build(),
-
-            low_stock: Cache::builder()
-                .max_capacity(10)
-                .time_to_live(Duration::from_secs(10))
-                .name("low_stock")
+            backend,
+            recent_stale_writes: Cache::builder()
+                .max_capacity(CacheNamespace::StaleReads.max_capacity())
+                .time_to_live(STALE_REFRESH_INTERVAL)
                 .build(),
+        }
+    }
 
-            depreciation: Cache::builder()
-                .max_capacity(10)
-                .time_to_live(Duration::from_secs(300))
-                .name("depreciation")
-                .build(),
+    pub async fn from_config(config: &CacheConfig) -> AppResult<Self> {
+        match config.backend {
+            CacheBackendKind::Memory => Ok(Self::new()),
+            CacheBackendKind::Redis => {
+                let backend = RedisCacheBackend::connect(config).await.map_err(|e| {
+                    AppError::ConfigError(format!("Failed to initialize Redis cache: {}", e))
+                })?;
+                Ok(Self::with_backend(Arc::new(backend)))
+            }
         }
     }
 
-    pub async fn get_dashboard_stats<F, Fut>(
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    async fn get_or_fetch<T, F, Fut>(
         &self,
+        namespace: CacheNamespace,
+        key: &str,
         fetch: F,
-    ) -> crate::error::AppResult<DashboardStats>
+    ) -> AppResult<T>
     where
+        T: Serialize + DeserializeOwned,
         F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = crate::error::AppResult<DashboardStats>>,
+        Fut: std::future::Future<Output = AppResult<T>>,
     {
-        let key = "global".to_string();
+        let cache_name = namespace.as_str();
 
-        if let Some(cached) = self.dashboard_stats.get(&key).await {
-            tracing::debug!("Dashboard stats cache HIT");
-            metrics::counter!("cache_hit_total", "cache" => "dashboard_stats").increment(1);
-            return Ok(cached);
+        match self.backend.get(namespace, key).await {
+            Ok(Some(bytes)) => match serde_json::from_slice::<T>(&bytes) {
+                Ok(value) => {
+                    tracing::debug!(cache = cache_name, key = %key, "Cache HIT");
+                    metrics::counter!("cache_hit_total", "cache" => cache_name).increment(1);
+                    return Ok(value);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        cache = cache_name,
+                        key = %key,
+                        error = %e,
+                        "Discarding undecodable cache entry"
+                    );
+                    self.record_error(namespace, "decode");
+                }
+            },
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(cache = cache_name, key = %key, error = %e, "Cache read failed");
+                self.record_error(namespace, "get");
+            }
         }
 
-        tracing::debug!("Dashboard stats cache MISS");
-        metrics::counter!("cache_miss_total", "cache" => "dashboard_stats").increment(1);
+        tracing::debug!(cache = cache_name, key = %key, "Cache MISS");
+        metrics::counter!("cache_miss_total", "cache" => cache_name).increment(1);
 
-        let stats = fetch().await?;
-        self.dashboard_stats.insert(key, stats.clone()).await;
+        let value = fetch().await?;
 
-        Ok(stats)
+        match serde_json::to_vec(&value) {
+            Ok(bytes) => {
+                if let Err(e) = self.backend.set(namespace, key, bytes).await {
+                    tracing::warn!(cache = cache_name, key = %key, error = %e, "Cache write failed");
+                    self.record_error(namespace, "set");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(cache = cache_name, error = %e, "Failed to encode cache entry");
+                self.record_error(namespace, "encode");
+            }
+        }
+
+        Ok(value)
     }
 
-    pub async fn get_car_by_id<F, Fut>(
-        &self,
-        car_id: &str,
-        fetch: F,
-    ) -> crate::error::AppResult<CarResponse>
-    where
-        F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = crate::error::AppResult<CarResponse>>,
-    {
-        if let Some(cached) = self.car_by_id.get(car_id).await {
-            tracing::debug!(car_id = %car_id, "Car cache HIT");
-            metrics::counter!("cache_hit_total", "cache" => "car_by_id").increment(1);
-            return Ok(cached);
+    fn record_error(&self, namespace: CacheNamespace, operation: &'static str) {
+        metrics::counter!(
+            "cache_error_total",
+            "cache" => namespace.as_str(),
+            "backend" => self.backend.name(),
+            "operation" => operation
+        )
+        .increment(1);
+    }
+
+    async fn invalidate_key(&self, namespace: CacheNamespace, key: &str) {
+        if let Err(e) = self.backend.invalidate(namespace, key).await {
+            tracing::warn!(cache = namespace.as_str(), key = %key, error = %e, "Cache invalidation failed");
+            self.record_error(namespace, "invalidate");
         }
+    }
 
-        tracing::debug!(car_id = %car_id, "Car cache MISS");
-        metrics::counter!("cache_miss_total", "cache" => "car_by_id").increment(1);
+    async fn invalidate_namespace(&self, namespace: CacheNamespace) {
+        if let Err(e) = self.backend.invalidate_namespace(namespace).await {
+            tracing::warn!(cache = namespace.as_str(), error = %e, "Cache invalidation failed");
+            self.record_error(namespace, "invalidate");
+        }
+    }
 
-        let car = fetch().await?;
-        self.car_by_id.insert(car_id.to_string(), car.clone()).await;
+    pub async fn get_dashboard_stats<F, Fut>(&self, fetch: F) -> AppResult<DashboardStats>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = AppResult<DashboardStats>>,
+    {
+        self.get_or_fetch(CacheNamespace::DashboardStats, "global", fetch)
+            .await
+    }
 
-        Ok(car)
+    pub async fn get_car_by_id<F, Fut>(&self, car_id: &str, fetch: F) -> AppResult<CarResponse>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = AppResult<CarResponse>>,
+    {
+        self.get_or_fetch(CacheNamespace::CarById, car_id, fetch)
+            .await
     }
 
     pub async fn get_low_stock<F, Fut>(
         &self,
         threshold: i32,
         fetch: F,
-    ) -> crate::error::AppResult<Vec<CarResponse>>
+    ) -> AppResult<Vec<CarResponse>>
     where
         F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = crate::error::AppResult<Vec<CarResponse>>>,
+        Fut: std::future::Future<Output = AppResult<Vec<CarResponse>>>,
     {
         let key = format!("threshold_{}", threshold);
-
-        if let Some(cached) = self.low_stock.get(&key).await {
-            tracing::debug!(threshold = threshold, "Low stock cache HIT");
-            metrics::counter!("cache_hit_total", "cache" => "low_stock").increment(1);
-            return Ok(cached);
-        }
-
-        tracing::debug!(threshold = threshold, "Low stock cache MISS");
-        metrics::counter!("cache_miss_total", "cache" => "low_stock").increment(1);
-
-        let cars = fetch().await?;
-        self.low_stock.insert(key, cars.clone()).await;
-
-        Ok(cars)
+        self.get_or_fetch(CacheNamespace::LowStock, &key, fetch)
+            .await
     }
 
-    pub async fn get_depreciation<F, Fut>(
-        &self,
-        fetch: F,
-    ) -> crate::error::AppResult<Vec<CarResponse>>
+    pub async fn get_depreciation<F, Fut>(&self, fetch: F) -> AppResult<Vec<CarDepreciation>>
     where
         F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = crate::error::AppResult<Vec<CarResponse>>>,
+        Fut: std::future::Future<Output = AppResult<Vec<CarDepreciation>>>,
     {
-        let key = "global".to_string();
-
-        if let Some(cached) = self.depreciation.get(&key).await {
-            tracing::debug!("Depreciation cache HIT");
-            metrics::counter!("cache_hit_total", "cache" => "depreciation").increment(1);
-            return Ok(cached);
-        }
-
-        tracing::debug!("Depreciation cache MISS");
-        metrics::counter!("cache_miss_total", "cache" => "depreciation").increment(1);
-
-        let cars = fetch().await?;
-        self.depreciation.insert(key, cars.clone()).await;
-
-        Ok(cars)
+        self.get_or_fetch(CacheNamespace::Depreciation, "global", fetch)
+            .await
     }
 
     pub async fn invalidate_car(&self, car_id: &str) {
         tracing::info!(car_id = %car_id, "Invalidating car from cache");
-        self.car_by_id.invalidate(car_id).await;
-        self.dashboard_stats.invalidate_all();
-        self.low_stock.invalidate_all();
+        self.invalidate_key(CacheNamespace::CarById, car_id).await;
+        self.invalidate_namespace(CacheNamespace::DashboardStats)
+            .await;
+        self.invalidate_namespace(CacheNamespace::LowStock).await;
+        self.invalidate_namespace(CacheNamespace::Depreciation)
+            .await;
     }
 
     pub async fn invalidate_all_cars(&self) {
         tracing::info!("Invalidating all car caches due to bulk operation");
-        self.car_by_id.invalidate_all();
-        self.dashboard_stats.invalidate_all();
-        self.low_stock.invalidate_all();
-        self.depreciation.invalidate_all();
+        for namespace in CacheNamespace::ALL {
+            if namespace != CacheNamespace::StaleReads {
+                self.invalidate_namespace(namespace).await;
+            }
+        }
+    }
+
+    /// Records the last good result for `key` without holding up the caller:
+    /// the write runs on a spawned task and is skipped if the key was written
+    /// within `STALE_REFRESH_INTERVAL`.
+    pub fn store_stale<T: Serialize>(&self, key: &str, value: &T) {
+        let namespace = CacheNamespace::StaleReads;
+
+        if self.recent_stale_writes.contains_key(key) {
+            return;
+        }
+
+        let bytes = match serde_json::to_vec(value) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!(key = %key, error = %e, "Failed to encode stale snapshot");
+                self.record_error(namespace, "encode");
+                return;
+            }
+        };
+
+        let cache = self.clone();
+        let key = key.to_string();
+        tokio::spawn(async move {
+            cache.recent_stale_writes.insert(key.clone(), ()).await;
+            if let Err(e) = cache.backend.set(namespace, &key, bytes).await {
+                tracing::debug!(key = %key, error = %e, "Stale snapshot write failed");
+                cache.record_error(namespace, "set");
+                cache.recent_stale_writes.invalidate(&key).await;
+            }
+        });
     }
 
-    pub fn metrics(&self) -> CacheMetrics {
+    pub async fn get_stale<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let namespace = CacheNamespace::StaleReads;
+
+        match self.backend.get(namespace, key).await {
+            Ok(Some(bytes)) => match serde_json::from_slice(&bytes) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    tracing::warn!(key = %key, error = %e, "Discarding undecodable stale snapshot");
+                    self.record_error(namespace, "decode");
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(key = %key, error = %e, "Stale snapshot read failed");
+                self.record_error(namespace, "get");
+                None
+            }
+        }
+    }
+
+    pub async fn metrics(&self) -> CacheMetrics {
+        let mut sizes = HashMap::new();
+
+        for namespace in CacheNamespace::ALL {
+            let size = match self.backend.entry_count(namespace).await {
+                Ok(size) => size,
+                Err(e) => {
+                    tracing::warn!(cache = namespace.as_str(), error = %e, "Failed to read cache size");
+                    0
+                }
+            };
+            sizes.insert(namespace, size);
+        }
+
         CacheMetrics {
-            dashboard_stats_size: self.dashboard_stats.entry_count(),
-            car_by_id_size: self.car_by_id.entry_count(),
-            low_stock_size: self.low_stock.entry_count(),
-            depreciation_size: self.depreciation.entry_count(),
+            backend: self.backend.name(),
+            dashboard_stats_size: sizes[&CacheNamespace::DashboardStats],
+            car_by_id_size: sizes[&CacheNamespace::CarById],
+            low_stock_size: sizes[&CacheNamespace::LowStock],
+            depreciation_size: sizes[&CacheNamespace::Depreciation],
+            stale_reads_size: sizes[&CacheNamespace::StaleReads],
         }
     }
 }
@@ -176,8 +596,338 @@ impl Default for QueryCache {
 
 #[derive(Debug)]
 pub struct CacheMetrics {
+    pub backend: &'static str,
     pub dashboard_stats_size: u64,
     pub car_by_id_size: u64,
     pub low_stock_size: u64,
     pub depreciation_size: u64,
+    pub stale_reads_size: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    use secrecy::SecretString;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    /// Keys handed out per SCAN call, small so the cursor loop is exercised.
+    const SCAN_PAGE: usize = 2;
+
+    /// Stored value and its expiry on the stand-in's clock.
+    type Entry = (Vec<u8>, Option<u64>);
+
+    /// Minimal RESP2 server covering the commands the Redis backend sends.
+    /// Expiry runs on a virtual millisecond clock the test advances by hand.
+    #[derive(Default)]
+    struct RespStandIn {
+        entries: Mutex<BTreeMap<String, Entry>>,
+        now_ms: AtomicU64,
+        scans: AtomicUsize,
+    }
+
+    impl RespStandIn {
+        async fn start() -> (Arc<Self>, String) {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("redis://{}", listener.local_addr().unwrap());
+            let server = Arc::new(Self::default());
+
+            let accepting = server.clone();
+            tokio::spawn(async move {
+                while let Ok((socket, _)) = listener.accept().await {
+                    tokio::spawn(accepting.clone().serve(socket));
+                }
+            });
+
+            (server, url)
+        }
+
+        fn advance(&self, by: Duration) {
+            self.now_ms
+                .fetch_add(by.as_millis() as u64, Ordering::SeqCst);
+        }
+
+        fn ttl_ms(&self, key: &str) -> Option<u64> {
+            let now = self.now_ms.load(Ordering::SeqCst);
+            let entries = self.entries.lock().unwrap();
+            entries
+                .get(key)
+                .and_then(|(_, expires_at)| *expires_at)
+                .map(|at| at.saturating_sub(now))
+        }
+
+        fn live_keys(&self) -> Vec<String> {
+            let now = self.now_ms.load(Ordering::SeqCst);
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|_, (_, expires_at)| expires_at.is_none_or(|at| at > now));
+            entries.keys().cloned().collect()
+        }
+
+        async fn serve(self: Arc<Self>, socket: TcpStream) {
+            let mut socket = BufReader::new(socket);
+            while let Some(command) = read_command(&mut socket).await {
+                let reply = self.execute(command);
+                if socket.get_mut().write_all(&reply).await.is_err() {
+                    break;
+                }
+            }
+        }
+
+        fn execute(&self, command: Vec<Vec<u8>>) -> Vec<u8> {
+            let name = String::from_utf8_lossy(&command[0]).to_ascii_uppercase();
+            let arg = |i: usize| String::from_utf8_lossy(&command[i]).into_owned();
+
+            match name.as_str() {
+                "GET" => {
+                    let key = arg(1);
+                    let value = self
+                        .live_keys()
+                        .contains(&key)
+                        .then(|| self.entries.lock().unwrap()[&key].0.clone());
+                    match value {
+                        Some(value) => bulk(&value),
+                        None => b"$-1\r\n".to_vec(),
+                    }
+                }
+                "SET" => {
+                    let expires_at = (command.len() == 5 && arg(3).eq_ignore_ascii_case("PX"))
+                        .then(|| {
+                            self.now_ms.load(Ordering::SeqCst) + arg(4).parse::<u64>().unwrap()
+                        });
+                    self.entries
+                        .lock()
+                        .unwrap()
+                        .insert(arg(1), (command[2].clone(), expires_at));
+                    b"+OK\r\n".to_vec()
+                }
+                "DEL" => {
+                    let mut entries = self.entries.lock().unwrap();
+                    let removed = (1..command.len())
+                        .filter(|&i| entries.remove(&arg(i)).is_some())
+                        .count();
+                    format!(":{}\r\n", removed).into_bytes()
+                }
+                "SCAN" => {
+                    self.scans.fetch_add(1, Ordering::SeqCst);
+                    let cursor: usize = arg(1).parse().unwrap();
+                    let prefix = arg(3).trim_end_matches('*').to_string();
+                    let matching: Vec<String> = self
+                        .live_keys()
+                        .into_iter()
+                        .filter(|key| key.starts_with(&prefix))
+                        .collect();
+                    let end = (cursor + SCAN_PAGE).min(matching.len());
+                    let next = if end == matching.len() { 0 } else { end };
+
+                    let mut reply = b"*2\r\n".to_vec();
+                    reply.extend(bulk(next.to_string().as_bytes()));
+                    reply.extend(format!("*{}\r\n", end - cursor).into_bytes());
+                    for key in &matching[cursor..end] {
+                        reply.extend(bulk(key.as_bytes()));
+                    }
+                    reply
+                }
+                "PING" => b"+PONG\r\n".to_vec(),
+                // CLIENT SETINFO and anything else from the connection handshake.
+                _ => b"+OK\r\n".to_vec(),
+            }
+        }
+    }
+
+    fn bulk(value: &[u8]) -> Vec<u8> {
+        let mut out = format!("${}\r\n", value.len()).into_bytes();
+        out.extend_from_slice(value);
+        out.extend_from_slice(b"\r\n");
+        out
+    }
+
+    async fn read_command(socket: &mut BufReader<TcpStream>) -> Option<Vec<Vec<u8>>> {
+        let mut line = String::new();
+        socket.read_line(&mut line).await.ok().filter(|&n| n > 0)?;
+        let count: usize = line.trim_end().strip_prefix('*')?.parse().ok()?;
+
+        let mut parts = Vec::with_capacity(count);
+        for _ in 0..count {
+            line.clear();
+            socket.read_line(&mut line).await.ok()?;
+            let len: usize = line.trim_end().strip_prefix('$')?.parse().ok()?;
+            let mut data = vec![0; len + 2];
+            socket.read_exact(&mut data).await.ok()?;
+            data.truncate(len);
+            parts.push(data);
+        }
+        Some(parts)
+    }
+
+    async fn redis_backend() -> (Arc<RespStandIn>, RedisCacheBackend) {
+        let (server, url) = RespStandIn::start().await;
+        let config = CacheConfig {
+            backend: CacheBackendKind::Redis,
+            redis_url: Some(SecretString::from(url)),
+            key_prefix: "test".into(),
+            ..CacheConfig::default()
+        };
+        let backend = RedisCacheBackend::connect(&config).await.unwrap();
+        (server, backend)
+    }
+
+    #[tokio::test]
+    async fn redis_get_and_set_round_trip_under_namespaced_key() {
+        let (server, backend) = redis_backend().await;
+
+        assert_eq!(
+            backend.get(CacheNamespace::CarById, "CAR-1").await.unwrap(),
+            None
+        );
+
+        backend
+            .set(CacheNamespace::CarById, "CAR-1", b"payload".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(server.live_keys(), vec!["test:car_by_id:CAR-1".to_string()]);
+        assert_eq!(
+            backend.get(CacheNamespace::CarById, "CAR-1").await.unwrap(),
+            Some(b"payload".to_vec())
+        );
+        assert_eq!(
+            backend
+                .get(CacheNamespace::LowStock, "CAR-1")
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn redis_set_applies_namespace_ttl() {
+        let (server, backend) = redis_backend().await;
+        let ttl = CacheNamespace::LowStock.time_to_live();
+
+        backend
+            .set(CacheNamespace::LowStock, "all", b"[]".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            server.ttl_ms("test:low_stock:all"),
+            Some(ttl.as_millis() as u64)
+        );
+
+        server.advance(ttl - Duration::from_millis(1));
+        assert!(
+            backend
+                .get(CacheNamespace::LowStock, "all")
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        server.advance(Duration::from_millis(1));
+        assert_eq!(
+            backend.get(CacheNamespace::LowStock, "all").await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn redis_invalidate_removes_single_key() {
+        let (_server, backend) = redis_backend().await;
+
+        for key in ["CAR-1", "CAR-2"] {
+            backend
+                .set(CacheNamespace::CarById, key, b"{}".to_vec())
+                .await
+                .unwrap();
+        }
+        backend
+            .invalidate(CacheNamespace::CarById, "CAR-1")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            backend.get(CacheNamespace::CarById, "CAR-1").await.unwrap(),
+            None
+        );
+        assert!(
+            backend
+                .get(CacheNamespace::CarById, "CAR-2")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn redis_invalidate_namespace_scans_and_deletes_only_that_namespace() {
+        let (server, backend) = redis_backend().await;
+
+        for i in 0..5 {
+            backend
+                .set(CacheNamespace::CarById, &format!("CAR-{i}"), b"{}".to_vec())
+                .await
+                .unwrap();
+        }
+        backend
+            .set(CacheNamespace::DashboardStats, "stats", b"{}".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            backend.entry_count(CacheNamespace::CarById).await.unwrap(),
+            5
+        );
+
+        server.scans.store(0, Ordering::SeqCst);
+        backend
+            .invalidate_namespace(CacheNamespace::CarById)
+            .await
+            .unwrap();
+
+        assert!(
+            server.scans.load(Ordering::SeqCst) > 1,
+            "SCAN cursor was not followed"
+        );
+        assert_eq!(
+            backend.entry_count(CacheNamespace::CarById).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            backend
+                .entry_count(CacheNamespace::DashboardStats)
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn stale_snapshot_writes_are_spawned_and_throttled_per_key() {
+        let (server, backend) = redis_backend().await;
+        let cache = QueryCache::with_backend(Arc::new(backend));
+
+        cache.store_stale("reads:find_all:1", &vec![1, 2, 3]);
+        for _ in 0..50 {
+            if !server.live_keys().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            cache.get_stale::<Vec<i32>>("reads:find_all:1").await,
+            Some(vec![1, 2, 3])
+        );
+
+        cache.store_stale("reads:find_all:1", &vec![4]);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            cache.get_stale::<Vec<i32>>("reads:find_all:1").await,
+            Some(vec![1, 2, 3])
+        );
+    }
 }
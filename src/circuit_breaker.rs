@@ -1,17 +1,51 @@
-use std::sync::Arc;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicU8, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 use crate::error::AppError;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+const WINDOW_BUCKETS: u64 = 10;
+const EMPTY_BUCKET: u64 = u64::MAX;
+const NO_FAILURE: u64 = u64::MAX;
+const MAX_TRANSITION_EVENTS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub enum CircuitState {
     Closed,
     Open,
     HalfOpen,
 }
 
+impl CircuitState {
+    fn as_gauge(self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            CircuitState::Closed => 0,
+            CircuitState::HalfOpen => 1,
+            CircuitState::Open => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => CircuitState::HalfOpen,
+            2 => CircuitState::Open,
+            _ => CircuitState::Closed,
+        }
+    }
+}
+
 impl std::fmt::Display for CircuitState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -24,7 +58,15 @@ impl std::fmt::Display for CircuitState {
 
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
-    pub failure_threshold: u32,
+    /// Fraction of failed calls in the window that opens the circuit.
+    pub failure_rate_threshold: f64,
+    /// Fraction of slow calls in the window that opens the circuit.
+    pub slow_call_rate_threshold: f64,
+    pub slow_call_duration: Duration,
+    /// Calls required in the window before rates are evaluated.
+    pub minimum_calls: u32,
+    pub window: Duration,
+    pub call_timeout: Option<Duration>,
     pub success_threshold: u32,
     pub open_duration: Duration,
     pub half_open_max_calls: u32,
@@ -33,7 +75,12 @@ pub struct CircuitBreakerConfig {
 impl Default for CircuitBreakerConfig {
     fn default() -> Self {
         Self {
-            failure_threshold: 5,
+            failure_rate_threshold: 0.5,
+            slow_call_rate_threshold: 0.8,
+            slow_call_duration: Duration::from_secs(2),
+            minimum_calls: 10,
+            window: Duration::from_secs(60),
+            call_timeout: Some(Duration::from_secs(10)),
             success_threshold: 3,
             open_duration: Duration::from_secs(30),
             half_open_max_calls: 3,
@@ -44,7 +91,12 @@ impl Default for CircuitBreakerConfig {
 impl CircuitBreakerConfig {
     pub fn aggressive() -> Self {
         Self {
-            failure_threshold: 3,
+            failure_rate_threshold: 0.3,
+            slow_call_rate_threshold: 0.5,
+            slow_call_duration: Duration::from_secs(1),
+            minimum_calls: 5,
+            window: Duration::from_secs(30),
+            call_timeout: Some(Duration::from_secs(3)),
             success_threshold: 2,
             open_duration: Duration::from_secs(10),
             half_open_max_calls: 2,
@@ -53,7 +105,12 @@ impl CircuitBreakerConfig {
 
     pub fn relaxed() -> Self {
         Self {
-            failure_threshold: 10,
+            failure_rate_threshold: 0.7,
+            slow_call_rate_threshold: 0.9,
+            slow_call_duration: Duration::from_secs(5),
+            minimum_calls: 20,
+            window: Duration::from_secs(120),
+            call_timeout: Some(Duration::from_secs(30)),
             success_threshold: 5,
             open_duration: Duration::from_secs(60),
             half_open_max_calls: 5,
@@ -61,13 +118,128 @@ impl CircuitBreakerConfig {
     }
 }
 
+#[derive(Default)]
+struct WindowBucket {
+    epoch: AtomicU64,
+    calls: AtomicU32,
+    failures: AtomicU32,
+    slow: AtomicU32,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct WindowSnapshot {
+    calls: u32,
+    failures: u32,
+    slow: u32,
+}
+
+impl WindowSnapshot {
+    fn failure_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.calls as f64
+        }
+    }
+
+    fn slow_call_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.slow as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Ring of time buckets covering the rolling window. A bucket is recycled by
+/// whichever caller first observes it belonging to an older epoch; counts that
+/// race with the recycle may be dropped, which is acceptable for rate estimates.
+struct SlidingWindow {
+    buckets: Vec<WindowBucket>,
+    bucket_ms: u64,
+}
+
+impl SlidingWindow {
+    fn new(window: Duration) -> Self {
+        let buckets = (0..WINDOW_BUCKETS)
+            .map(|_| WindowBucket {
+                epoch: AtomicU64::new(EMPTY_BUCKET),
+                ..Default::default()
+            })
+            .collect();
+
+        Self {
+            buckets,
+            bucket_ms: (window.as_millis() as u64 / WINDOW_BUCKETS).max(1),
+        }
+    }
+
+    fn record(&self, now_ms: u64, failed: bool, slow: bool) {
+        let epoch = now_ms / self.bucket_ms;
+        let bucket = &self.buckets[(epoch % WINDOW_BUCKETS) as usize];
+
+        let seen = bucket.epoch.load(Ordering::Acquire);
+        if seen != epoch
+            && bucket
+                .epoch
+                .compare_exchange(seen, epoch, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            bucket.calls.store(0, Ordering::Release);
+            bucket.failures.store(0, Ordering::Release);
+            bucket.slow.store(0, Ordering::Release);
+        }
+
+        bucket.calls.fetch_add(1, Ordering::AcqRel);
+        if failed {
+            bucket.failures.fetch_add(1, Ordering::AcqRel);
+        }
+        if slow {
+            bucket.slow.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    fn snapshot(&self, now_ms: u64) -> WindowSnapshot {
+        let current = now_ms / self.bucket_ms;
+
+        self.buckets
+            .iter()
+            .filter(|bucket| {
+                let epoch = bucket.epoch.load(Ordering::Acquire);
+                epoch != EMPTY_BUCKET && current.saturating_sub(epoch) < WINDOW_BUCKETS
+            })
+            .fold(WindowSnapshot::default(), |acc, bucket| WindowSnapshot {
+                calls: acc.calls + bucket.calls.load(Ordering::Acquire),
+                failures: acc.failures + bucket.failures.load(Ordering::Acquire),
+                slow: acc.slow + bucket.slow.load(Ordering::Acquire),
+            })
+    }
+
+    fn reset(&self) {
+        for bucket in &self.buckets {
+            bucket.epoch.store(EMPTY_BUCKET, Ordering::Release);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TransitionEvent {
+    pub from: CircuitState,
+    pub to: CircuitState,
+    pub reason: &'static str,
+    pub at: DateTime<Utc>,
+}
+
 pub struct CircuitBreaker {
-    state: Arc<RwLock<CircuitState>>,
-    failure_count: Arc<RwLock<u32>>,
-    success_count: Arc<RwLock<u32>>,
-    half_open_calls: Arc<RwLock<u32>>,
-    last_failure_time: Arc<RwLock<Option<Instant>>>,
-    last_state_change: Arc<RwLock<Instant>>,
+    state: AtomicU8,
+    window: SlidingWindow,
+    half_open_calls: AtomicU32,
+    half_open_successes: AtomicU32,
+    opened_at_ms: AtomicU64,
+    last_failure_ms: AtomicU64,
+    last_state_change_ms: AtomicU64,
+    transitions: Mutex<VecDeque<TransitionEvent>>,
+    created_at: Instant,
     config: CircuitBreakerConfig,
     name: String,
 }
@@ -102,190 +274,347 @@ impl CircuitBreaker {
         let name = name.into();
         tracing::info!(
             circuit_breaker = %name,
-            failure_threshold = config.failure_threshold,
+            failure_rate_threshold = config.failure_rate_threshold,
+            slow_call_rate_threshold = config.slow_call_rate_threshold,
+            window_secs = config.window.as_secs(),
             open_duration_secs = config.open_duration.as_secs(),
             "Creating circuit breaker"
         );
 
+        metrics::gauge!("circuit_breaker_state", "name" => name.clone())
+            .set(CircuitState::Closed.as_gauge());
+
         Self {
-            state: Arc::new(RwLock::new(CircuitState::Closed)),
-            failure_count: Arc::new(RwLock::new(0)),
-            success_count: Arc::new(RwLock::new(0)),
-            half_open_calls: Arc::new(RwLock::new(0)),
-            last_failure_time: Arc::new(RwLock::new(None)),
-            last_state_change: Arc::new(RwLock::new(Instant::now())),
+            state: AtomicU8::new(CircuitState::Closed.as_u8()),
+            window: SlidingWindow::new(config.window),
+            half_open_calls: AtomicU32::new(0),
+            half_open_successes: AtomicU32::new(0),
+            opened_at_ms: AtomicU64::new(0),
+            last_failure_ms: AtomicU64::new(NO_FAILURE),
+            last_state_change_ms: AtomicU64::new(0),
+            transitions: Mutex::new(VecDeque::with_capacity(MAX_TRANSITION_EVENTS)),
+            created_at: Instant::now(),
             config,
             name,
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     pub async fn call<F, Fut, T>(&self, operation: F) -> Result<T, CircuitError<AppError>>
     where
         F: FnOnce() -> Fut,
         Fut: std::future::Future<Output = Result<T, AppError>>,
     {
-        self.check_and_transition_state().await;
-
-        let current_state = *self.state.read().await;
+        self.call_with(operation, |e: &AppError| e.status_code().is_server_error())
+            .await
+    }
 
-        match current_state {
-            CircuitState::Open => {
-                tracing::debug!(
-                    circuit_breaker = %self.name,
-                    state = %current_state,
-                    "Rejecting call: circuit is open"
-                );
-                return Err(CircuitError::Open);
-            }
-            CircuitState::HalfOpen => {
-                let mut calls = self.half_open_calls.write().await;
-                if *calls >= self.config.half_open_max_calls {
-                    tracing::debug!(
-                        circuit_breaker = %self.name,
-                        calls = *calls,
-                        max = self.config.half_open_max_calls,
-                        "Rejecting call: half-open limit reached"
-                    );
-                    return Err(CircuitError::HalfOpenLimit);
-                }
-                *calls += 1;
+    pub async fn call_with<F, Fut, T, E, C>(
+        &self,
+        operation: F,
+        is_failure: C,
+    ) -> Result<T, CircuitError<E>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        C: Fn(&E) -> bool,
+    {
+        let _permit = match self.acquire_permission() {
+            Ok(permit) => permit,
+            Err(rejection) => {
+                self.record_call("rejected");
+                return Err(rejection);
             }
-            CircuitState::Closed => {}
-        }
+        };
 
         let start = Instant::now();
-        let result = operation().await;
+        let result = match self.config.call_timeout {
+            Some(limit) => tokio::time::timeout(limit, operation()).await.ok(),
+            None => Some(operation().await),
+        };
+        let elapsed = start.elapsed();
 
         match result {
-            Ok(value) => {
-                self.on_success(start.elapsed()).await;
+            None => {
+                tracing::warn!(
+                    circuit_breaker = %self.name,
+                    timeout_ms = elapsed.as_millis(),
+                    "Call timed out"
+                );
+                self.record_call("timeout");
+                self.on_failure(elapsed);
+                Err(CircuitError::Timeout)
+            }
+            Some(Ok(value)) => {
+                self.record_call("success");
+                self.on_success(elapsed);
                 Ok(value)
             }
-            Err(e) => {
-                self.on_failure(start.elapsed()).await;
+            Some(Err(e)) if is_failure(&e) => {
+                self.record_call("failure");
+                self.on_failure(elapsed);
+                Err(CircuitError::Underlying(e))
+            }
+            Some(Err(e)) => {
+                self.record_call("ignored");
+                self.on_success(elapsed);
                 Err(CircuitError::Underlying(e))
             }
         }
     }
 
-    async fn check_and_transition_state(&self) {
-        let mut state = self.state.write().await;
+    /// Half-open trial calls hold a permit until they finish or are dropped.
+    fn acquire_permission<E>(&self) -> Result<Option<HalfOpenPermit<'_>>, CircuitError<E>> {
+        if self.state() == CircuitState::Open {
+            let opened_for = self
+                .now_ms()
+                .saturating_sub(self.opened_at_ms.load(Ordering::Acquire));
+            if opened_for < self.config.open_duration.as_millis() as u64 {
+                tracing::debug!(circuit_breaker = %self.name, "Rejecting call: circuit is open");
+                return Err(CircuitError::Open);
+            }
+
+            self.transition(
+                CircuitState::Open,
+                CircuitState::HalfOpen,
+                "open_duration_elapsed",
+            );
+        }
 
-        if *state == CircuitState::Open {
-            let last_fail = *self.last_failure_time.read().await;
-            if let Some(time) = last_fail {
-                let elapsed = time.elapsed();
-                if elapsed >= self.config.open_duration {
-                    tracing::info!(
+        match self.state() {
+            CircuitState::Closed => Ok(None),
+            CircuitState::Open => Err(CircuitError::Open),
+            CircuitState::HalfOpen => {
+                let permit = HalfOpenPermit {
+                    breaker: self,
+                    opened_at_ms: self.opened_at_ms.load(Ordering::Acquire),
+                };
+                let calls = self.half_open_calls.fetch_add(1, Ordering::AcqRel);
+                if calls >= self.config.half_open_max_calls {
+                    tracing::debug!(
                         circuit_breaker = %self.name,
-                        open_duration = ?self.config.open_duration,
-                        "Transitioning from Open to HalfOpen"
+                        max = self.config.half_open_max_calls,
+                        "Rejecting call: half-open limit reached"
                     );
-                    *state = CircuitState::HalfOpen;
-                    *self.last_state_change.write().await = Instant::now();
-                    *self.success_count.write().await = 0;
-                    *self.half_open_calls.write().await = 0;
+                    return Err(CircuitError::HalfOpenLimit);
                 }
+                Ok(Some(permit))
             }
         }
     }
 
-    async fn on_success(&self, duration: Duration) {
-        let state = *self.state.read().await;
+    fn on_success(&self, duration: Duration) {
+        let slow = self.is_slow(duration);
+        self.window.record(self.now_ms(), false, slow);
 
-        match state {
+        match self.state() {
+            CircuitState::HalfOpen if slow => self.trip(CircuitState::HalfOpen, "slow_probe"),
             CircuitState::HalfOpen => {
-                let mut successes = self.success_count.write().await;
-                *successes += 1;
-
-                tracing::debug!(
-                    circuit_breaker = %self.name,
-                    success_count = *successes,
-                    threshold = self.config.success_threshold,
-                    "Recording success in HalfOpen state"
-                );
-
-                if *successes >= self.config.success_threshold {
-                    let mut state_guard = self.state.write().await;
-                    if *state_guard == CircuitState::HalfOpen {
-                        tracing::info!(
-                            circuit_breaker = %self.name,
-                            success_count = *successes,
-                            "Transitioning from HalfOpen to Closed (recovered)"
-                        );
-                        *state_guard = CircuitState::Closed;
-                        *self.last_state_change.write().await = Instant::now();
-                        *self.failure_count.write().await = 0;
-                        *self.half_open_calls.write().await = 0;
-                    }
+                let successes = self.half_open_successes.fetch_add(1, Ordering::AcqRel) + 1;
+                if successes >= self.config.success_threshold
+                    && self.transition(CircuitState::HalfOpen, CircuitState::Closed, "recovered")
+                {
+                    self.window.reset();
                 }
             }
-            CircuitState::Closed => {
-                let mut failures = self.failure_count.write().await;
-                if *failures > 0 {
-                    *failures = 0;
-                    tracing::debug!(
-                        circuit_breaker = %self.name,
-                        "Resetting failure count after success"
-                    );
-                }
-            }
-            _ => {}
+            CircuitState::Closed => self.evaluate_window(),
+            CircuitState::Open => {}
         }
 
         tracing::trace!(
             circuit_breaker = %self.name,
-            state = %state,
             duration_ms = duration.as_millis(),
+            slow = slow,
             "Call succeeded"
         );
     }
 
-    async fn on_failure(&self, duration: Duration) {
-        let mut failures = self.failure_count.write().await;
-        *failures += 1;
-
-        let current_state = *self.state.read().await;
+    fn on_failure(&self, duration: Duration) {
+        let now = self.now_ms();
+        self.window.record(now, true, self.is_slow(duration));
+        self.last_failure_ms.store(now, Ordering::Release);
 
         tracing::debug!(
             circuit_breaker = %self.name,
-            state = %current_state,
-            failure_count = *failures,
-            threshold = self.config.failure_threshold,
             duration_ms = duration.as_millis(),
             "Recording failure"
         );
 
-        if *failures >= self.config.failure_threshold && current_state != CircuitState::Open {
-            let mut state = self.state.write().await;
-            if *state != CircuitState::Open {
-                tracing::warn!(
-                    circuit_breaker = %self.name,
-                    failure_count = *failures,
-                    threshold = self.config.failure_threshold,
-                    "Transitioning to Open (failure threshold reached)"
-                );
-                *state = CircuitState::Open;
-                *self.last_failure_time.write().await = Some(Instant::now());
-                *self.last_state_change.write().await = Instant::now();
-                *self.success_count.write().await = 0;
-                *self.half_open_calls.write().await = 0;
-            }
+        match self.state() {
+            CircuitState::HalfOpen => self.trip(CircuitState::HalfOpen, "probe_failed"),
+            CircuitState::Closed => self.evaluate_window(),
+            CircuitState::Open => {}
+        }
+    }
+
+    fn evaluate_window(&self) {
+        let snapshot = self.window.snapshot(self.now_ms());
+        let failure_rate = snapshot.failure_rate();
+        let slow_call_rate = snapshot.slow_call_rate();
+
+        metrics::gauge!("circuit_breaker_failure_rate", "name" => self.name.clone())
+            .set(failure_rate);
+        metrics::gauge!("circuit_breaker_slow_call_rate", "name" => self.name.clone())
+            .set(slow_call_rate);
+
+        if snapshot.calls < self.config.minimum_calls {
+            return;
+        }
+
+        if failure_rate >= self.config.failure_rate_threshold {
+            self.trip(CircuitState::Closed, "failure_rate");
+        } else if slow_call_rate >= self.config.slow_call_rate_threshold {
+            self.trip(CircuitState::Closed, "slow_call_rate");
         }
     }
 
-    pub async fn state(&self) -> CircuitState {
-        *self.state.read().await
+    fn trip(&self, from: CircuitState, reason: &'static str) {
+        self.opened_at_ms.store(self.now_ms(), Ordering::Release);
+        self.transition(from, CircuitState::Open, reason);
     }
 
-    pub async fn metrics(&self) -> CircuitBreakerMetrics {
+    fn transition(&self, from: CircuitState, to: CircuitState, reason: &'static str) -> bool {
+        if self
+            .state
+            .compare_exchange(
+                from.as_u8(),
+                to.as_u8(),
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_err()
+        {
+            return false;
+        }
+
+        self.last_state_change_ms
+            .store(self.now_ms(), Ordering::Release);
+
+        if to == CircuitState::Open {
+            self.half_open_calls.store(0, Ordering::Release);
+            self.half_open_successes.store(0, Ordering::Release);
+
+            let snapshot = self.window.snapshot(self.now_ms());
+            tracing::warn!(
+                circuit_breaker = %self.name,
+                from = %from,
+                reason = reason,
+                calls = snapshot.calls,
+                failure_rate = snapshot.failure_rate(),
+                slow_call_rate = snapshot.slow_call_rate(),
+                "Circuit opened"
+            );
+        } else {
+            tracing::info!(
+                circuit_breaker = %self.name,
+                from = %from,
+                to = %to,
+                reason = reason,
+                "Circuit state transition"
+            );
+        }
+
+        metrics::gauge!("circuit_breaker_state", "name" => self.name.clone()).set(to.as_gauge());
+        metrics::counter!(
+            "circuit_breaker_transitions_total",
+            "name" => self.name.clone(),
+            "from" => from.to_string(),
+            "to" => to.to_string(),
+            "reason" => reason
+        )
+        .increment(1);
+
+        let mut transitions = self
+            .transitions
+            .lock()
+            .expect("circuit breaker transition log poisoned");
+        if transitions.len() == MAX_TRANSITION_EVENTS {
+            transitions.pop_front();
+        }
+        transitions.push_back(TransitionEvent {
+            from,
+            to,
+            reason,
+            at: Utc::now(),
+        });
+
+        true
+    }
+
+    fn is_slow(&self, duration: Duration) -> bool {
+        duration >= self.config.slow_call_duration
+    }
+
+    fn now_ms(&self) -> u64 {
+        self.created_at.elapsed().as_millis() as u64
+    }
+
+    fn record_call(&self, outcome: &'static str) {
+        metrics::counter!(
+            "circuit_breaker_calls_total",
+            "name" => self.name.clone(),
+            "outcome" => outcome
+        )
+        .increment(1);
+    }
+
+    pub fn state(&self) -> CircuitState {
+        CircuitState::from_u8(self.state.load(Ordering::Acquire))
+    }
+
+    pub fn metrics(&self) -> CircuitBreakerMetrics {
+        let now = self.now_ms();
+        let window = self.window.snapshot(now);
+        let last_failure_ms = self.last_failure_ms.load(Ordering::Acquire);
+
         CircuitBreakerMetrics {
             name: self.name.clone(),
-            state: *self.state.read().await,
-            failure_count: *self.failure_count.read().await,
-            success_count: *self.success_count.read().await,
-            last_failure_time: *self.last_failure_time.read().await,
-            time_in_current_state: self.last_state_change.read().await.elapsed(),
+            state: self.state(),
+            calls_in_window: window.calls,
+            failure_count: window.failures,
+            slow_call_count: window.slow,
+            failure_rate: window.failure_rate(),
+            slow_call_rate: window.slow_call_rate(),
+            success_count: self.half_open_successes.load(Ordering::Acquire),
+            last_failure_time: (last_failure_ms != NO_FAILURE)
+                .then(|| self.created_at + Duration::from_millis(last_failure_ms)),
+            time_in_current_state: Duration::from_millis(
+                now.saturating_sub(self.last_state_change_ms.load(Ordering::Acquire)),
+            ),
+            recent_transitions: self
+                .transitions
+                .lock()
+                .expect("circuit breaker transition log poisoned")
+                .iter()
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+}
+
+/// Slot in the half-open trial budget, returned on drop so a cancelled trial
+/// call cannot use it up for good. A trip resets the budget, so permits from
+/// an earlier half-open period are not returned.
+struct HalfOpenPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    opened_at_ms: u64,
+}
+
+impl Drop for HalfOpenPermit<'_> {
+    fn drop(&mut self) {
+        if self.breaker.opened_at_ms.load(Ordering::Acquire) == self.opened_at_ms {
+            let _ = self.breaker.half_open_calls.fetch_update(
+                Ordering::AcqRel,
+                Ordering::Acquire,
+                |calls| calls.checked_sub(1),
+            );
         }
     }
 }
@@ -294,19 +623,293 @@ impl CircuitBreaker {
 pub struct CircuitBreakerMetrics {
     pub name: String,
     pub state: CircuitState,
+    pub calls_in_window: u32,
     pub failure_count: u32,
+    pub slow_call_count: u32,
+    pub failure_rate: f64,
+    pub slow_call_rate: f64,
     pub success_count: u32,
     pub last_failure_time: Option<Instant>,
     pub time_in_current_state: Duration,
+    pub recent_transitions: Vec<TransitionEvent>,
+}
+
+#[derive(Default)]
+pub struct CircuitBreakerRegistry {
+    breakers: std::sync::RwLock<BTreeMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, breaker: Arc<CircuitBreaker>) {
+        self.breakers
+            .write()
+            .expect("circuit breaker registry poisoned")
+            .insert(breaker.name().to_string(), breaker);
+    }
+
+    pub fn get_or_create(&self, name: &str, config: CircuitBreakerConfig) -> Arc<CircuitBreaker> {
+        let mut breakers = self
+            .breakers
+            .write()
+            .expect("circuit breaker registry poisoned");
+
+        Arc::clone(
+            breakers
+                .entry(name.to_string())
+                .or_insert_with(|| Arc::new(CircuitBreaker::with_config(name, config))),
+        )
+    }
+
+    pub fn all(&self) -> Vec<Arc<CircuitBreaker>> {
+        self.breakers
+            .read()
+            .expect("circuit breaker registry poisoned")
+            .values()
+            .cloned()
+            .collect()
+    }
 }
 
 impl From<CircuitError<AppError>> for AppError {
     fn from(err: CircuitError<AppError>) -> Self {
         match err {
-            CircuitError::Open => AppError::ServiceUnavailable,
-            CircuitError::HalfOpenLimit => AppError::ServiceUnavailable,
-            CircuitError::Timeout => AppError::ServiceUnavailable,
+            CircuitError::Open | CircuitError::HalfOpenLimit => {
+                AppError::ServiceUnavailable { retry_after: None }
+            }
+            CircuitError::Timeout => AppError::ServiceUnavailable { retry_after: None },
             CircuitError::Underlying(app_err) => app_err,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::future::pending;
+
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_rate_threshold: 0.5,
+            slow_call_rate_threshold: 0.5,
+            slow_call_duration: Duration::from_secs(5),
+            minimum_calls: 4,
+            window: Duration::from_secs(60),
+            call_timeout: None,
+            success_threshold: 2,
+            open_duration: Duration::from_millis(20),
+            half_open_max_calls: 2,
+        }
+    }
+
+    async fn succeed(breaker: &CircuitBreaker) -> Result<(), CircuitError<&'static str>> {
+        breaker.call_with(|| async { Ok(()) }, |_| true).await
+    }
+
+    async fn fail(breaker: &CircuitBreaker) -> Result<(), CircuitError<&'static str>> {
+        breaker.call_with(|| async { Err("down") }, |_| true).await
+    }
+
+    async fn trip(breaker: &CircuitBreaker) {
+        for _ in 0..breaker.config().minimum_calls {
+            let _ = fail(breaker).await;
+        }
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    fn transitions(breaker: &CircuitBreaker) -> Vec<(CircuitState, CircuitState, &'static str)> {
+        breaker
+            .metrics()
+            .recent_transitions
+            .iter()
+            .map(|event| (event.from, event.to, event.reason))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn failure_rate_opens_only_after_minimum_calls() {
+        let breaker = CircuitBreaker::with_config("test", config());
+
+        for _ in 0..3 {
+            let _ = fail(&breaker).await;
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        succeed(&breaker).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.metrics().calls_in_window, 4);
+        assert_eq!(breaker.metrics().failure_count, 3);
+        assert!(matches!(succeed(&breaker).await, Err(CircuitError::Open)));
+        assert_eq!(
+            transitions(&breaker),
+            [(CircuitState::Closed, CircuitState::Open, "failure_rate")]
+        );
+    }
+
+    #[tokio::test]
+    async fn ignored_errors_do_not_count_as_failures() {
+        let breaker = CircuitBreaker::with_config("test", config());
+
+        for _ in 0..8 {
+            let result: Result<(), _> = breaker
+                .call_with(|| async { Err("not found") }, |_| false)
+                .await;
+            assert!(matches!(result, Err(CircuitError::Underlying("not found"))));
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.metrics().failure_count, 0);
+    }
+
+    #[tokio::test]
+    async fn slow_call_rate_opens_the_circuit() {
+        let breaker = CircuitBreaker::with_config(
+            "test",
+            CircuitBreakerConfig {
+                slow_call_duration: Duration::from_millis(5),
+                minimum_calls: 2,
+                ..config()
+            },
+        );
+
+        for _ in 0..2 {
+            breaker
+                .call_with(
+                    || async {
+                        tokio::time::sleep(Duration::from_millis(10)).await;
+                        Ok::<_, &str>(())
+                    },
+                    |_| true,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(
+            transitions(&breaker),
+            [(CircuitState::Closed, CircuitState::Open, "slow_call_rate")]
+        );
+    }
+
+    #[tokio::test]
+    async fn timeout_is_reported_and_counted_as_failure() {
+        let breaker = CircuitBreaker::with_config(
+            "test",
+            CircuitBreakerConfig {
+                call_timeout: Some(Duration::from_millis(5)),
+                minimum_calls: 1,
+                ..config()
+            },
+        );
+
+        let result = breaker
+            .call_with(pending::<Result<(), &str>>, |_| true)
+            .await;
+
+        assert!(matches!(result, Err(CircuitError::Timeout)));
+        assert_eq!(breaker.metrics().failure_count, 1);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(
+            AppError::from(CircuitError::<AppError>::Timeout),
+            AppError::ServiceUnavailable { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn half_open_closes_after_enough_successful_probes() {
+        let breaker = CircuitBreaker::with_config("test", config());
+        trip(&breaker).await;
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        succeed(&breaker).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        succeed(&breaker).await.unwrap();
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.metrics().calls_in_window, 0);
+        assert_eq!(
+            transitions(&breaker),
+            [
+                (CircuitState::Closed, CircuitState::Open, "failure_rate"),
+                (
+                    CircuitState::Open,
+                    CircuitState::HalfOpen,
+                    "open_duration_elapsed"
+                ),
+                (CircuitState::HalfOpen, CircuitState::Closed, "recovered"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_probe_reopens_the_circuit() {
+        let breaker = CircuitBreaker::with_config("test", config());
+        trip(&breaker).await;
+
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let _ = fail(&breaker).await;
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(matches!(succeed(&breaker).await, Err(CircuitError::Open)));
+        assert_eq!(
+            transitions(&breaker).last(),
+            Some(&(CircuitState::HalfOpen, CircuitState::Open, "probe_failed"))
+        );
+    }
+
+    #[tokio::test]
+    async fn half_open_limit_rejects_extra_probes_while_in_flight() {
+        let breaker = CircuitBreaker::with_config(
+            "test",
+            CircuitBreakerConfig {
+                half_open_max_calls: 1,
+                ..config()
+            },
+        );
+        trip(&breaker).await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        let mut probe = Box::pin(breaker.call_with(pending::<Result<(), &str>>, |_| true));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(5), &mut probe)
+                .await
+                .is_err()
+        );
+
+        assert!(matches!(
+            succeed(&breaker).await,
+            Err(CircuitError::HalfOpenLimit)
+        ));
+    }
+
+    #[tokio::test]
+    async fn cancelled_probe_returns_its_half_open_permit() {
+        let breaker = CircuitBreaker::with_config(
+            "test",
+            CircuitBreakerConfig {
+                half_open_max_calls: 1,
+                success_threshold: 1,
+                ..config()
+            },
+        );
+        trip(&breaker).await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+
+        for _ in 0..3 {
+            let probe = breaker.call_with(pending::<Result<(), &str>>, |_| true);
+            assert!(
+                tokio::time::timeout(Duration::from_millis(5), probe)
+                    .await
+                    .is_err()
+            );
+        }
+
+        succeed(&breaker).await.unwrap();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+}
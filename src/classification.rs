@@ -0,0 +1,220 @@
+use bigdecimal::ToPrimitive;
+
+use crate::config::ClassificationConfig;
+use crate::models::{AbcClass, CarClassification, ClassificationInput, XyzClass};
+
+/// Ranks cars by revenue and assigns ABC and XYZ classes.
+///
+/// A car is A while the revenue ranked above it is below `a_revenue_share`
+/// of the total, so the car crossing the boundary still counts as A; B
+/// follows the same rule with `b_revenue_share`. Cars without revenue are
+/// always C. XYZ compares the coefficient of variation of weekly demand
+/// against `x_max_cv` and `y_max_cv`; cars that never sold are Z.
+pub fn classify(
+    inputs: Vec<ClassificationInput>,
+    config: &ClassificationConfig,
+) -> Vec<CarClassification> {
+    let mut ranked: Vec<(ClassificationInput, f64)> = inputs
+        .into_iter()
+        .map(|input| {
+            let revenue = input.revenue.to_f64().unwrap_or(0.0).max(0.0);
+            (input, revenue)
+        })
+        .collect();
+    ranked.sort_by(|(a, a_revenue), (b, b_revenue)| {
+        b_revenue
+            .total_cmp(a_revenue)
+            .then_with(|| a.car_id.as_str().cmp(b.car_id.as_str()))
+    });
+
+    let total: f64 = ranked.iter().map(|(_, revenue)| revenue).sum();
+    let mut cumulative = 0.0;
+
+    ranked
+        .into_iter()
+        .map(|(input, revenue)| {
+            let share = if total > 0.0 { revenue / total } else { 0.0 };
+            let abc_class = if revenue <= 0.0 {
+                AbcClass::C
+            } else if cumulative < config.a_revenue_share {
+                AbcClass::A
+            } else if cumulative < config.b_revenue_share {
+                AbcClass::B
+            } else {
+                AbcClass::C
+            };
+            cumulative += share;
+
+            let demand_cv = coefficient_of_variation(&input.weekly_units);
+            let xyz_class = match demand_cv {
+                Some(cv) if cv <= config.x_max_cv => XyzClass::X,
+                Some(cv) if cv <= config.y_max_cv => XyzClass::Y,
+                _ => XyzClass::Z,
+            };
+
+            CarClassification {
+                car_id: input.car_id,
+                brand: input.brand,
+                model: input.model,
+                revenue: input.revenue,
+                units_sold: input.units_sold,
+                revenue_share: share,
+                cumulative_share: cumulative.min(1.0),
+                demand_cv,
+                abc_class,
+                xyz_class,
+            }
+        })
+        .collect()
+}
+
+/// Population standard deviation over the mean; `None` when nothing sold.
+fn coefficient_of_variation(series: &[i64]) -> Option<f64> {
+    if series.is_empty() {
+        return None;
+    }
+
+    let n = series.len() as f64;
+    let mean = series.iter().sum::<i64>() as f64 / n;
+    if mean <= 0.0 {
+        return None;
+    }
+
+    let variance = series
+        .iter()
+        .map(|&units| (units as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    Some(variance.sqrt() / mean)
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+
+    use crate::models::CarId;
+
+    use super::*;
+
+    fn input(car_id: &str, revenue: i64, weekly_units: &[i64]) -> ClassificationInput {
+        ClassificationInput {
+            car_id: CarId::new(car_id.to_string()).unwrap(),
+            brand: "Toyota".to_string(),
+            model: "Corolla".to_string(),
+            revenue: BigDecimal::from(revenue),
+            units_sold: weekly_units.iter().sum(),
+            weekly_units: weekly_units.to_vec(),
+        }
+    }
+
+    fn classes(classified: &[CarClassification]) -> Vec<(&str, AbcClass)> {
+        classified
+            .iter()
+            .map(|c| (c.car_id.as_str(), c.abc_class))
+            .collect()
+    }
+
+    fn xyz(weekly_units: &[i64]) -> (Option<f64>, XyzClass) {
+        let classified = classify(
+            vec![input("C0001", 100, weekly_units)],
+            &ClassificationConfig::default(),
+        );
+        (classified[0].demand_cv, classified[0].xyz_class)
+    }
+
+    #[test]
+    fn abc_boundary_car_keeps_the_higher_class() {
+        // Shares are powers of two so the cumulative sums are exact.
+        let config = ClassificationConfig {
+            a_revenue_share: 0.75,
+            b_revenue_share: 0.9375,
+            ..ClassificationConfig::default()
+        };
+        let inputs = vec![
+            input("C0005", 16, &[1]),
+            input("C0003", 192, &[1]),
+            input("C0001", 512, &[1]),
+            input("C0004", 48, &[1]),
+            input("C0002", 256, &[1]),
+        ];
+
+        let classified = classify(inputs, &config);
+
+        // C0002 crosses 0.75 and stays A; C0003 starts exactly on it and is B.
+        assert_eq!(
+            classes(&classified),
+            [
+                ("C0001", AbcClass::A),
+                ("C0002", AbcClass::A),
+                ("C0003", AbcClass::B),
+                ("C0004", AbcClass::C),
+                ("C0005", AbcClass::C),
+            ]
+        );
+        let cumulative: Vec<f64> = classified.iter().map(|c| c.cumulative_share).collect();
+        assert_eq!(cumulative, [0.5, 0.75, 0.9375, 0.984375, 1.0]);
+        assert_eq!(classified[2].revenue_share, 0.1875);
+    }
+
+    #[test]
+    fn equal_revenue_ranks_by_car_id() {
+        let classified = classify(
+            vec![
+                input("C0002", 10, &[1]),
+                input("C0001", 10, &[1]),
+                input("C0003", 80, &[1]),
+            ],
+            &ClassificationConfig::default(),
+        );
+
+        let order: Vec<&str> = classified.iter().map(|c| c.car_id.as_str()).collect();
+        assert_eq!(order, ["C0003", "C0001", "C0002"]);
+        assert_eq!(classified[1].revenue_share, classified[2].revenue_share);
+    }
+
+    #[test]
+    fn xyz_thresholds_are_inclusive() {
+        assert_eq!(xyz(&[4, 4, 4, 4]), (Some(0.0), XyzClass::X));
+        // Mean 2, deviation 1.
+        assert_eq!(xyz(&[1, 3, 1, 3]), (Some(0.5), XyzClass::X));
+        // Mean 2.5, deviation 1.5.
+        assert_eq!(xyz(&[1, 4]), (Some(0.6), XyzClass::Y));
+        // Mean 1, deviation 1.
+        assert_eq!(xyz(&[0, 2, 0, 2]), (Some(1.0), XyzClass::Y));
+
+        let (cv, class) = xyz(&[0, 0, 0, 4]);
+        assert!((cv.unwrap() - 3f64.sqrt()).abs() < 1e-9);
+        assert_eq!(class, XyzClass::Z);
+    }
+
+    #[test]
+    fn cars_without_sales_are_c_and_z() {
+        let classified = classify(
+            vec![
+                input("C0001", 0, &[0, 0, 0]),
+                input("C0002", 0, &[]),
+                input("C0003", 100, &[2, 2, 2]),
+            ],
+            &ClassificationConfig::default(),
+        );
+
+        assert_eq!(classified[0].car_id.as_str(), "C0003");
+        for unsold in &classified[1..] {
+            assert_eq!(unsold.abc_class, AbcClass::C);
+            assert_eq!(unsold.xyz_class, XyzClass::Z);
+            assert_eq!(unsold.demand_cv, None);
+            assert_eq!(unsold.revenue_share, 0.0);
+        }
+    }
+
+    #[test]
+    fn no_revenue_at_all_leaves_every_car_c() {
+        let classified = classify(
+            vec![input("C0001", 0, &[0]), input("C0002", 0, &[0])],
+            &ClassificationConfig::default(),
+        );
+
+        assert!(classified.iter().all(|c| c.abc_class == AbcClass::C));
+        assert!(classified.iter().all(|c| c.cumulative_share == 0.0));
+    }
+}
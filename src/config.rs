@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::time::Duration;
 
 use config::{Config, Environment, File};
@@ -11,6 +13,8 @@ use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use validator::{Validate, ValidationError};
 
 use crate::error::AppError;
+use crate::models::EngineType;
+use crate::scheduler::JobSchedule;
 
 static LOG_LEVEL_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"^(trace|debug|info|warn|error|TRACE|DEBUG|INFO|WARN|ERROR)$")
@@ -43,6 +47,39 @@ pub struct AppConfig {
 
     #[serde(default)]
     pub runtime: RuntimeConfig,
+
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    #[serde(default)]
+    pub resilience: ResilienceConfig,
+
+    #[serde(default)]
+    pub rate_limiting: RateLimitConfig,
+
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+
+    #[serde(default)]
+    pub reorder_planning: ReorderPlanningConfig,
+
+    #[serde(default)]
+    pub rebalancing: RebalancingConfig,
+
+    #[serde(default)]
+    pub classification: ClassificationConfig,
+
+    #[serde(default)]
+    pub aging: AgingConfig,
+
+    #[serde(default)]
+    pub depreciation: DepreciationConfig,
+
+    #[serde(default)]
+    pub price_history: PriceHistoryConfig,
+
+    #[serde(default)]
+    pub pricing: PricingConfig,
 }
 
 #[derive(Debug, Clone, Validate, Deserialize)]
@@ -68,6 +105,12 @@ pub struct ServerConfig {
 
     #[validate(range(min = 1, max = 60))]
     pub shutdown_timeout_seconds: u64,
+
+    /// How long readiness reports failing before the listener stops accepting.
+    #[validate(range(max = 60))]
+    #[serde(default = "default_readiness_drain_delay_seconds")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub readiness_drain_delay_seconds: u64,
 }
 
 impl ServerConfig {
@@ -79,6 +122,10 @@ impl ServerConfig {
         Duration::from_secs(self.request_timeout_seconds)
     }
 
+    pub fn readiness_drain_delay(&self) -> Duration {
+        Duration::from_secs(self.readiness_drain_delay_seconds)
+    }
+
     pub fn shutdown_timeout(&self) -> Duration {
         Duration::from_secs(self.shutdown_timeout_seconds)
     }
@@ -116,6 +163,70 @@ pub struct DatabaseConfig {
     #[serde(default = "default_health_check_acquire_timeout_ms")]
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub health_check_acquire_timeout_ms: u64,
+
+    #[serde(default)]
+    pub replica_urls: Option<SecretString>,
+
+    #[serde(default = "default_max_replica_lag_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_replica_lag_ms: u64,
+
+    #[serde(default)]
+    pub pool_scaling: PoolScalingConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PoolScalingConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub min_connections: Option<u32>,
+
+    #[serde(default)]
+    pub max_connections: Option<u32>,
+
+    #[serde(default = "default_target_utilization")]
+    pub target_utilization: f64,
+
+    #[serde(default = "default_scale_up_threshold")]
+    pub scale_up_threshold: f64,
+
+    #[serde(default = "default_scale_down_threshold")]
+    pub scale_down_threshold: f64,
+
+    #[serde(default = "default_scaling_check_interval_secs")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub check_interval_secs: u64,
+
+    #[serde(default = "default_scaling_health_check_interval_secs")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub health_check_interval_secs: u64,
+
+    #[serde(default = "default_slow_acquire_threshold_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub slow_acquire_threshold_ms: u64,
+
+    #[serde(default = "default_scaling_cooldown_secs")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for PoolScalingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            min_connections: None,
+            max_connections: None,
+            target_utilization: default_target_utilization(),
+            scale_up_threshold: default_scale_up_threshold(),
+            scale_down_threshold: default_scale_down_threshold(),
+            check_interval_secs: default_scaling_check_interval_secs(),
+            health_check_interval_secs: default_scaling_health_check_interval_secs(),
+            slow_acquire_threshold_ms: default_slow_acquire_threshold_ms(),
+            cooldown_secs: default_scaling_cooldown_secs(),
+        }
+    }
 }
 
 impl DatabaseConfig {
@@ -138,6 +249,24 @@ impl DatabaseConfig {
     pub fn health_check_acquire_timeout(&self) -> Duration {
         Duration::from_millis(self.health_check_acquire_timeout_ms)
     }
+
+    pub fn replica_urls(&self) -> Vec<SecretString> {
+        self.replica_urls
+            .as_ref()
+            .map(|urls| {
+                urls.expose_secret()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|url| !url.is_empty())
+                    .map(|url| SecretString::from(url.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn max_replica_lag(&self) -> Duration {
+        Duration::from_millis(self.max_replica_lag_ms)
+    }
 }
 
 impl Validate for DatabaseConfig {
@@ -331,6 +460,17 @@ pub struct ObservabilityConfig {
     #[validate(length(min = 1))]
     #[serde(default = "default_otel_endpoint")]
     pub otel_endpoint: String,
+
+    #[serde(default)]
+    pub otel_protocol: OtelProtocol,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtelProtocol {
+    #[default]
+    Grpc,
+    Http,
 }
 
 fn validate_log_level(level: &str) -> Result<(), ValidationError> {
@@ -350,153 +490,1058 @@ pub struct FeaturesConfig {
     pub enable_rate_limiting: bool,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
-pub enum EnvironmentType {
-    #[serde(rename = "development")]
-    Development,
-    #[serde(rename = "staging")]
-    Staging,
-    #[serde(rename = "production")]
-    Production,
-    #[serde(rename = "test")]
-    Test,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackendKind {
+    #[default]
+    Memory,
+    Redis,
 }
 
-impl EnvironmentType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Development => "development",
-            Self::Staging => "staging",
-            Self::Production => "production",
-            Self::Test => "test",
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheConfig {
+    #[serde(default)]
+    pub backend: CacheBackendKind,
+
+    #[serde(default)]
+    pub redis_url: Option<SecretString>,
+
+    #[serde(default = "default_cache_key_prefix")]
+    pub key_prefix: String,
+
+    #[serde(default = "default_cache_connect_timeout_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub connect_timeout_ms: u64,
+
+    #[serde(default = "default_cache_response_timeout_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub response_timeout_ms: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            backend: CacheBackendKind::default(),
+            redis_url: None,
+            key_prefix: default_cache_key_prefix(),
+            connect_timeout_ms: default_cache_connect_timeout_ms(),
+            response_timeout_ms: default_cache_response_timeout_ms(),
         }
     }
+}
+
+impl CacheConfig {
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_millis(self.connect_timeout_ms)
+    }
 
-    pub fn is_production(&self) -> bool {
-        matches!(self, Self::Production)
+    pub fn response_timeout(&self) -> Duration {
+        Duration::from_millis(self.response_timeout_ms)
     }
 }
 
-fn default_environment() -> EnvironmentType {
-    EnvironmentType::Development
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResilienceConfig {
+    #[serde(default)]
+    pub bulkheads: BulkheadsConfig,
+
+    #[serde(default)]
+    pub load_shedding: LoadSheddingConfig,
 }
 
-fn deserialize_secret_string<'de, D>(deserializer: D) -> Result<SecretString, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s = String::deserialize(deserializer)?;
-    if s.trim().is_empty() {
-        return Err(serde::de::Error::custom("DATABASE_URL cannot be empty"));
-    }
-    Ok(SecretString::from(s))
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkheadsConfig {
+    #[serde(default = "default_catalogue_bulkhead")]
+    pub catalogue: BulkheadConfig,
+
+    #[serde(default = "default_reservations_bulkhead")]
+    pub reservations: BulkheadConfig,
+
+    #[serde(default = "default_analytics_bulkhead")]
+    pub analytics: BulkheadConfig,
 }
 
-fn default_true() -> bool {
-    true
+impl Default for BulkheadsConfig {
+    fn default() -> Self {
+        Self {
+            catalogue: default_catalogue_bulkhead(),
+            reservations: default_reservations_bulkhead(),
+            analytics: default_analytics_bulkhead(),
+        }
+    }
 }
 
-fn default_false() -> bool {
-    false
+#[derive(Debug, Clone, Deserialize)]
+pub struct BulkheadConfig {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_concurrent: usize,
+
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_wait_ms: u64,
 }
 
-fn default_max_connections() -> u32 {
-    10
+impl BulkheadConfig {
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_millis(self.max_wait_ms)
+    }
 }
 
-fn default_min_connections() -> u32 {
-    2
+#[derive(Debug, Clone, Deserialize)]
+pub struct LoadSheddingConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    #[serde(default = "default_shed_pool_wait_threshold_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub pool_wait_threshold_ms: u64,
+
+    #[serde(default = "default_shed_retry_after_secs")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub retry_after_secs: u64,
 }
 
-fn default_max_lifetime() -> u64 {
-    1800
+impl Default for LoadSheddingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            pool_wait_threshold_ms: default_shed_pool_wait_threshold_ms(),
+            retry_after_secs: default_shed_retry_after_secs(),
+        }
+    }
 }
 
-fn default_acquire_timeout_seconds() -> u64 {
-    5
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrincipalKind {
+    ApiKey,
+    User,
+    Tenant,
 }
 
-fn default_idle_timeout() -> u64 {
-    600
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitConfig {
+    /// Comma-separated principal precedence, e.g. "api_key,user,tenant".
+    /// Requests carrying none of them are keyed by client IP.
+    #[serde(default = "default_principal_precedence")]
+    pub principal_precedence: String,
+
+    /// Comma-separated proxy IPs whose `X-Forwarded-For` is trusted. Only
+    /// these proxies may assert user and tenant principals.
+    #[serde(default)]
+    pub trusted_proxies: String,
+
+    /// Comma-separated API keys accepted as principals from direct clients.
+    /// Any other `X-API-Key` is ignored and the request is keyed by IP.
+    #[serde(default)]
+    pub api_keys: Option<SecretString>,
+
+    #[serde(default = "default_catalogue_rate_limit")]
+    pub catalogue: RateLimitPolicy,
+
+    #[serde(default = "default_reservations_rate_limit")]
+    pub reservations: RateLimitPolicy,
+
+    #[serde(default = "default_analytics_rate_limit")]
+    pub analytics: RateLimitPolicy,
 }
 
-fn default_max_age() -> u64 {
-    3600
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            principal_precedence: default_principal_precedence(),
+            trusted_proxies: String::new(),
+            api_keys: None,
+            catalogue: default_catalogue_rate_limit(),
+            reservations: default_reservations_rate_limit(),
+            analytics: default_analytics_rate_limit(),
+        }
+    }
 }
 
-fn default_health_check_timeout_seconds() -> u64 {
-    3
+impl RateLimitConfig {
+    pub fn principal_precedence(&self) -> Result<Vec<PrincipalKind>, AppError> {
+        self.principal_precedence
+            .split(',')
+            .map(str::trim)
+            .filter(|kind| !kind.is_empty())
+            .map(|kind| match kind {
+                "api_key" => Ok(PrincipalKind::ApiKey),
+                "user" => Ok(PrincipalKind::User),
+                "tenant" => Ok(PrincipalKind::Tenant),
+                other => Err(AppError::ConfigError(format!(
+                    "Unknown rate limit principal '{}'. Expected api_key, user or tenant",
+                    other
+                ))),
+            })
+            .collect()
+    }
+
+    pub fn trusted_proxies(&self) -> Result<Vec<IpAddr>, AppError> {
+        self.trusted_proxies
+            .split(',')
+            .map(str::trim)
+            .filter(|ip| !ip.is_empty())
+            .map(|ip| {
+                ip.parse().map_err(|_| {
+                    AppError::ConfigError(format!("Invalid trusted proxy address '{}'", ip))
+                })
+            })
+            .collect()
+    }
+
+    pub fn api_keys(&self) -> HashSet<String> {
+        self.api_keys
+            .as_ref()
+            .map(|keys| {
+                keys.expose_secret()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
-fn default_health_check_acquire_timeout_ms() -> u64 {
-    500
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimitPolicy {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub requests_per_second: f64,
+
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub burst: u32,
 }
 
-fn default_cors_methods() -> Vec<String> {
-    vec![
-        "GET".to_string(),
-        "POST".to_string(),
-        "PUT".to_string(),
-        "DELETE".to_string(),
-    ]
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchedulerConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Per-job settings keyed by job name. Jobs without an entry use the
+    /// `JobScheduleConfig` defaults.
+    #[serde(default)]
+    pub jobs: HashMap<String, JobScheduleConfig>,
+
+    #[serde(default)]
+    pub leader_election: LeaderElectionConfig,
 }
 
-fn default_otel_endpoint() -> String {
-    "http://localhost:4317".to_string()
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            jobs: HashMap::new(),
+            leader_election: LeaderElectionConfig::default(),
+        }
+    }
 }
 
-pub fn load_config() -> Result<AppConfig, AppError> {
-    let environment = std::env::var("APP_ENVIRONMENT")
-        .unwrap_or_else(|_| "development".into())
-        .to_lowercase();
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderElectionConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
 
-    let env_type = match environment.as_str() {
-        "production" | "prod" => EnvironmentType::Production,
-        "staging" | "stg" => EnvironmentType::Staging,
-        "test" | "testing" => EnvironmentType::Test,
-        _ => EnvironmentType::Development,
-    };
+    /// Identifies this replica in health output. Defaults to `$HOSTNAME`.
+    #[serde(default)]
+    pub instance_id: Option<String>,
 
-    tracing::info!(
-        "Loading configuration for environment: {}",
-        env_type.as_str()
-    );
+    /// First key of the two-key advisory locks; must be the same on every
+    /// replica sharing the database.
+    #[serde(default = "default_leader_lock_namespace")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub lock_namespace: i32,
 
-    let mut builder = Config::builder()
-        .set_default("default", true)
-        .map_err(|e| AppError::ConfigError(e.to_string()))?
-        .add_source(File::with_name("config/default").required(false))
-        .add_source(File::with_name(&format!("config/{}", env_type.as_str())).required(false))
-        .add_source(File::with_name("config/local").required(false))
-        .add_source(
-            Environment::with_prefix("APP")
-                .prefix_separator("__")
-                .separator("__"),
-        );
+    #[serde(default = "default_leader_retry_interval_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub retry_interval_ms: u64,
+}
 
-    if let Ok(db_url) = std::env::var("DATABASE_URL") {
-        builder = builder
-            .set_override("database.url", db_url)
-            .map_err(|e| AppError::ConfigError(e.to_string()))?;
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            instance_id: None,
+            lock_namespace: default_leader_lock_namespace(),
+            retry_interval_ms: default_leader_retry_interval_ms(),
+        }
     }
+}
 
-    let config = builder
-        .build()
-        .map_err(|e| AppError::ConfigError(format!("Failed to build config: {}", e)))?;
+impl LeaderElectionConfig {
+    pub fn instance_id(&self) -> String {
+        self.instance_id
+            .clone()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(|| format!("instance-{}", std::process::id()))
+    }
 
-    let mut app_config: AppConfig = config
-        .try_deserialize()
-        .map_err(|e| AppError::ConfigError(format!("Failed to deserialize config: {}", e)))?;
+    pub fn retry_interval(&self) -> Duration {
+        Duration::from_millis(self.retry_interval_ms)
+    }
+}
 
-    app_config.environment = env_type;
+fn default_leader_lock_namespace() -> i32 {
+    0x4A4F42
+}
 
-    app_config
-        .validate()
-        .map_err(|e| AppError::ConfigError(format!("Configuration validation failed: {}", e)))?;
+fn default_leader_retry_interval_ms() -> u64 {
+    5000
+}
 
-    validate_business_rules(&app_config)?;
+/// Inputs of the `reorder_planning` job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReorderPlanningConfig {
+    /// z-score of the target cycle service level (1.65 ≈ 95%).
+    #[serde(default = "default_service_level_z")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub service_level_z: f64,
 
-    log_config_loaded(&app_config);
+    /// Used for cars without their own `lead_time_days`.
+    #[serde(default = "default_lead_time_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub default_lead_time_days: u32,
+
+    /// Fixed cost of placing one purchase order.
+    #[serde(default = "default_ordering_cost")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub ordering_cost: f64,
+
+    /// Annual holding cost as a fraction of the unit price.
+    #[serde(default = "default_holding_cost_rate")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub holding_cost_rate: f64,
+
+    #[serde(default = "default_planning_history_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub history_days: u32,
+}
+
+impl Default for ReorderPlanningConfig {
+    fn default() -> Self {
+        Self {
+            service_level_z: default_service_level_z(),
+            default_lead_time_days: default_lead_time_days(),
+            ordering_cost: default_ordering_cost(),
+            holding_cost_rate: default_holding_cost_rate(),
+            history_days: default_planning_history_days(),
+        }
+    }
+}
+
+fn default_service_level_z() -> f64 {
+    1.65
+}
+
+fn default_lead_time_days() -> u32 {
+    14
+}
+
+fn default_ordering_cost() -> f64 {
+    500.0
+}
+
+fn default_holding_cost_rate() -> f64 {
+    0.25
+}
+
+fn default_planning_history_days() -> u32 {
+    90
+}
+
+/// Inputs of inter-warehouse rebalancing proposals.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RebalancingConfig {
+    /// Sales window used for per-warehouse daily demand.
+    #[serde(default = "default_rebalancing_demand_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub demand_days: u32,
+
+    /// Days of demand each location should hold; stock above that is
+    /// surplus that may be moved.
+    #[serde(default = "default_target_cover_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub target_cover_days: u32,
+
+    /// Proposals moving fewer units are dropped.
+    #[serde(default = "default_min_transfer_quantity")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub min_transfer_quantity: i32,
+}
+
+impl Default for RebalancingConfig {
+    fn default() -> Self {
+        Self {
+            demand_days: default_rebalancing_demand_days(),
+            target_cover_days: default_target_cover_days(),
+            min_transfer_quantity: default_min_transfer_quantity(),
+        }
+    }
+}
+
+fn default_rebalancing_demand_days() -> u32 {
+    30
+}
+
+fn default_target_cover_days() -> u32 {
+    14
+}
+
+fn default_min_transfer_quantity() -> i32 {
+    1
+}
+
+/// Boundaries of the ABC (revenue) and XYZ (demand variability) classes.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClassificationConfig {
+    /// Weeks of sales the classes are computed over.
+    #[serde(default = "default_classification_history_weeks")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub history_weeks: u32,
+
+    /// Cumulative revenue share covered by class A cars.
+    #[serde(default = "default_a_revenue_share")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub a_revenue_share: f64,
+
+    /// Cumulative revenue share covered by class A and B cars together.
+    #[serde(default = "default_b_revenue_share")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub b_revenue_share: f64,
+
+    /// Highest coefficient of variation of weekly demand for class X.
+    #[serde(default = "default_x_max_cv")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub x_max_cv: f64,
+
+    /// Highest coefficient of variation of weekly demand for class Y.
+    #[serde(default = "default_y_max_cv")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub y_max_cv: f64,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        Self {
+            history_weeks: default_classification_history_weeks(),
+            a_revenue_share: default_a_revenue_share(),
+            b_revenue_share: default_b_revenue_share(),
+            x_max_cv: default_x_max_cv(),
+            y_max_cv: default_y_max_cv(),
+        }
+    }
+}
+
+fn default_classification_history_weeks() -> u32 {
+    26
+}
+
+fn default_a_revenue_share() -> f64 {
+    0.8
+}
+
+fn default_b_revenue_share() -> f64 {
+    0.95
+}
+
+fn default_x_max_cv() -> f64 {
+    0.5
+}
+
+fn default_y_max_cv() -> f64 {
+    1.0
+}
+
+/// When aged stock counts as a slow mover.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgingConfig {
+    /// Sales window behind the average daily sales of a car.
+    #[serde(default = "default_aging_sales_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub sales_days: u32,
+
+    /// Minimum days in stock of the oldest unit.
+    #[serde(default = "default_slow_mover_age_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub slow_mover_age_days: u32,
+
+    /// Minimum days of supply at current sales; cars without sales always qualify.
+    #[serde(default = "default_slow_mover_supply_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub slow_mover_supply_days: u32,
+}
+
+impl Default for AgingConfig {
+    fn default() -> Self {
+        Self {
+            sales_days: default_aging_sales_days(),
+            slow_mover_age_days: default_slow_mover_age_days(),
+            slow_mover_supply_days: default_slow_mover_supply_days(),
+        }
+    }
+}
+
+fn default_aging_sales_days() -> u32 {
+    30
+}
+
+fn default_slow_mover_age_days() -> u32 {
+    90
+}
+
+fn default_slow_mover_supply_days() -> u32 {
+    90
+}
+
+/// How sales velocity is compared around a price change.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PriceHistoryConfig {
+    /// Days of sales on each side of a price change; shorter when another
+    /// change or today comes first.
+    #[serde(default = "default_elasticity_window_days")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub elasticity_window_days: u32,
+}
+
+impl Default for PriceHistoryConfig {
+    fn default() -> Self {
+        Self {
+            elasticity_window_days: default_elasticity_window_days(),
+        }
+    }
+}
+
+fn default_elasticity_window_days() -> u32 {
+    30
+}
+
+/// Limits on what pricing rules may take off a sale.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricingConfig {
+    /// Lowest share of the list price a unit may sell for, whatever rules apply.
+    #[serde(default = "default_min_price_ratio")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub min_price_ratio: f64,
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            min_price_ratio: default_min_price_ratio(),
+        }
+    }
+}
+
+fn default_min_price_ratio() -> f64 {
+    0.7
+}
+
+/// How much of its list price a car retains with age.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum DepreciationCurve {
+    /// Loses the same amount every year until it reaches `salvage_ratio`.
+    StraightLine {
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        useful_life_years: f64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        salvage_ratio: f64,
+    },
+    /// Loses `annual_rate` of its remaining value every year, down to `salvage_ratio`.
+    DecliningBalance {
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        annual_rate: f64,
+        #[serde(deserialize_with = "deserialize_number_from_string")]
+        salvage_ratio: f64,
+    },
+    /// Retained ratio at each whole year of age, interpolated in between;
+    /// the last entry holds for older cars.
+    Custom { retained_by_year: Vec<f64> },
+}
+
+impl DepreciationCurve {
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::StraightLine { .. } => "straight_line",
+            Self::DecliningBalance { .. } => "declining_balance",
+            Self::Custom { .. } => "custom",
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            Self::StraightLine {
+                useful_life_years,
+                salvage_ratio,
+            } => *useful_life_years > 0.0 && (0.0..1.0).contains(salvage_ratio),
+            Self::DecliningBalance {
+                annual_rate,
+                salvage_ratio,
+            } => *annual_rate > 0.0 && *annual_rate < 1.0 && (0.0..1.0).contains(salvage_ratio),
+            Self::Custom { retained_by_year } => {
+                !retained_by_year.is_empty()
+                    && retained_by_year.iter().all(|r| (0.0..=1.0).contains(r))
+                    && retained_by_year.windows(2).all(|w| w[1] <= w[0])
+            }
+        }
+    }
+}
+
+/// Depreciation curve per engine type.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EngineDepreciationCurves {
+    #[serde(default = "default_electric_curve")]
+    pub electric: DepreciationCurve,
+
+    #[serde(default = "default_hybrid_curve")]
+    pub hybrid: DepreciationCurve,
+
+    #[serde(default = "default_combustion_curve")]
+    pub gasoline: DepreciationCurve,
+
+    #[serde(default = "default_diesel_curve")]
+    pub diesel: DepreciationCurve,
+
+    #[serde(default = "default_combustion_curve")]
+    pub petrol: DepreciationCurve,
+}
+
+impl EngineDepreciationCurves {
+    pub fn for_engine(&self, engine_type: &EngineType) -> &DepreciationCurve {
+        match engine_type {
+            EngineType::Electric => &self.electric,
+            EngineType::Hybrid => &self.hybrid,
+            EngineType::Gasoline => &self.gasoline,
+            EngineType::Diesel => &self.diesel,
+            EngineType::Petrol => &self.petrol,
+        }
+    }
+
+    fn all(&self) -> [(&'static str, &DepreciationCurve); 5] {
+        [
+            ("electric", &self.electric),
+            ("hybrid", &self.hybrid),
+            ("gasoline", &self.gasoline),
+            ("diesel", &self.diesel),
+            ("petrol", &self.petrol),
+        ]
+    }
+}
+
+impl Default for EngineDepreciationCurves {
+    fn default() -> Self {
+        Self {
+            electric: default_electric_curve(),
+            hybrid: default_hybrid_curve(),
+            gasoline: default_combustion_curve(),
+            diesel: default_diesel_curve(),
+            petrol: default_combustion_curve(),
+        }
+    }
+}
+
+/// Inputs of the depreciation engine behind car valuations.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DepreciationConfig {
+    #[serde(default)]
+    pub curves: EngineDepreciationCurves,
+
+    /// Mileage considered normal for each year of age.
+    #[serde(default = "default_expected_km_per_year")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub expected_km_per_year: u32,
+
+    /// Value ratio lost (or gained) per 1000 km above (or below) normal mileage.
+    #[serde(default = "default_mileage_adjustment_per_1000_km")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub mileage_adjustment_per_1000_km: f64,
+
+    /// Cap on the mileage adjustment in either direction.
+    #[serde(default = "default_max_mileage_adjustment")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_mileage_adjustment: f64,
+}
+
+impl Default for DepreciationConfig {
+    fn default() -> Self {
+        Self {
+            curves: EngineDepreciationCurves::default(),
+            expected_km_per_year: default_expected_km_per_year(),
+            mileage_adjustment_per_1000_km: default_mileage_adjustment_per_1000_km(),
+            max_mileage_adjustment: default_max_mileage_adjustment(),
+        }
+    }
+}
+
+fn default_electric_curve() -> DepreciationCurve {
+    DepreciationCurve::Custom {
+        retained_by_year: vec![
+            1.0, 0.75, 0.63, 0.54, 0.47, 0.41, 0.36, 0.32, 0.28, 0.25, 0.22,
+        ],
+    }
+}
+
+fn default_diesel_curve() -> DepreciationCurve {
+    DepreciationCurve::Custom {
+        retained_by_year: vec![
+            1.0, 0.85, 0.76, 0.68, 0.61, 0.55, 0.50, 0.45, 0.41, 0.37, 0.34,
+        ],
+    }
+}
+
+fn default_hybrid_curve() -> DepreciationCurve {
+    DepreciationCurve::StraightLine {
+        useful_life_years: 12.0,
+        salvage_ratio: 0.15,
+    }
+}
+
+fn default_combustion_curve() -> DepreciationCurve {
+    DepreciationCurve::DecliningBalance {
+        annual_rate: 0.15,
+        salvage_ratio: 0.1,
+    }
+}
+
+fn default_expected_km_per_year() -> u32 {
+    15000
+}
+
+fn default_mileage_adjustment_per_1000_km() -> f64 {
+    0.003
+}
+
+fn default_max_mileage_adjustment() -> f64 {
+    0.25
+}
+
+impl SchedulerConfig {
+    pub fn job(&self, name: &str) -> JobScheduleConfig {
+        self.jobs.get(name).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobScheduleConfig {
+    /// Six-field cron expression (`sec min hour day month weekday`, UTC).
+    /// When set it takes precedence over `interval_seconds`.
+    #[serde(default)]
+    pub cron: Option<String>,
+
+    #[serde(default = "default_job_interval_seconds")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub interval_seconds: u64,
+
+    #[serde(default = "default_job_timeout_seconds")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub timeout_seconds: u64,
+
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_retries: u32,
+
+    #[serde(default = "default_job_retry_backoff_ms")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub retry_backoff_ms: u64,
+
+    #[serde(default = "default_job_max_concurrent")]
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub max_concurrent: usize,
+
+    #[serde(default)]
+    pub paused: bool,
+}
+
+impl Default for JobScheduleConfig {
+    fn default() -> Self {
+        Self {
+            cron: None,
+            interval_seconds: default_job_interval_seconds(),
+            timeout_seconds: default_job_timeout_seconds(),
+            max_retries: 0,
+            retry_backoff_ms: default_job_retry_backoff_ms(),
+            max_concurrent: default_job_max_concurrent(),
+            paused: false,
+        }
+    }
+}
+
+impl JobScheduleConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_seconds)
+    }
+
+    pub fn retry_backoff(&self) -> Duration {
+        Duration::from_millis(self.retry_backoff_ms)
+    }
+}
+
+fn default_job_interval_seconds() -> u64 {
+    60
+}
+
+fn default_job_timeout_seconds() -> u64 {
+    300
+}
+
+fn default_job_retry_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_job_max_concurrent() -> usize {
+    1
+}
+
+fn default_principal_precedence() -> String {
+    "api_key,user,tenant".to_string()
+}
+
+fn default_catalogue_rate_limit() -> RateLimitPolicy {
+    RateLimitPolicy {
+        requests_per_second: 20.0,
+        burst: 40,
+    }
+}
+
+fn default_reservations_rate_limit() -> RateLimitPolicy {
+    RateLimitPolicy {
+        requests_per_second: 10.0,
+        burst: 20,
+    }
+}
+
+fn default_analytics_rate_limit() -> RateLimitPolicy {
+    RateLimitPolicy {
+        requests_per_second: 2.0,
+        burst: 5,
+    }
+}
+
+fn default_catalogue_bulkhead() -> BulkheadConfig {
+    BulkheadConfig {
+        max_concurrent: 64,
+        max_wait_ms: 250,
+    }
+}
+
+fn default_reservations_bulkhead() -> BulkheadConfig {
+    BulkheadConfig {
+        max_concurrent: 32,
+        max_wait_ms: 500,
+    }
+}
+
+fn default_analytics_bulkhead() -> BulkheadConfig {
+    BulkheadConfig {
+        max_concurrent: 8,
+        max_wait_ms: 100,
+    }
+}
+
+fn default_shed_pool_wait_threshold_ms() -> u64 {
+    500
+}
+
+fn default_shed_retry_after_secs() -> u64 {
+    5
+}
+
+fn default_cache_key_prefix() -> String {
+    "automobile-inventory".to_string()
+}
+
+fn default_cache_connect_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_cache_response_timeout_ms() -> u64 {
+    250
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum EnvironmentType {
+    #[serde(rename = "development")]
+    Development,
+    #[serde(rename = "staging")]
+    Staging,
+    #[serde(rename = "production")]
+    Production,
+    #[serde(rename = "test")]
+    Test,
+}
+
+impl EnvironmentType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Development => "development",
+            Self::Staging => "staging",
+            Self::Production => "production",
+            Self::Test => "test",
+        }
+    }
+
+    pub fn is_production(&self) -> bool {
+        matches!(self, Self::Production)
+    }
+}
+
+fn default_environment() -> EnvironmentType {
+    EnvironmentType::Development
+}
+
+fn deserialize_secret_string<'de, D>(deserializer: D) -> Result<SecretString, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if s.trim().is_empty() {
+        return Err(serde::de::Error::custom("DATABASE_URL cannot be empty"));
+    }
+    Ok(SecretString::from(s))
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_max_connections() -> u32 {
+    10
+}
+
+fn default_min_connections() -> u32 {
+    2
+}
+
+fn default_max_lifetime() -> u64 {
+    1800
+}
+
+fn default_acquire_timeout_seconds() -> u64 {
+    5
+}
+
+fn default_idle_timeout() -> u64 {
+    600
+}
+
+fn default_readiness_drain_delay_seconds() -> u64 {
+    5
+}
+
+fn default_max_age() -> u64 {
+    3600
+}
+
+fn default_health_check_timeout_seconds() -> u64 {
+    3
+}
+
+fn default_health_check_acquire_timeout_ms() -> u64 {
+    500
+}
+
+fn default_cors_methods() -> Vec<String> {
+    vec![
+        "GET".to_string(),
+        "POST".to_string(),
+        "PUT".to_string(),
+        "DELETE".to_string(),
+    ]
+}
+
+fn default_max_replica_lag_ms() -> u64 {
+    5000
+}
+
+fn default_target_utilization() -> f64 {
+    0.7
+}
+
+fn default_scale_up_threshold() -> f64 {
+    0.8
+}
+
+fn default_scale_down_threshold() -> f64 {
+    0.3
+}
+
+fn default_scaling_check_interval_secs() -> u64 {
+    30
+}
+
+fn default_scaling_health_check_interval_secs() -> u64 {
+    10
+}
+
+fn default_slow_acquire_threshold_ms() -> u64 {
+    100
+}
+
+fn default_scaling_cooldown_secs() -> u64 {
+    60
+}
+
+fn default_otel_endpoint() -> String {
+    "http://localhost:4317".to_string()
+}
+
+pub fn load_config() -> Result<AppConfig, AppError> {
+    let environment = std::env::var("APP_ENVIRONMENT")
+        .unwrap_or_else(|_| "development".into())
+        .to_lowercase();
+
+    let env_type = match environment.as_str() {
+        "production" | "prod" => EnvironmentType::Production,
+        "staging" | "stg" => EnvironmentType::Staging,
+        "test" | "testing" => EnvironmentType::Test,
+        _ => EnvironmentType::Development,
+    };
+
+    tracing::info!(
+        "Loading configuration for environment: {}",
+        env_type.as_str()
+    );
+
+    let mut builder = Config::builder()
+        .set_default("default", true)
+        .map_err(|e| AppError::ConfigError(e.to_string()))?
+        .add_source(File::with_name("config/default").required(false))
+        .add_source(File::with_name(&format!("config/{}", env_type.as_str())).required(false))
+        .add_source(File::with_name("config/local").required(false))
+        .add_source(
+            Environment::with_prefix("APP")
+                .prefix_separator("__")
+                .separator("__"),
+        );
+
+    if let Ok(db_url) = std::env::var("DATABASE_URL") {
+        builder = builder
+            .set_override("database.url", db_url)
+            .map_err(|e| AppError::ConfigError(e.to_string()))?;
+    }
+
+    let config = builder
+        .build()
+        .map_err(|e| AppError::ConfigError(format!("Failed to build config: {}", e)))?;
+
+    let mut app_config: AppConfig = config
+        .try_deserialize()
+        .map_err(|e| AppError::ConfigError(format!("Failed to deserialize config: {}", e)))?;
+
+    app_config.environment = env_type;
+
+    app_config
+        .validate()
+        .map_err(|e| AppError::ConfigError(format!("Configuration validation failed: {}", e)))?;
+
+    validate_business_rules(&app_config)?;
+
+    log_config_loaded(&app_config);
 
     Ok(app_config)
 }
@@ -544,6 +1589,191 @@ fn validate_business_rules(config: &AppConfig) -> Result<(), AppError> {
         );
     }
 
+    if config.cache.backend == CacheBackendKind::Redis
+        && config
+            .cache
+            .redis_url
+            .as_ref()
+            .is_none_or(|url| url.expose_secret().trim().is_empty())
+    {
+        return Err(AppError::ConfigError(
+            "cache.redis_url is required when cache.backend is 'redis'".into(),
+        ));
+    }
+
+    let scaling = &config.database.pool_scaling;
+    if !(0.0 < scaling.scale_down_threshold
+        && scaling.scale_down_threshold < scaling.target_utilization
+        && scaling.target_utilization < scaling.scale_up_threshold
+        && scaling.scale_up_threshold <= 1.0)
+    {
+        return Err(AppError::ConfigError(
+            "database.pool_scaling thresholds must satisfy 0 < scale_down_threshold < target_utilization < scale_up_threshold <= 1".into(),
+        ));
+    }
+
+    let scaling_min = scaling
+        .min_connections
+        .unwrap_or(config.database.min_connections);
+    let scaling_max = scaling
+        .max_connections
+        .unwrap_or(config.database.max_connections);
+    if scaling_min == 0 || scaling_min > scaling_max || scaling_max > 100 {
+        return Err(AppError::ConfigError(
+            "database.pool_scaling connection bounds must satisfy 1 <= min_connections <= max_connections <= 100".into(),
+        ));
+    }
+
+    let bulkheads = &config.resilience.bulkheads;
+    if [
+        &bulkheads.catalogue,
+        &bulkheads.reservations,
+        &bulkheads.analytics,
+    ]
+    .iter()
+    .any(|bulkhead| bulkhead.max_concurrent == 0)
+    {
+        return Err(AppError::ConfigError(
+            "resilience.bulkheads max_concurrent must be at least 1 for every route group".into(),
+        ));
+    }
+
+    let rate_limiting = &config.rate_limiting;
+    rate_limiting.principal_precedence()?;
+    rate_limiting.trusted_proxies()?;
+    if [
+        &rate_limiting.catalogue,
+        &rate_limiting.reservations,
+        &rate_limiting.analytics,
+    ]
+    .iter()
+    .any(|policy| policy.requests_per_second <= 0.0 || policy.burst == 0)
+    {
+        return Err(AppError::ConfigError(
+            "rate_limiting policies need requests_per_second > 0 and burst >= 1".into(),
+        ));
+    }
+
+    if config.resilience.load_shedding.enabled
+        && config.resilience.load_shedding.pool_wait_threshold_ms == 0
+    {
+        return Err(AppError::ConfigError(
+            "resilience.load_shedding.pool_wait_threshold_ms must be greater than 0".into(),
+        ));
+    }
+
+    for (name, job) in &config.scheduler.jobs {
+        JobSchedule::try_from(job)
+            .map_err(|e| AppError::ConfigError(format!("scheduler.jobs.{}: {}", name, e)))?;
+
+        if job.timeout_seconds == 0 || job.max_concurrent == 0 {
+            return Err(AppError::ConfigError(format!(
+                "scheduler.jobs.{} needs timeout_seconds > 0 and max_concurrent >= 1",
+                name
+            )));
+        }
+    }
+
+    let planning = &config.reorder_planning;
+    if planning.service_level_z <= 0.0
+        || planning.default_lead_time_days == 0
+        || planning.ordering_cost <= 0.0
+        || planning.holding_cost_rate <= 0.0
+        || planning.holding_cost_rate > 1.0
+        || !(14..=730).contains(&planning.history_days)
+    {
+        return Err(AppError::ConfigError(
+            "reorder_planning needs positive service_level_z, default_lead_time_days and ordering_cost, \
+             holding_cost_rate in (0, 1] and history_days between 14 and 730"
+                .into(),
+        ));
+    }
+
+    let rebalancing = &config.rebalancing;
+    if !(1..=365).contains(&rebalancing.demand_days)
+        || !(1..=180).contains(&rebalancing.target_cover_days)
+        || rebalancing.min_transfer_quantity < 1
+    {
+        return Err(AppError::ConfigError(
+            "rebalancing needs demand_days between 1 and 365, target_cover_days between 1 and 180 \
+             and min_transfer_quantity >= 1"
+                .into(),
+        ));
+    }
+
+    let classification = &config.classification;
+    if !(4..=104).contains(&classification.history_weeks)
+        || classification.a_revenue_share <= 0.0
+        || classification.b_revenue_share <= classification.a_revenue_share
+        || classification.b_revenue_share > 1.0
+        || classification.x_max_cv <= 0.0
+        || classification.y_max_cv <= classification.x_max_cv
+    {
+        return Err(AppError::ConfigError(
+            "classification needs history_weeks between 4 and 104, \
+             0 < a_revenue_share < b_revenue_share <= 1 and 0 < x_max_cv < y_max_cv"
+                .into(),
+        ));
+    }
+
+    let depreciation = &config.depreciation;
+    if let Some((engine, _)) = depreciation
+        .curves
+        .all()
+        .into_iter()
+        .find(|(_, curve)| !curve.is_valid())
+    {
+        return Err(AppError::ConfigError(format!(
+            "depreciation.curves.{} is invalid: straight_line needs useful_life_years > 0, \
+             declining_balance an annual_rate in (0, 1), both a salvage_ratio in [0, 1); \
+             custom needs non-increasing retained_by_year ratios in [0, 1]",
+            engine
+        )));
+    }
+    if depreciation.expected_km_per_year == 0
+        || !(0.0..=1.0).contains(&depreciation.mileage_adjustment_per_1000_km)
+        || !(0.0..1.0).contains(&depreciation.max_mileage_adjustment)
+    {
+        return Err(AppError::ConfigError(
+            "depreciation needs expected_km_per_year > 0, mileage_adjustment_per_1000_km in [0, 1] \
+             and max_mileage_adjustment in [0, 1)"
+                .into(),
+        ));
+    }
+
+    let aging = &config.aging;
+    if !(1..=365).contains(&aging.sales_days)
+        || aging.slow_mover_age_days == 0
+        || aging.slow_mover_supply_days == 0
+    {
+        return Err(AppError::ConfigError(
+            "aging needs sales_days between 1 and 365 and positive slow_mover_age_days \
+             and slow_mover_supply_days"
+                .into(),
+        ));
+    }
+
+    if !(1..=365).contains(&config.price_history.elasticity_window_days) {
+        return Err(AppError::ConfigError(
+            "price_history.elasticity_window_days must be between 1 and 365".into(),
+        ));
+    }
+
+    if !(config.pricing.min_price_ratio > 0.0 && config.pricing.min_price_ratio <= 1.0) {
+        return Err(AppError::ConfigError(
+            "pricing.min_price_ratio must be in (0, 1]".into(),
+        ));
+    }
+
+    let leader_election = &config.scheduler.leader_election;
+    if leader_election.enabled
+        && (leader_election.retry_interval_ms == 0 || leader_election.lock_namespace < 0)
+    {
+        return Err(AppError::ConfigError(
+            "scheduler.leader_election needs retry_interval_ms > 0 and a non-negative lock_namespace".into(),
+        ));
+    }
+
     if config.database.max_connections <= config.database.min_connections {
         return Err(AppError::ConfigError(
             "database.max_connections must be greater than database.min_connections".into(),
@@ -568,6 +1798,8 @@ fn log_config_loaded(config: &AppConfig) {
         log_level = %config.observability.log_level,
         metrics_enabled = %config.observability.enable_metrics,
         tracing_enabled = %config.observability.enable_tracing,
+        otel_protocol = ?config.observability.otel_protocol,
+        cache_backend = ?config.cache.backend,
         "Configuration loaded successfully"
     );
 }
@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive, Zero};
+use chrono::{Datelike, NaiveDate};
+
+use crate::config::{DepreciationConfig, DepreciationCurve};
+use crate::models::{
+    CarDepreciation, DepreciationInput, ValuationTotals, WarehouseStockRow, WarehouseValuation,
+};
+
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Share of the list price a car retains after `age_years`.
+pub fn retained_ratio(curve: &DepreciationCurve, age_years: f64) -> f64 {
+    let age = age_years.max(0.0);
+    match curve {
+        DepreciationCurve::StraightLine {
+            useful_life_years,
+            salvage_ratio,
+        } => (1.0 - (1.0 - salvage_ratio) * age / useful_life_years).max(*salvage_ratio),
+        DepreciationCurve::DecliningBalance {
+            annual_rate,
+            salvage_ratio,
+        } => (1.0 - annual_rate).powf(age).max(*salvage_ratio),
+        DepreciationCurve::Custom { retained_by_year } => {
+            let year = age.floor() as usize;
+            match (retained_by_year.get(year), retained_by_year.get(year + 1)) {
+                (Some(from), Some(to)) => from + (to - from) * age.fract(),
+                (Some(last), None) => *last,
+                _ => retained_by_year.last().copied().unwrap_or(1.0),
+            }
+        }
+    }
+}
+
+/// Relative value change for mileage away from `expected_km_per_year` per
+/// year of age: negative above normal, positive below, capped either way.
+pub fn mileage_adjustment(config: &DepreciationConfig, mileage_km: i32, age_years: f64) -> f64 {
+    let expected_km = config.expected_km_per_year as f64 * age_years.max(0.0);
+    let excess_thousands = (mileage_km as f64 - expected_km) / 1000.0;
+
+    (-excess_thousands * config.mileage_adjustment_per_1000_km).clamp(
+        -config.max_mileage_adjustment,
+        config.max_mileage_adjustment,
+    )
+}
+
+/// Current estimated value of one car from its engine type's curve and mileage.
+pub fn appraise(
+    input: DepreciationInput,
+    config: &DepreciationConfig,
+    today: NaiveDate,
+) -> CarDepreciation {
+    let age_years = NaiveDate::from_ymd_opt(input.year, 1, 1)
+        .map(|model_year| (today - model_year).num_days() as f64 / DAYS_PER_YEAR)
+        .unwrap_or(0.0)
+        .max(0.0);
+
+    let curve = config.curves.for_engine(&input.engine_type);
+    let retained = retained_ratio(curve, age_years);
+    let adjustment = input
+        .mileage_km
+        .map(|km| mileage_adjustment(config, km, age_years))
+        .unwrap_or(0.0);
+
+    let factor =
+        BigDecimal::from_f64(retained * (1.0 + adjustment)).unwrap_or_else(BigDecimal::zero);
+    let estimated_value = (&input.price * factor).round(2);
+    let book_value = &estimated_value * BigDecimal::from(input.quantity_in_stock.max(0));
+
+    CarDepreciation {
+        car_id: input.car_id,
+        brand: input.brand,
+        model: input.model,
+        year: input.year,
+        engine_type: input.engine_type,
+        mileage_km: input.mileage_km,
+        age_years,
+        method: curve.method().to_string(),
+        retained_ratio: retained,
+        mileage_adjustment: adjustment,
+        list_price: input.price,
+        estimated_value,
+        units_in_stock: input.quantity_in_stock,
+        book_value,
+    }
+}
+
+/// Today's date in UTC, the reference for car ages.
+pub fn today() -> NaiveDate {
+    let now = chrono::Utc::now();
+    NaiveDate::from_ymd_opt(now.year(), now.month(), now.day()).unwrap_or_default()
+}
+
+fn add_units(totals: &mut ValuationTotals, car: &CarDepreciation, units: i64) {
+    let units_dec = BigDecimal::from(units);
+    totals.units += units;
+    totals.list_value += &car.list_price * &units_dec;
+    totals.book_value += &car.estimated_value * &units_dec;
+}
+
+fn finish(mut totals: ValuationTotals) -> ValuationTotals {
+    totals.depreciation = &totals.list_value - &totals.book_value;
+    totals.depreciation_pct = match totals.list_value.to_f64() {
+        Some(list) if list > 0.0 => totals.depreciation.to_f64().unwrap_or(0.0) / list * 100.0,
+        _ => 0.0,
+    };
+    totals
+}
+
+/// Book value against list value for the whole inventory and per warehouse.
+pub fn valuation(
+    cars: &[CarDepreciation],
+    stock: Vec<WarehouseStockRow>,
+) -> (ValuationTotals, Vec<WarehouseValuation>) {
+    let mut total = ValuationTotals::default();
+    for car in cars {
+        add_units(&mut total, car, car.units_in_stock.max(0) as i64);
+    }
+
+    let by_car: HashMap<&str, &CarDepreciation> =
+        cars.iter().map(|car| (car.car_id.as_str(), car)).collect();
+    let mut warehouses: Vec<WarehouseValuation> = Vec::new();
+    for row in stock {
+        let Some(car) = by_car.get(row.car_id.as_str()) else {
+            continue;
+        };
+        if warehouses
+            .last()
+            .is_none_or(|w| w.warehouse_id != row.warehouse_id)
+        {
+            warehouses.push(WarehouseValuation {
+                warehouse_id: row.warehouse_id.clone(),
+                name: row.name.clone(),
+                totals: ValuationTotals::default(),
+            });
+        }
+        if let Some(warehouse) = warehouses.last_mut() {
+            add_units(&mut warehouse.totals, car, row.units);
+        }
+    }
+
+    for warehouse in &mut warehouses {
+        warehouse.totals = finish(std::mem::take(&mut warehouse.totals));
+    }
+
+    (finish(total), warehouses)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::EngineDepreciationCurves;
+    use crate::models::{CarId, EngineType};
+
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    fn mileage_config() -> DepreciationConfig {
+        DepreciationConfig {
+            expected_km_per_year: 15_000,
+            mileage_adjustment_per_1000_km: 0.005,
+            max_mileage_adjustment: 0.1,
+            ..DepreciationConfig::default()
+        }
+    }
+
+    #[test]
+    fn straight_line_declines_evenly_down_to_salvage() {
+        let curve = DepreciationCurve::StraightLine {
+            useful_life_years: 10.0,
+            salvage_ratio: 0.2,
+        };
+
+        assert_close(retained_ratio(&curve, 0.0), 1.0);
+        assert_close(retained_ratio(&curve, 5.0), 0.6);
+        assert_close(retained_ratio(&curve, 10.0), 0.2);
+        assert_close(retained_ratio(&curve, 15.0), 0.2);
+        assert_close(retained_ratio(&curve, -1.0), 1.0);
+    }
+
+    #[test]
+    fn declining_balance_compounds_annual_rate_down_to_salvage() {
+        let curve = DepreciationCurve::DecliningBalance {
+            annual_rate: 0.15,
+            salvage_ratio: 0.1,
+        };
+
+        assert_close(retained_ratio(&curve, 1.0), 0.85);
+        assert_close(retained_ratio(&curve, 2.0), 0.85 * 0.85);
+        assert_close(retained_ratio(&curve, 2.5), 0.85_f64.powf(2.5));
+        assert_close(retained_ratio(&curve, 30.0), 0.1);
+    }
+
+    #[test]
+    fn custom_table_interpolates_and_holds_last_entry() {
+        let curve = DepreciationCurve::Custom {
+            retained_by_year: vec![1.0, 0.8, 0.7, 0.65],
+        };
+
+        assert_close(retained_ratio(&curve, 1.0), 0.8);
+        assert_close(retained_ratio(&curve, 1.5), 0.75);
+        assert_close(retained_ratio(&curve, 3.0), 0.65);
+        assert_close(retained_ratio(&curve, 3.5), 0.65);
+        assert_close(retained_ratio(&curve, 12.0), 0.65);
+    }
+
+    #[test]
+    fn mileage_adjustment_follows_distance_from_expected() {
+        let config = mileage_config();
+
+        assert_close(mileage_adjustment(&config, 30_000, 2.0), 0.0);
+        assert_close(mileage_adjustment(&config, 40_000, 2.0), -0.05);
+        assert_close(mileage_adjustment(&config, 20_000, 2.0), 0.05);
+    }
+
+    #[test]
+    fn mileage_adjustment_is_clamped_both_ways() {
+        let config = mileage_config();
+
+        assert_close(mileage_adjustment(&config, 200_000, 2.0), -0.1);
+        assert_close(mileage_adjustment(&config, 0, 10.0), 0.1);
+    }
+
+    #[test]
+    fn appraise_applies_curve_and_mileage_to_list_price() {
+        let flat = DepreciationCurve::Custom {
+            retained_by_year: vec![0.5],
+        };
+        let config = DepreciationConfig {
+            curves: EngineDepreciationCurves {
+                electric: flat.clone(),
+                hybrid: flat.clone(),
+                gasoline: flat.clone(),
+                diesel: flat.clone(),
+                petrol: flat,
+            },
+            ..mileage_config()
+        };
+        let input = |mileage_km| DepreciationInput {
+            car_id: CarId::new("C0001".to_string()).unwrap(),
+            brand: "Toyota".into(),
+            model: "Camry".into(),
+            year: 2022,
+            engine_type: EngineType::Petrol,
+            mileage_km,
+            price: BigDecimal::from(30_000),
+            quantity_in_stock: 3,
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 7, 1).unwrap();
+
+        let car = appraise(input(None), &config, today);
+        assert_eq!(car.method, "custom");
+        assert_eq!(car.estimated_value, BigDecimal::from(15_000));
+        assert_eq!(car.book_value, BigDecimal::from(45_000));
+
+        let worn = appraise(input(Some(500_000)), &config, today);
+        assert_close(worn.mileage_adjustment, -0.1);
+        assert_eq!(worn.estimated_value, BigDecimal::from(13_500));
+    }
+}
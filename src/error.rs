@@ -109,6 +109,51 @@ pub enum TransferError {
     Database(#[from] sqlx::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum PurchaseOrderError {
+    #[error("Supplier not found: {0}")]
+    SupplierNotFound(String),
+
+    #[error("Receiving warehouse not found: {0}")]
+    WarehouseNotFound(String),
+
+    #[error("Car not found: {0}")]
+    CarNotFound(String),
+
+    #[error("Purchase order not found: {0}")]
+    PurchaseOrderNotFound(Uuid),
+
+    #[error("Supplier {supplier_id} has no price for car {car_id}")]
+    NoSupplierPrice { supplier_id: String, car_id: String },
+
+    #[error(
+        "Order quantity for car {car_id} below supplier minimum: minimum {minimum}, requested {requested}"
+    )]
+    BelowMinimumOrder {
+        car_id: String,
+        minimum: i32,
+        requested: i32,
+    },
+
+    #[error("Car {0} is not on this purchase order")]
+    LineNotFound(String),
+
+    #[error(
+        "Receipt for car {car_id} exceeds outstanding quantity: outstanding {outstanding}, received {received}"
+    )]
+    OverReceipt {
+        car_id: String,
+        outstanding: i32,
+        received: i32,
+    },
+
+    #[error("Invalid purchase order state: expected {expected}, found {found}")]
+    InvalidState { expected: String, found: String },
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
 #[derive(Debug, Error)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -142,7 +187,7 @@ pub enum AppError {
     RateLimited { retry_after: u64 },
 
     #[error("Service temporarily unavailable")]
-    ServiceUnavailable,
+    ServiceUnavailable { retry_after: Option<u64> },
 
     #[error("Feature not yet implemented")]
     NotImplemented,
@@ -174,9 +219,15 @@ pub enum AppError {
     #[error("Background job failed: {0}")]
     BackgroundJobError(String),
 
+    #[error("Job already running: {0}")]
+    JobAlreadyRunning(String),
+
     #[error("Transfer error: {0}")]
     TransferError(#[from] TransferError),
 
+    #[error("Purchase order error: {0}")]
+    PurchaseOrderError(#[from] PurchaseOrderError),
+
     #[error("Context: {context}")]
     WithContext {
         #[source]
@@ -211,7 +262,10 @@ impl AppError {
             Self::Unauthorized => "UNAUTHORIZED".to_string(),
             Self::Forbidden => "FORBIDDEN".to_string(),
             Self::RateLimited { .. } => "RATE_LIMITED".to_string(),
-            Self::ServiceUnavailable => "SERVICE_UNAVAILABLE".to_string(),
+            Self::ServiceUnavailable { .. }
+            | Self::DatabaseError(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                "SERVICE_UNAVAILABLE".to_string()
+            }
             Self::NotImplemented => "RESOURCE_NOT_IMPLEMENTED".to_string(),
             Self::DatabaseError(_) => "DATABASE_ERROR".to_string(),
             Self::MigrationError(_) => "MIGRATION_ERROR".to_string(),
@@ -225,6 +279,7 @@ impl AppError {
             Self::TransferNotFound(_) => "TRANSFER_NOT_FOUND".to_string(),
             Self::BusinessRuleViolation(_) => "BUSINESS_RULE_VIOLATION".to_string(),
             Self::BackgroundJobError(_) => "BACKGROUND_JOB_ERROR".to_string(),
+            Self::JobAlreadyRunning(_) => "JOB_ALREADY_RUNNING".to_string(),
             Self::TransferError(e) => match e {
                 TransferError::InsufficientStock { .. } => "INSUFFICIENT_STOCK".to_string(),
                 TransferError::SourceWarehouseNotFound(_) => {
@@ -235,8 +290,32 @@ impl AppError {
                 }
                 TransferError::TransferNotFound(_) => "TRANSFER_NOT_FOUND".to_string(),
                 TransferError::InvalidState { .. } => "INVALID_TRANSFER_STATE".to_string(),
+                TransferError::Database(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                    "SERVICE_UNAVAILABLE".to_string()
+                }
                 TransferError::Database(_) => "DATABASE_ERROR".to_string(),
             },
+            Self::PurchaseOrderError(e) => match e {
+                PurchaseOrderError::SupplierNotFound(_) => "SUPPLIER_NOT_FOUND".to_string(),
+                PurchaseOrderError::WarehouseNotFound(_) => "WAREHOUSE_NOT_FOUND".to_string(),
+                PurchaseOrderError::CarNotFound(_) => "CAR_NOT_FOUND".to_string(),
+                PurchaseOrderError::PurchaseOrderNotFound(_) => {
+                    "PURCHASE_ORDER_NOT_FOUND".to_string()
+                }
+                PurchaseOrderError::NoSupplierPrice { .. } => "NO_SUPPLIER_PRICE".to_string(),
+                PurchaseOrderError::BelowMinimumOrder { .. } => {
+                    "BELOW_MINIMUM_ORDER_QUANTITY".to_string()
+                }
+                PurchaseOrderError::LineNotFound(_) => "PURCHASE_ORDER_LINE_NOT_FOUND".to_string(),
+                PurchaseOrderError::OverReceipt { .. } => "OVER_RECEIPT".to_string(),
+                PurchaseOrderError::InvalidState { .. } => {
+                    "INVALID_PURCHASE_ORDER_STATE".to_string()
+                }
+                PurchaseOrderError::Database(
+                    sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed,
+                ) => "SERVICE_UNAVAILABLE".to_string(),
+                PurchaseOrderError::Database(_) => "DATABASE_ERROR".to_string(),
+            },
             Self::WithContext { source, .. } => source.error_code(),
         }
     }
@@ -254,7 +333,7 @@ impl AppError {
                 retry_after
             )
             .into(),
-            Self::ServiceUnavailable => {
+            Self::ServiceUnavailable { .. } => {
                 "Service temporarily unavailable. Please try again later".into()
             }
             Self::NotImplemented => "This feature is not yet implemented".into(),
@@ -271,6 +350,10 @@ impl AppError {
             Self::TransferNotFound(_) => "Transfer order not found".into(),
             Self::BusinessRuleViolation(_) => "This operation violates business rules".into(),
             Self::BackgroundJobError(_) => "Background processing error occurred".into(),
+            Self::JobAlreadyRunning(_) => "The job is already running".into(),
+            Self::DatabaseError(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                "Database is temporarily unavailable. Please retry shortly".into()
+            }
             Self::DatabaseError(_) | Self::MigrationError(_) => {
                 "An internal error occurred. Please contact support if the problem persists".into()
             }
@@ -287,8 +370,34 @@ impl AppError {
                 TransferError::InvalidState { .. } => {
                     "Transfer is in an invalid state for this operation".into()
                 }
+                TransferError::Database(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                    "Database is temporarily unavailable. Please retry shortly".into()
+                }
                 TransferError::Database(_) => "An internal error occurred".into(),
             },
+            Self::PurchaseOrderError(e) => match e {
+                PurchaseOrderError::SupplierNotFound(_) => "Supplier not found".into(),
+                PurchaseOrderError::WarehouseNotFound(_) => "Receiving warehouse not found".into(),
+                PurchaseOrderError::CarNotFound(_) => "Car not found".into(),
+                PurchaseOrderError::PurchaseOrderNotFound(_) => "Purchase order not found".into(),
+                PurchaseOrderError::NoSupplierPrice { .. } => {
+                    "The supplier has no price for this car".into()
+                }
+                PurchaseOrderError::BelowMinimumOrder { .. } => {
+                    "Order quantity is below the supplier minimum".into()
+                }
+                PurchaseOrderError::LineNotFound(_) => "Car is not on this purchase order".into(),
+                PurchaseOrderError::OverReceipt { .. } => {
+                    "Received quantity exceeds the outstanding order quantity".into()
+                }
+                PurchaseOrderError::InvalidState { .. } => {
+                    "Purchase order is in an invalid state for this operation".into()
+                }
+                PurchaseOrderError::Database(
+                    sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed,
+                ) => "Database is temporarily unavailable. Please retry shortly".into(),
+                PurchaseOrderError::Database(_) => "An internal error occurred".into(),
+            },
             Self::WithContext { source, .. } => source.safe_message(),
         }
     }
@@ -314,7 +423,10 @@ impl AppError {
             Self::Unauthorized => StatusCode::UNAUTHORIZED,
             Self::Forbidden => StatusCode::FORBIDDEN,
             Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
-            Self::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::ServiceUnavailable { .. }
+            | Self::DatabaseError(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
             Self::InsufficientStock { .. } => StatusCode::CONFLICT,
             Self::ReservationNotFound => StatusCode::NOT_FOUND,
             Self::ReservationExpired => StatusCode::GONE,
@@ -324,14 +436,33 @@ impl AppError {
             Self::TransferNotFound(_) => StatusCode::NOT_FOUND,
             Self::BusinessRuleViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
             Self::BackgroundJobError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::JobAlreadyRunning(_) => StatusCode::CONFLICT,
             Self::TransferError(e) => match e {
                 TransferError::InsufficientStock { .. } => StatusCode::CONFLICT,
                 TransferError::SourceWarehouseNotFound(_) => StatusCode::NOT_FOUND,
                 TransferError::DestinationWarehouseNotFound(_) => StatusCode::NOT_FOUND,
                 TransferError::TransferNotFound(_) => StatusCode::NOT_FOUND,
                 TransferError::InvalidState { .. } => StatusCode::CONFLICT,
+                TransferError::Database(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed) => {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
                 TransferError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
             },
+            Self::PurchaseOrderError(e) => match e {
+                PurchaseOrderError::SupplierNotFound(_) => StatusCode::NOT_FOUND,
+                PurchaseOrderError::WarehouseNotFound(_) => StatusCode::NOT_FOUND,
+                PurchaseOrderError::CarNotFound(_) => StatusCode::NOT_FOUND,
+                PurchaseOrderError::PurchaseOrderNotFound(_) => StatusCode::NOT_FOUND,
+                PurchaseOrderError::NoSupplierPrice { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                PurchaseOrderError::BelowMinimumOrder { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+                PurchaseOrderError::LineNotFound(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                PurchaseOrderError::OverReceipt { .. } => StatusCode::CONFLICT,
+                PurchaseOrderError::InvalidState { .. } => StatusCode::CONFLICT,
+                PurchaseOrderError::Database(
+                    sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed,
+                ) => StatusCode::SERVICE_UNAVAILABLE,
+                PurchaseOrderError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            },
             Self::WithContext { source, .. } => source.status_code(),
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -503,9 +634,15 @@ impl IntoResponse for AppError {
         });
         response.headers_mut().insert("X-Error-Id", error_id_header);
 
-        if let AppError::RateLimited { retry_after } = &self {
-            let retry_value = HeaderValue::from(*retry_after);
-            response.headers_mut().insert("Retry-After", retry_value);
+        match &self {
+            AppError::RateLimited { retry_after }
+            | AppError::ServiceUnavailable {
+                retry_after: Some(retry_after),
+            } => {
+                let retry_value = HeaderValue::from(*retry_after);
+                response.headers_mut().insert("Retry-After", retry_value);
+            }
+            _ => {}
         }
 
         response
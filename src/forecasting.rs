@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use crate::config::ReorderPlanningConfig;
+use crate::models::{DailySalesRow, ForecastModel};
+
+/// Weekly seasonality for daily demand.
+const SEASON_LENGTH: usize = 7;
+const MOVING_AVERAGE_WINDOW: usize = 7;
+const MAX_BACKTEST_DAYS: usize = 14;
+const MIN_BACKTEST_DAYS: usize = 3;
+const HOLT_WINTERS_DAMPING: f64 = 0.9;
+const SMOOTHING_GRID: [f64; 4] = [0.05, 0.1, 0.3, 0.5];
+const CROSTON_ALPHA: f64 = 0.1;
+/// z-score of the two-sided 95% prediction interval.
+pub const INTERVAL_Z: f64 = 1.96;
+pub const INTERVAL_LEVEL: f64 = 0.95;
+
+const CANDIDATES: [ForecastModel; 3] = [
+    ForecastModel::MovingAverage,
+    ForecastModel::HoltWinters,
+    ForecastModel::Croston,
+];
+
+pub struct Forecast {
+    pub model: ForecastModel,
+    pub points: Vec<f64>,
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+    /// Mean absolute error of each candidate on the held-out tail.
+    pub scores: Vec<(ForecastModel, f64)>,
+}
+
+/// Picks the candidate with the lowest backtest MAE on the last days of
+/// `history`, refits it on the whole series and forecasts `horizon` days.
+pub fn forecast(history: &[f64], horizon: usize) -> Forecast {
+    let holdout = (history.len() / 4).min(MAX_BACKTEST_DAYS);
+
+    let mut scores = Vec::new();
+    let mut sigma = residual_sigma(history);
+    if holdout >= MIN_BACKTEST_DAYS {
+        let (train, test) = history.split_at(history.len() - holdout);
+        for model in CANDIDATES {
+            if let Some(predicted) = predict(model, train, holdout) {
+                scores.push((
+                    model,
+                    mean_absolute_error(&predicted, test),
+                    rmse(&predicted, test),
+                ));
+            }
+        }
+    }
+
+    let best = scores.iter().min_by(|a, b| a.1.total_cmp(&b.1)).copied();
+    let model = match best {
+        Some((model, _, backtest_rmse)) => {
+            sigma = backtest_rmse;
+            model
+        }
+        None => ForecastModel::MovingAverage,
+    };
+
+    let points = predict(model, history, horizon)
+        .or_else(|| predict(ForecastModel::MovingAverage, history, horizon))
+        .unwrap_or_else(|| vec![0.0; horizon]);
+
+    // The backtest RMSE covers errors up to `holdout` days ahead; further out
+    // the interval widens with the square root of the distance.
+    let (lower, upper) = points
+        .iter()
+        .enumerate()
+        .map(|(step, point)| {
+            let growth = ((step + 1) as f64 / holdout.max(1) as f64).sqrt().max(1.0);
+            let half_width = INTERVAL_Z * sigma * growth;
+            ((point - half_width).max(0.0), point + half_width)
+        })
+        .unzip();
+
+    Forecast {
+        model,
+        points,
+        lower,
+        upper,
+        scores: scores.into_iter().map(|(m, mae, _)| (m, mae)).collect(),
+    }
+}
+
+/// Days until cumulative forecast demand exceeds `available`, or `None` if
+/// the stock outlasts the forecast.
+pub fn days_until_stockout(points: &[f64], available: f64) -> Option<i32> {
+    if available <= 0.0 {
+        return Some(0);
+    }
+
+    let mut cumulative = 0.0;
+    points.iter().enumerate().find_map(|(day, demand)| {
+        cumulative += demand;
+        (cumulative > available).then_some(day as i32)
+    })
+}
+
+/// Groups zero-filled daily sales rows into one demand series per car.
+pub fn demand_by_car(rows: Vec<DailySalesRow>) -> HashMap<String, Vec<f64>> {
+    let mut demand: HashMap<String, Vec<f64>> = HashMap::new();
+    for row in rows {
+        demand
+            .entry(row.car_id.as_str().to_string())
+            .or_default()
+            .push(row.units as f64);
+    }
+    demand
+}
+
+pub struct ReorderPlan {
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub safety_stock: i32,
+    pub avg_daily_demand: f64,
+    pub demand_std_dev: f64,
+}
+
+/// Safety stock covers demand variability over the lead time at the
+/// configured service level; the reorder point adds the forecast demand over
+/// the lead time; EOQ balances ordering against holding cost.
+pub fn plan_reorder(
+    history: &[f64],
+    lead_time_days: u32,
+    unit_price: f64,
+    config: &ReorderPlanningConfig,
+) -> ReorderPlan {
+    let avg_daily_demand = if history.is_empty() {
+        0.0
+    } else {
+        mean(history)
+    };
+    let demand_std_dev = residual_sigma(history);
+
+    let lead_time_demand: f64 = forecast(history, lead_time_days as usize)
+        .points
+        .iter()
+        .sum();
+    let safety_stock = config.service_level_z * demand_std_dev * (lead_time_days as f64).sqrt();
+
+    let annual_demand = avg_daily_demand * 365.0;
+    let holding_cost = config.holding_cost_rate * unit_price;
+    let economic_order_qty = if annual_demand > 0.0 && holding_cost > 0.0 {
+        (2.0 * annual_demand * config.ordering_cost / holding_cost).sqrt()
+    } else {
+        1.0
+    };
+
+    ReorderPlan {
+        reorder_point: (lead_time_demand + safety_stock).ceil() as i32,
+        economic_order_qty: (economic_order_qty.ceil() as i32).max(1),
+        safety_stock: safety_stock.ceil() as i32,
+        avg_daily_demand,
+        demand_std_dev,
+    }
+}
+
+fn predict(model: ForecastModel, history: &[f64], horizon: usize) -> Option<Vec<f64>> {
+    match model {
+        ForecastModel::MovingAverage => moving_average(history, horizon),
+        ForecastModel::HoltWinters => holt_winters(history, horizon),
+        ForecastModel::Croston => croston(history, horizon),
+    }
+}
+
+fn moving_average(history: &[f64], horizon: usize) -> Option<Vec<f64>> {
+    if history.is_empty() {
+        return None;
+    }
+
+    let window = &history[history.len().saturating_sub(MOVING_AVERAGE_WINDOW)..];
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    Some(vec![mean; horizon])
+}
+
+/// Additive Holt-Winters with damped trend, smoothing parameters chosen by
+/// one-step-ahead squared error. Needs two full seasons to initialise.
+fn holt_winters(history: &[f64], horizon: usize) -> Option<Vec<f64>> {
+    if history.len() < SEASON_LENGTH * 2 {
+        return None;
+    }
+
+    let mut best: Option<(f64, HoltWintersState)> = None;
+    for alpha in SMOOTHING_GRID {
+        for beta in SMOOTHING_GRID {
+            for gamma in SMOOTHING_GRID {
+                let (sse, state) = HoltWintersState::fit(history, alpha, beta, gamma);
+                if best.as_ref().is_none_or(|(best_sse, _)| sse < *best_sse) {
+                    best = Some((sse, state));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, state)| state.forecast(history.len(), horizon))
+}
+
+struct HoltWintersState {
+    level: f64,
+    trend: f64,
+    seasonal: [f64; SEASON_LENGTH],
+}
+
+impl HoltWintersState {
+    fn fit(history: &[f64], alpha: f64, beta: f64, gamma: f64) -> (f64, Self) {
+        let first = mean(&history[..SEASON_LENGTH]);
+        let second = mean(&history[SEASON_LENGTH..SEASON_LENGTH * 2]);
+
+        let mut state = Self {
+            level: first,
+            trend: (second - first) / SEASON_LENGTH as f64,
+            seasonal: std::array::from_fn(|i| history[i] - first),
+        };
+
+        let mut sse = 0.0;
+        for (t, &y) in history.iter().enumerate() {
+            let season = t % SEASON_LENGTH;
+            let seasonal = state.seasonal[season];
+            let damped_trend = HOLT_WINTERS_DAMPING * state.trend;
+
+            let error = y - (state.level + damped_trend + seasonal);
+            sse += error * error;
+
+            let previous_level = state.level;
+            state.level = alpha * (y - seasonal) + (1.0 - alpha) * (previous_level + damped_trend);
+            state.trend = beta * (state.level - previous_level) + (1.0 - beta) * damped_trend;
+            state.seasonal[season] = gamma * (y - state.level) + (1.0 - gamma) * seasonal;
+        }
+
+        (sse, state)
+    }
+
+    fn forecast(&self, observed: usize, horizon: usize) -> Vec<f64> {
+        let mut damping = 0.0;
+        let mut factor = 1.0;
+        (1..=horizon)
+            .map(|step| {
+                factor *= HOLT_WINTERS_DAMPING;
+                damping += factor;
+                let season = (observed + step - 1) % SEASON_LENGTH;
+                (self.level + damping * self.trend + self.seasonal[season]).max(0.0)
+            })
+            .collect()
+    }
+}
+
+/// Croston's method with the Syntetos-Boylan bias correction, for series
+/// with many zero-demand days.
+fn croston(history: &[f64], horizon: usize) -> Option<Vec<f64>> {
+    let first_demand = history.iter().position(|&y| y > 0.0)?;
+
+    let mut size = history[first_demand];
+    let mut interval = (first_demand + 1) as f64;
+    let mut since_demand = 1.0;
+
+    for &y in &history[first_demand + 1..] {
+        if y > 0.0 {
+            size += CROSTON_ALPHA * (y - size);
+            interval += CROSTON_ALPHA * (since_demand - interval);
+            since_demand = 1.0;
+        } else {
+            since_demand += 1.0;
+        }
+    }
+
+    let rate = (1.0 - CROSTON_ALPHA / 2.0) * size / interval;
+    Some(vec![rate; horizon])
+}
+
+fn residual_sigma(history: &[f64]) -> f64 {
+    if history.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(history);
+    let variance =
+        history.iter().map(|y| (y - mean).powi(2)).sum::<f64>() / (history.len() - 1) as f64;
+    variance.sqrt()
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn mean_absolute_error(predicted: &[f64], actual: &[f64]) -> f64 {
+    mean(
+        &predicted
+            .iter()
+            .zip(actual)
+            .map(|(p, a)| (p - a).abs())
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn rmse(predicted: &[f64], actual: &[f64]) -> f64 {
+    mean(
+        &predicted
+            .iter()
+            .zip(actual)
+            .map(|(p, a)| (p - a).powi(2))
+            .collect::<Vec<_>>(),
+    )
+    .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekly_pattern(weeks: usize) -> Vec<f64> {
+        let week = [2.0, 3.0, 3.0, 4.0, 8.0, 12.0, 1.0];
+        week.iter()
+            .copied()
+            .cycle()
+            .take(weeks * SEASON_LENGTH)
+            .collect()
+    }
+
+    /// One sale of `size` units every `every` days, first on day `every - 1`.
+    fn intermittent(days: usize, every: usize, size: f64) -> Vec<f64> {
+        (0..days)
+            .map(|day| if (day + 1) % every == 0 { size } else { 0.0 })
+            .collect()
+    }
+
+    fn config() -> ReorderPlanningConfig {
+        ReorderPlanningConfig {
+            service_level_z: 1.65,
+            default_lead_time_days: 14,
+            ordering_cost: 250.0,
+            holding_cost_rate: 0.2,
+            history_days: 180,
+        }
+    }
+
+    #[test]
+    fn seasonal_series_selects_holt_winters() {
+        let result = forecast(&weekly_pattern(8), 14);
+        assert_eq!(result.model, ForecastModel::HoltWinters);
+        assert_eq!(result.scores.len(), CANDIDATES.len());
+    }
+
+    #[test]
+    fn intermittent_series_selects_croston() {
+        // Three units every twelve days: the moving average chases the last
+        // sale and Holt-Winters can't fit a 12-day cycle to a 7-day season.
+        let result = forecast(&intermittent(90, 12, 3.0), 14);
+        assert_eq!(result.model, ForecastModel::Croston);
+    }
+
+    #[test]
+    fn short_history_falls_back_to_moving_average() {
+        let result = forecast(&[1.0, 2.0, 3.0], 5);
+        assert_eq!(result.model, ForecastModel::MovingAverage);
+        assert!(result.scores.is_empty());
+        assert_eq!(result.points, vec![2.0; 5]);
+    }
+
+    #[test]
+    fn croston_rate_is_bias_corrected_size_over_interval() {
+        let points = croston(&intermittent(60, 4, 4.0), 3).unwrap();
+        let expected = (1.0 - CROSTON_ALPHA / 2.0) * 4.0 / 4.0;
+        for point in points {
+            assert!((point - expected).abs() < 1e-9, "{point} != {expected}");
+        }
+    }
+
+    #[test]
+    fn croston_needs_at_least_one_demand() {
+        assert!(croston(&[0.0; 30], 7).is_none());
+    }
+
+    #[test]
+    fn holt_winters_reproduces_weekly_season() {
+        let history = weekly_pattern(8);
+        let points = holt_winters(&history, SEASON_LENGTH * 2).unwrap();
+        for (step, point) in points.iter().enumerate() {
+            let actual = history[(history.len() + step) % SEASON_LENGTH];
+            assert!(
+                (point - actual).abs() < 0.5,
+                "day {step}: {point} vs {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn holt_winters_needs_two_seasons() {
+        assert!(holt_winters(&weekly_pattern(1), 7).is_none());
+    }
+
+    #[test]
+    fn stockout_days_with_zero_demand_is_none() {
+        assert_eq!(days_until_stockout(&[0.0; 90], 5.0), None);
+        assert_eq!(days_until_stockout(&[], 5.0), None);
+    }
+
+    #[test]
+    fn stockout_days_counts_until_demand_exceeds_stock() {
+        assert_eq!(days_until_stockout(&[2.0; 10], 5.0), Some(2));
+        assert_eq!(days_until_stockout(&[2.0; 10], 0.0), Some(0));
+    }
+
+    #[test]
+    fn reorder_plan_uses_eoq_and_lead_time_demand() {
+        let config = config();
+        let plan = plan_reorder(&[2.0; 60], 10, 20_000.0, &config);
+
+        // Flat demand: no safety stock, lead-time demand is 2/day * 10 days.
+        assert_eq!(plan.safety_stock, 0);
+        assert_eq!(plan.reorder_point, 20);
+        assert_eq!(plan.avg_daily_demand, 2.0);
+
+        // EOQ = sqrt(2 * D * S / H) = sqrt(2 * 730 * 250 / 4000) ≈ 9.55.
+        let eoq =
+            (2.0 * 730.0 * config.ordering_cost / (config.holding_cost_rate * 20_000.0)).sqrt();
+        assert_eq!(plan.economic_order_qty, eoq.ceil() as i32);
+        assert_eq!(plan.economic_order_qty, 10);
+    }
+
+    #[test]
+    fn reorder_plan_adds_safety_stock_for_variable_demand() {
+        let history: Vec<f64> = (0..60)
+            .map(|day| if day % 2 == 0 { 1.0 } else { 3.0 })
+            .collect();
+        let plan = plan_reorder(&history, 9, 20_000.0, &config());
+
+        // Safety stock = z * sigma * sqrt(lead time).
+        let safety = 1.65 * residual_sigma(&history) * 3.0;
+        let lead_time_demand: f64 = forecast(&history, 9).points.iter().sum();
+        assert_eq!(plan.safety_stock, safety.ceil() as i32);
+        assert_eq!(
+            plan.reorder_point,
+            (lead_time_demand + safety).ceil() as i32
+        );
+    }
+
+    #[test]
+    fn reorder_plan_without_demand_orders_one_unit() {
+        let plan = plan_reorder(&[0.0; 30], 14, 20_000.0, &config());
+        assert_eq!(plan.economic_order_qty, 1);
+        assert_eq!(plan.reorder_point, 0);
+        assert_eq!(plan.safety_stock, 0);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::models::WarehouseId;
+
+/// Splits a sale over the warehouses holding the car, taking from the one
+/// with the most free stock first. When they can't cover it together, the
+/// error carries their total free stock.
+pub fn split_sale(
+    free_stock: &[(WarehouseId, i32)],
+    quantity: i32,
+) -> Result<Vec<(WarehouseId, i32)>, i32> {
+    let mut sources: Vec<&(WarehouseId, i32)> =
+        free_stock.iter().filter(|(_, free)| *free > 0).collect();
+    sources.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.as_str().cmp(b.0.as_str())));
+
+    let available: i32 = sources.iter().map(|(_, free)| free).sum();
+    if available < quantity {
+        return Err(available);
+    }
+
+    let mut remaining = quantity;
+    let mut parts = Vec::new();
+    for (warehouse_id, free) in sources {
+        if remaining == 0 {
+            break;
+        }
+        let units = remaining.min(*free);
+        parts.push((warehouse_id.clone(), units));
+        remaining -= units;
+    }
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warehouse(id: &str) -> WarehouseId {
+        WarehouseId::new(id.to_string()).unwrap()
+    }
+
+    fn stock(levels: &[(&str, i32)]) -> Vec<(WarehouseId, i32)> {
+        levels
+            .iter()
+            .map(|(id, free)| (warehouse(id), *free))
+            .collect()
+    }
+
+    #[test]
+    fn sale_within_one_location_takes_from_the_fullest() {
+        let parts = split_sale(&stock(&[("W0001", 4), ("W0002", 9)]), 3).unwrap();
+
+        assert_eq!(parts, [(warehouse("W0002"), 3)]);
+    }
+
+    #[test]
+    fn sale_larger_than_the_biggest_location_is_split() {
+        let free = stock(&[("W0001", 4), ("W0002", 6), ("W0003", 0), ("W0004", 4)]);
+
+        let parts = split_sale(&free, 12).unwrap();
+
+        // Ties go to the lower warehouse id.
+        assert_eq!(
+            parts,
+            [
+                (warehouse("W0002"), 6),
+                (warehouse("W0001"), 4),
+                (warehouse("W0004"), 2)
+            ]
+        );
+        assert_eq!(parts.iter().map(|(_, units)| units).sum::<i32>(), 12);
+    }
+
+    #[test]
+    fn sale_beyond_all_locations_reports_free_stock() {
+        let free = stock(&[("W0001", 4), ("W0002", 6), ("W0003", -2)]);
+
+        assert_eq!(split_sale(&free, 11), Err(10));
+        assert_eq!(split_sale(&[], 1), Err(0));
+    }
+}
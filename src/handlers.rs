@@ -3,19 +3,29 @@ use uuid::Uuid;
 
 use axum::{
     extract::{Path, Query, Request, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Json},
 };
 
+use crate::circuit_breaker::CircuitState;
 use crate::error::{AppError, AppResult};
 use crate::extractors::ValidatedJson;
 use crate::middleware::extract_context;
 use crate::models::{
-    Car, CarId, CarResponse, CarSearchQuery, CarSearchRequest, CarSearchResult, CarStatus,
-    CreateCarDto, CreateReservationDto, CreateWarehouseDto, DashboardStats, EngineType,
-    HealthResponse, HealthStatus, InventoryAlertSummary, InventoryMetrics, PaginatedResponse,
-    ReservationResponse, SalesVelocity, StockTransferDto, TransferOrder, UpdateCarDto, Warehouse,
-    WarehouseId,
+    AbcClass, AlertThreshold, Car, CarDepreciation, CarId, CarPriceHistory, CarResponse,
+    CarSearchQuery, CarSearchRequest, CarSearchResult, CarStatus, CheckoutDto,
+    ClassificationReport, CreateCarDto, CreatePricingRuleDto, CreatePurchaseOrderDto,
+    CreateReservationDto, CreateSupplierDto, CreateWarehouseDto, DashboardStats, DemandForecast,
+    EngineType, ForecastQuery, GoodsReceiptDto, HealthResponse, HealthStatus, InventoryAgingReport,
+    InventoryAlertSummary, InventoryMetrics, InventoryValuation, JobExecution, JobExecutionQuery,
+    JobScheduleStatus, JobStatus, MetricsAggregation, MetricsBucket, MetricsGapFill,
+    MetricsHistoryQuery, MetricsHistoryResponse, PaginatedResponse, PricingRule, PurchaseOrder,
+    PurchaseOrderListQuery, PurchaseOrderResponse, PurchaseOrderStatus, RebalancingAcceptance,
+    RebalancingProposal, RebalancingProposalQuery, RebalancingStatus, ReorderOverrideDto,
+    ReorderParametersResponse, ReservationResponse, SaleReceipt, SalesVelocity,
+    SchedulePriceChangeDto, ScheduledPriceChange, SetAlertThresholdDto, SetSupplierPriceDto,
+    SlowMover, StockTransferDto, Supplier, SupplierDetail, SupplierId, SupplierPrice,
+    TransferOrder, UpdateCarDto, Warehouse, WarehouseId, XyzClass,
 };
 use crate::state::AppState;
 
@@ -61,11 +71,133 @@ pub async fn health_check_handler(State(state): State<AppState>) -> impl IntoRes
         uptime_seconds: state.start_time.elapsed().as_secs(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
+        scheduler_leader: match &state.leader_election {
+            Some(leader_election) => Some(leader_election.status().await),
+            None => None,
+        },
     };
 
     (http_status, Json(response)).into_response()
 }
 
+fn probe_status(status: &HealthStatus) -> (bool, serde_json::Value) {
+    match status {
+        HealthStatus::Healthy => (true, serde_json::json!({ "status": "up" })),
+        HealthStatus::Degraded(reason) => (
+            true,
+            serde_json::json!({ "status": "degraded", "details": reason }),
+        ),
+        HealthStatus::Unhealthy(reason) => (
+            false,
+            serde_json::json!({ "status": "down", "details": reason }),
+        ),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "Process is running", body = serde_json::Value)
+    ),
+    tag = "System"
+)]
+pub async fn liveness_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "status": "alive",
+        "uptime_seconds": state.lifecycle.uptime().as_secs(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/startup",
+    responses(
+        (status = 200, description = "Startup complete", body = serde_json::Value),
+        (status = 503, description = "Still starting")
+    ),
+    tag = "System"
+)]
+pub async fn startup_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let started = state.lifecycle.is_started();
+    let status = if started {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(serde_json::json!({
+            "status": if started { "started" } else { "starting" },
+            "uptime_seconds": state.lifecycle.uptime().as_secs(),
+        })),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Ready to receive traffic", body = serde_json::Value),
+        (status = 503, description = "Not ready: dependency down, breaker open or draining")
+    ),
+    tag = "System"
+)]
+pub async fn readiness_handler(State(state): State<AppState>) -> impl IntoResponse {
+    let lifecycle = &state.lifecycle;
+    let draining = lifecycle.is_draining();
+
+    let (database_ok, database) = probe_status(&state.health_check_service.check_db().await);
+    let (migrations_ok, migrations) =
+        probe_status(&state.health_check_service.check_migrations().await);
+
+    let open_breakers: Vec<String> = state
+        .circuit_breakers
+        .all()
+        .iter()
+        .filter(|breaker| breaker.state() == CircuitState::Open)
+        .map(|breaker| breaker.name().to_string())
+        .collect();
+
+    let heartbeat_age = lifecycle.heartbeat_age();
+    let worker_ok = heartbeat_age.is_some_and(|age| age <= lifecycle.heartbeat_max_age());
+
+    let ready = !draining
+        && lifecycle.is_started()
+        && database_ok
+        && migrations_ok
+        && open_breakers.is_empty()
+        && worker_ok;
+
+    let response = serde_json::json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "checks": {
+            "draining": draining,
+            "startup_complete": lifecycle.is_started(),
+            "database": database,
+            "migrations": migrations,
+            "circuit_breakers": {
+                "status": if open_breakers.is_empty() { "closed" } else { "open" },
+                "open": open_breakers,
+            },
+            "background_worker": {
+                "status": if worker_ok { "up" } else { "stale" },
+                "last_heartbeat_seconds_ago": heartbeat_age.map(|age| age.as_secs()),
+                "max_age_seconds": lifecycle.heartbeat_max_age().as_secs(),
+            },
+        },
+    });
+
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(response))
+}
+
 #[utoipa::path(
     get,
     path = "/health/circuit-breakers",
@@ -75,18 +207,37 @@ pub async fn health_check_handler(State(state): State<AppState>) -> impl IntoRes
     tag = "System"
 )]
 pub async fn circuit_breaker_health_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let metrics = state.db_circuit_breaker.metrics().await;
+    let mut breakers = Vec::new();
 
-    let response = serde_json::json!({
-        "circuit_breakers": [{
+    for breaker in state.circuit_breakers.all() {
+        let metrics = breaker.metrics();
+        let config = breaker.config();
+        breakers.push(serde_json::json!({
             "name": metrics.name,
             "state": format!("{}", metrics.state),
-            "failure_count": metrics.failure_count,
-            "success_count": metrics.success_count,
+            "window": {
+                "calls": metrics.calls_in_window,
+                "failures": metrics.failure_count,
+                "slow_calls": metrics.slow_call_count,
+                "failure_rate": metrics.failure_rate,
+                "slow_call_rate": metrics.slow_call_rate,
+            },
+            "thresholds": {
+                "failure_rate": config.failure_rate_threshold,
+                "slow_call_rate": config.slow_call_rate_threshold,
+                "slow_call_ms": config.slow_call_duration.as_millis() as u64,
+                "minimum_calls": config.minimum_calls,
+                "window_secs": config.window.as_secs(),
+                "call_timeout_ms": config.call_timeout.map(|t| t.as_millis() as u64),
+            },
+            "half_open_successes": metrics.success_count,
             "seconds_in_current_state": metrics.time_in_current_state.as_secs(),
             "last_failure_seconds_ago": metrics.last_failure_time.map(|t| t.elapsed().as_secs()),
-        }]
-    });
+            "recent_transitions": metrics.recent_transitions,
+        }));
+    }
+
+    let response = serde_json::json!({ "circuit_breakers": breakers });
 
     Json(response)
 }
@@ -100,14 +251,16 @@ pub async fn circuit_breaker_health_handler(State(state): State<AppState>) -> im
     tag = "System"
 )]
 pub async fn cache_metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let metrics = state.car_service.cache_metrics();
+    let metrics = state.car_service.cache_metrics().await;
 
     let response = serde_json::json!({
         "cache": {
+            "backend": metrics.backend,
             "dashboard_stats_entries": metrics.dashboard_stats_size,
             "car_by_id_entries": metrics.car_by_id_size,
             "low_stock_entries": metrics.low_stock_size,
             "depreciation_entries": metrics.depreciation_size,
+            "stale_read_entries": metrics.stale_reads_size,
         }
     });
 
@@ -123,8 +276,8 @@ pub async fn cache_metrics_handler(State(state): State<AppState>) -> impl IntoRe
     tag = "System"
 )]
 pub async fn pool_metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
-    let metrics = match &state.pool_manager {
-        Some(manager) => manager.metrics().await,
+    let (metrics, max_replica_lag_ms) = match &state.pool_manager {
+        Some(manager) => (manager.metrics().await, manager.max_replica_lag_ms()),
         None => {
             return Json(serde_json::json!({
                 "error": "Pool manager not available"
@@ -139,17 +292,61 @@ pub async fn pool_metrics_handler(State(state): State<AppState>) -> impl IntoRes
             "idle": metrics.idle,
             "min_connections": metrics.min_connections,
             "max_connections": metrics.max_connections,
+            "capacity": metrics.capacity,
             "usage_percent": format!("{:.2}%", metrics.usage_percent * 100.0),
             "wait_time_ms": metrics.wait_time_ms,
             "health_check_failures": metrics.health_check_failures,
             "total_acquires": metrics.total_acquires,
             "slow_acquires": metrics.slow_acquires,
-        }
+        },
+        "scaling": {
+            "scale_ups": metrics.scale_ups,
+            "scale_downs": metrics.scale_downs,
+            "recent_events": metrics.scale_events.iter().map(|event| serde_json::json!({
+                "timestamp": event.timestamp.to_rfc3339(),
+                "direction": event.direction.to_string(),
+                "from_size": event.from_size,
+                "to_size": event.to_size,
+                "usage_percent": format!("{:.2}%", event.usage_percent * 100.0),
+            })).collect::<Vec<_>>(),
+        },
+        "replicas": metrics.replicas.iter().map(|replica| serde_json::json!({
+            "name": replica.name,
+            "healthy": replica.healthy,
+            "size": replica.size,
+            "active": replica.active,
+            "idle": replica.idle,
+            "replication_lag_ms": replica.replication_lag_ms,
+            "routed_queries": replica.routed_queries,
+            "health_check_failures": replica.health_check_failures,
+        })).collect::<Vec<_>>(),
+        "max_replica_lag_ms": max_replica_lag_ms,
     });
 
     Json(response)
 }
 
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Prometheus metrics in text exposition format", body = String),
+        (status = 404, description = "Metrics are disabled")
+    ),
+    tag = "System"
+)]
+pub async fn metrics_handler(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
+    let handle = state.metrics_handle.as_ref().ok_or(AppError::NotFound)?;
+
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        handle.render(),
+    ))
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/cars",
@@ -233,7 +430,9 @@ pub async fn get_car_by_id_resilient_handler(
     params(
         ("page" = Option<u32>, Query, description = "Number of page"),
         ("page_size" = Option<u32>, Query, description = "Elements by page"),
-        ("brand" = Option<String>, Query, description = "Filter by brand")
+        ("brand" = Option<String>, Query, description = "Filter by brand"),
+        ("abc_class" = Option<AbcClass>, Query, description = "Filter by revenue class"),
+        ("xyz_class" = Option<XyzClass>, Query, description = "Filter by demand variability class")
     ),
     responses(
         (status = 200, description = "List of cars paginated", body = PaginatedResponse<Car>),
@@ -385,6 +584,77 @@ pub async fn search_cars_handler(
     Ok(Json(result))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/cars/{id}/prices",
+    params(
+        ("id" = String, Path, description = "Unique ID of car")
+    ),
+    responses(
+        (status = 200, description = "Price changes with sales velocity around each, and pending scheduled changes", body = CarPriceHistory),
+        (status = 404, description = "Car not found")
+    ),
+    tag = "Services"
+)]
+pub async fn get_price_history_handler(
+    State(state): State<AppState>,
+    Path(car_id): Path<CarId>,
+) -> AppResult<impl IntoResponse> {
+    let history = state.price_history_service.get_history(car_id).await?;
+    Ok(Json(history))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/cars/{id}/prices/scheduled",
+    params(
+        ("id" = String, Path, description = "Unique ID of car")
+    ),
+    request_body = SchedulePriceChangeDto,
+    responses(
+        (status = 201, description = "Price change scheduled", body = ScheduledPriceChange),
+        (status = 400, description = "Non-positive price or effective time not in the future"),
+        (status = 404, description = "Car not found")
+    ),
+    tag = "Services"
+)]
+pub async fn schedule_price_change_handler(
+    State(state): State<AppState>,
+    Path(car_id): Path<CarId>,
+    ValidatedJson(dto): ValidatedJson<SchedulePriceChangeDto>,
+) -> AppResult<impl IntoResponse> {
+    let change = state
+        .price_history_service
+        .schedule_change(car_id, dto)
+        .await?;
+    Ok((StatusCode::CREATED, Json(change)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/cars/{id}/prices/scheduled/{change_id}",
+    params(
+        ("id" = String, Path, description = "Unique ID of car"),
+        ("change_id" = Uuid, Path, description = "Scheduled price change ID")
+    ),
+    responses(
+        (status = 200, description = "Scheduled price change cancelled", body = ScheduledPriceChange),
+        (status = 404, description = "Scheduled price change not found"),
+        (status = 422, description = "Price change already applied or cancelled")
+    ),
+    tag = "Services"
+)]
+pub async fn cancel_scheduled_price_change_handler(
+    State(state): State<AppState>,
+    Path((car_id, change_id)): Path<(CarId, Uuid)>,
+) -> AppResult<impl IntoResponse> {
+    let change = state
+        .price_history_service
+        .cancel_change(car_id, change_id)
+        .await?;
+    Ok(Json(change))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/cars/analytics/dashboard",
@@ -404,7 +674,7 @@ pub async fn get_dashboard_stats_handler(
 #[utoipa::path(
     get,
     path = "/api/v1/cars/analytics/depreciation",
-    responses((status = 200, body = [CarResponse])),
+    responses((status = 200, description = "Estimated current value of every car from its engine type's depreciation curve and mileage", body = [CarDepreciation])),
     tag = "Analytics"
 )]
 pub async fn get_depreciation_handler(
@@ -414,6 +684,19 @@ pub async fn get_depreciation_handler(
     Ok(Json(report))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/cars/analytics/valuation",
+    responses((status = 200, description = "Book value against list value for the whole inventory and per warehouse", body = InventoryValuation)),
+    tag = "Analytics"
+)]
+pub async fn get_inventory_valuation_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    let valuation = state.car_service.get_inventory_valuation().await?;
+    Ok(Json(valuation))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/cars/analytics/low-stock",
@@ -477,6 +760,32 @@ pub async fn confirm_reservation_handler(
     Ok(Json(reservation))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/reservations/{id}/checkout",
+    params(
+        ("id" = Uuid, Path, description = "Reservation ID")
+    ),
+    request_body = CheckoutDto,
+    responses(
+        (status = 201, description = "Sale recorded at the price left by the pricing rules", body = SaleReceipt),
+        (status = 404, description = "Reservation not found"),
+        (status = 422, description = "Promo code does not apply to this sale")
+    ),
+    tag = "Reservations"
+)]
+pub async fn checkout_reservation_handler(
+    State(state): State<AppState>,
+    Path(reservation_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<CheckoutDto>,
+) -> AppResult<impl IntoResponse> {
+    let receipt = state
+        .sale_service
+        .process_sale(reservation_id, dto.customer_id, dto.promo_code)
+        .await?;
+    Ok((StatusCode::CREATED, Json(receipt)))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/v1/reservations/{id}",
@@ -632,6 +941,352 @@ pub async fn get_transfer_handler(
     Ok(Json(transfer))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/warehouses/rebalancing/proposals",
+    responses(
+        (status = 201, description = "Proposals generated; earlier open proposals are superseded", body = Vec<RebalancingProposal>)
+    ),
+    tag = "Warehouses"
+)]
+pub async fn generate_rebalancing_proposals_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    let proposals = state.rebalancing_service.generate_proposals().await?;
+    Ok((StatusCode::CREATED, Json(proposals)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/warehouses/rebalancing/proposals",
+    params(
+        ("status" = Option<RebalancingStatus>, Query, description = "Filter by status")
+    ),
+    responses(
+        (status = 200, description = "Most recent rebalancing proposals", body = Vec<RebalancingProposal>)
+    ),
+    tag = "Warehouses"
+)]
+pub async fn list_rebalancing_proposals_handler(
+    State(state): State<AppState>,
+    Query(query): Query<RebalancingProposalQuery>,
+) -> AppResult<impl IntoResponse> {
+    let proposals = state
+        .rebalancing_service
+        .list_proposals(query.status)
+        .await?;
+    Ok(Json(proposals))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/warehouses/rebalancing/proposals/{id}/accept",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Proposal accepted and stock transferred", body = RebalancingAcceptance),
+        (status = 404, description = "Proposal not found"),
+        (status = 409, description = "Insufficient stock in source warehouse"),
+        (status = 422, description = "Proposal already decided")
+    ),
+    tag = "Warehouses"
+)]
+pub async fn accept_rebalancing_proposal_handler(
+    State(state): State<AppState>,
+    Path(proposal_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let acceptance = state.rebalancing_service.accept(proposal_id).await?;
+    Ok(Json(acceptance))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/warehouses/rebalancing/proposals/{id}/reject",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Proposal rejected", body = RebalancingProposal),
+        (status = 404, description = "Proposal not found"),
+        (status = 422, description = "Proposal already decided")
+    ),
+    tag = "Warehouses"
+)]
+pub async fn reject_rebalancing_proposal_handler(
+    State(state): State<AppState>,
+    Path(proposal_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let proposal = state.rebalancing_service.reject(proposal_id).await?;
+    Ok(Json(proposal))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/pricing/rules",
+    request_body = CreatePricingRuleDto,
+    responses(
+        (status = 201, description = "Pricing rule created", body = PricingRule),
+        (status = 400, description = "Validation error or inconsistent conditions"),
+        (status = 409, description = "Promo code already used by an active rule")
+    ),
+    tag = "Pricing"
+)]
+pub async fn create_pricing_rule_handler(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<CreatePricingRuleDto>,
+) -> AppResult<impl IntoResponse> {
+    let rule = state.pricing_service.create_rule(dto).await?;
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/pricing/rules",
+    responses(
+        (status = 200, description = "Pricing rules, active first", body = Vec<PricingRule>)
+    ),
+    tag = "Pricing"
+)]
+pub async fn list_pricing_rules_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    let rules = state.pricing_service.list_rules().await?;
+    Ok(Json(rules))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/pricing/rules/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Pricing rule ID")
+    ),
+    responses(
+        (status = 200, description = "Pricing rule deactivated", body = PricingRule),
+        (status = 404, description = "Pricing rule not found")
+    ),
+    tag = "Pricing"
+)]
+pub async fn deactivate_pricing_rule_handler(
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let rule = state.pricing_service.deactivate_rule(rule_id).await?;
+    Ok(Json(rule))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/suppliers",
+    request_body = CreateSupplierDto,
+    responses(
+        (status = 201, description = "Supplier created successfully", body = Supplier),
+        (status = 400, description = "Validation error or invalid supplier ID"),
+        (status = 409, description = "Supplier ID already exists")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn create_supplier_handler(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<CreateSupplierDto>,
+) -> AppResult<impl IntoResponse> {
+    let supplier = state.purchasing_service.create_supplier(dto).await?;
+    Ok((StatusCode::CREATED, Json(supplier)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/suppliers",
+    responses(
+        (status = 200, description = "Active suppliers", body = Vec<Supplier>)
+    ),
+    tag = "Purchasing"
+)]
+pub async fn list_suppliers_handler(State(state): State<AppState>) -> AppResult<impl IntoResponse> {
+    let suppliers = state.purchasing_service.list_suppliers().await?;
+    Ok(Json(suppliers))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/suppliers/{id}",
+    params(
+        ("id" = String, Path, description = "Supplier ID")
+    ),
+    responses(
+        (status = 200, description = "Supplier with its price list", body = SupplierDetail),
+        (status = 404, description = "Supplier not found")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn get_supplier_handler(
+    State(state): State<AppState>,
+    Path(supplier_id): Path<SupplierId>,
+) -> AppResult<impl IntoResponse> {
+    let supplier = state.purchasing_service.get_supplier(supplier_id).await?;
+    Ok(Json(supplier))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/suppliers/{id}/prices/{car_id}",
+    params(
+        ("id" = String, Path, description = "Supplier ID"),
+        ("car_id" = String, Path, description = "Unique ID of car")
+    ),
+    request_body = SetSupplierPriceDto,
+    responses(
+        (status = 200, description = "Price list entry created or replaced", body = SupplierPrice),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Supplier or car not found")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn set_supplier_price_handler(
+    State(state): State<AppState>,
+    Path((supplier_id, car_id)): Path<(SupplierId, CarId)>,
+    ValidatedJson(dto): ValidatedJson<SetSupplierPriceDto>,
+) -> AppResult<impl IntoResponse> {
+    let price = state
+        .purchasing_service
+        .set_supplier_price(supplier_id, car_id, dto)
+        .await?;
+    Ok(Json(price))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/purchase-orders",
+    request_body = CreatePurchaseOrderDto,
+    responses(
+        (status = 201, description = "Purchase order drafted", body = PurchaseOrderResponse),
+        (status = 400, description = "Validation error or duplicate lines"),
+        (status = 404, description = "Supplier, warehouse or car not found"),
+        (status = 422, description = "Missing supplier price or quantity below supplier minimum")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn create_purchase_order_handler(
+    State(state): State<AppState>,
+    ValidatedJson(dto): ValidatedJson<CreatePurchaseOrderDto>,
+) -> AppResult<impl IntoResponse> {
+    let order = state.purchasing_service.create_purchase_order(dto).await?;
+    Ok((StatusCode::CREATED, Json(order)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/purchase-orders",
+    params(
+        ("status" = Option<PurchaseOrderStatus>, Query, description = "Filter by status"),
+        ("supplier_id" = Option<String>, Query, description = "Filter by supplier")
+    ),
+    responses(
+        (status = 200, description = "Most recent purchase orders", body = Vec<PurchaseOrder>)
+    ),
+    tag = "Purchasing"
+)]
+pub async fn list_purchase_orders_handler(
+    State(state): State<AppState>,
+    Query(query): Query<PurchaseOrderListQuery>,
+) -> AppResult<impl IntoResponse> {
+    let orders = state.purchasing_service.list_purchase_orders(query).await?;
+    Ok(Json(orders))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/purchase-orders/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Purchase order ID")
+    ),
+    responses(
+        (status = 200, description = "Purchase order with lines", body = PurchaseOrderResponse),
+        (status = 404, description = "Purchase order not found")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn get_purchase_order_handler(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let order = state.purchasing_service.get_purchase_order(po_id).await?;
+    Ok(Json(order))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/purchase-orders/{id}/submit",
+    params(
+        ("id" = Uuid, Path, description = "Purchase order ID")
+    ),
+    responses(
+        (status = 200, description = "Purchase order submitted to the supplier", body = PurchaseOrderResponse),
+        (status = 404, description = "Purchase order not found"),
+        (status = 409, description = "Purchase order is not a draft")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn submit_purchase_order_handler(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let order = state
+        .purchasing_service
+        .submit_purchase_order(po_id)
+        .await?;
+    Ok(Json(order))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/purchase-orders/{id}/cancel",
+    params(
+        ("id" = Uuid, Path, description = "Purchase order ID")
+    ),
+    responses(
+        (status = 200, description = "Purchase order cancelled", body = PurchaseOrderResponse),
+        (status = 404, description = "Purchase order not found"),
+        (status = 409, description = "Goods were already received against the order")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn cancel_purchase_order_handler(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+) -> AppResult<impl IntoResponse> {
+    let order = state
+        .purchasing_service
+        .cancel_purchase_order(po_id)
+        .await?;
+    Ok(Json(order))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/purchase-orders/{id}/receipts",
+    params(
+        ("id" = Uuid, Path, description = "Purchase order ID")
+    ),
+    request_body = GoodsReceiptDto,
+    responses(
+        (status = 200, description = "Goods booked into the receiving warehouse", body = PurchaseOrderResponse),
+        (status = 400, description = "Validation error or duplicate lines"),
+        (status = 404, description = "Purchase order not found"),
+        (status = 409, description = "Order not submitted or quantity exceeds outstanding"),
+        (status = 422, description = "Car is not on the purchase order")
+    ),
+    tag = "Purchasing"
+)]
+pub async fn receive_goods_handler(
+    State(state): State<AppState>,
+    Path(po_id): Path<Uuid>,
+    ValidatedJson(dto): ValidatedJson<GoodsReceiptDto>,
+) -> AppResult<impl IntoResponse> {
+    let order = state.purchasing_service.receive_goods(po_id, dto).await?;
+    Ok(Json(order))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/inventory/alerts",
@@ -651,10 +1306,11 @@ pub async fn get_stock_alerts_handler(
     get,
     path = "/api/v1/inventory/velocity",
     params(
-        ("days" = Option<i32>, Query, description = "Analysis period in days (default 30)")
+        ("days" = Option<i32>, Query, description = "Analysis period in days, 1 to 365 (default 30)")
     ),
     responses(
         (status = 200, description = "Sales velocity data", body = Vec<SalesVelocity>),
+        (status = 400, description = "Period out of range"),
     ),
     tag = "Inventory Analytics"
 )]
@@ -687,3 +1343,280 @@ pub async fn get_inventory_metrics_handler(
         .await?;
     Ok(Json(metrics))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/forecast/{car_id}",
+    params(
+        ("car_id" = String, Path, description = "Unique ID of car"),
+        ("horizon_days" = Option<u32>, Query, description = "Days to forecast, 1 to 180 (default 30)"),
+        ("history_days" = Option<u32>, Query, description = "Days of sales history to fit, 14 to 730 (default 180)")
+    ),
+    responses(
+        (status = 200, description = "Daily demand forecast with 95% prediction intervals", body = DemandForecast),
+        (status = 400, description = "Horizon or history out of range"),
+        (status = 404, description = "Car not found")
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_demand_forecast_handler(
+    State(state): State<AppState>,
+    Path(car_id): Path<CarId>,
+    Query(query): Query<ForecastQuery>,
+) -> AppResult<impl IntoResponse> {
+    let forecast = state
+        .inventory_analytics_service
+        .get_demand_forecast(car_id, query)
+        .await?;
+    Ok(Json(forecast))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/reorder/{car_id}",
+    params(
+        ("car_id" = String, Path, description = "Unique ID of car")
+    ),
+    responses(
+        (status = 200, description = "Reorder parameters, overrides and recent changes", body = ReorderParametersResponse),
+        (status = 404, description = "Car not found")
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_reorder_parameters_handler(
+    State(state): State<AppState>,
+    Path(car_id): Path<CarId>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.reorder_service.get_parameters(&car_id).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/inventory/reorder/{car_id}",
+    params(
+        ("car_id" = String, Path, description = "Unique ID of car")
+    ),
+    request_body = ReorderOverrideDto,
+    responses(
+        (status = 200, description = "Overrides replaced and applied", body = ReorderParametersResponse),
+        (status = 400, description = "Validation error"),
+        (status = 404, description = "Car not found")
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn set_reorder_overrides_handler(
+    State(state): State<AppState>,
+    Path(car_id): Path<CarId>,
+    ValidatedJson(payload): ValidatedJson<ReorderOverrideDto>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(
+        state
+            .reorder_service
+            .set_overrides(&car_id, payload)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/classification",
+    responses(
+        (status = 200, description = "ABC/XYZ classes computed from current sales, ranked by revenue", body = ClassificationReport)
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_classification_report_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.classification_service.get_report().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/classification/thresholds",
+    responses(
+        (status = 200, description = "Stock alert thresholds per ABC class", body = Vec<AlertThreshold>)
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn list_alert_thresholds_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(
+        state.classification_service.list_alert_thresholds().await?,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/inventory/classification/thresholds/{class}",
+    params(
+        ("class" = AbcClass, Path, description = "ABC class")
+    ),
+    request_body = SetAlertThresholdDto,
+    responses(
+        (status = 200, description = "Thresholds replaced", body = AlertThreshold),
+        (status = 400, description = "Validation error or warning ratio below critical ratio")
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn set_alert_threshold_handler(
+    State(state): State<AppState>,
+    Path(abc_class): Path<AbcClass>,
+    ValidatedJson(payload): ValidatedJson<SetAlertThresholdDto>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(
+        state
+            .classification_service
+            .set_alert_threshold(abc_class, payload)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/aging",
+    responses(
+        (status = 200, description = "Days in stock per car by receipt date, aging buckets and accrued holding cost", body = InventoryAgingReport)
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_inventory_aging_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.aging_service.get_report().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/aging/slow-movers",
+    responses(
+        (status = 200, description = "Aged, slow-selling cars with marketing push suggestions, costliest to hold first", body = Vec<SlowMover>)
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_slow_movers_handler(
+    State(state): State<AppState>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.aging_service.get_slow_movers().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/metrics/history",
+    params(
+        ("from" = Option<DateTime<Utc>>, Query, description = "Range start, inclusive (default 7 days before `to`)"),
+        ("to" = Option<DateTime<Utc>>, Query, description = "Range end, exclusive (default now)"),
+        ("bucket" = Option<MetricsBucket>, Query, description = "hour, day, week or month (default: finest that fits 1000 points)"),
+        ("aggregation" = Option<MetricsAggregation>, Query, description = "avg, min, max, sum or last (default avg)"),
+        ("fill" = Option<MetricsGapFill>, Query, description = "Empty buckets: none, null, zero or previous (default null)")
+    ),
+    responses(
+        (status = 200, description = "Bucketed metrics with period-over-period deltas", body = MetricsHistoryResponse),
+        (status = 400, description = "Invalid range or too many buckets"),
+    ),
+    tag = "Inventory Analytics"
+)]
+pub async fn get_metrics_history_handler(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsHistoryQuery>,
+) -> AppResult<impl IntoResponse> {
+    let history = state
+        .inventory_analytics_service
+        .get_metrics_history(query)
+        .await?;
+    Ok(Json(history))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/jobs",
+    params(
+        ("page" = Option<u32>, Query, description = "Number of page"),
+        ("page_size" = Option<u32>, Query, description = "Elements by page"),
+        ("job_type" = Option<String>, Query, description = "Filter by job name"),
+        ("status" = Option<JobStatus>, Query, description = "Filter by run status")
+    ),
+    responses(
+        (status = 200, description = "Job runs, most recent first", body = PaginatedResponse<JobExecution>),
+    ),
+    tag = "Admin"
+)]
+pub async fn list_job_executions_handler(
+    State(state): State<AppState>,
+    Query(query): Query<JobExecutionQuery>,
+) -> AppResult<impl IntoResponse> {
+    let executions = state.job_service.list_executions(query).await?;
+    Ok(Json(executions))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/jobs/schedules",
+    responses(
+        (status = 200, description = "Registered jobs and their schedules", body = Vec<JobScheduleStatus>),
+    ),
+    tag = "Admin"
+)]
+pub async fn list_job_schedules_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.job_service.list_schedules())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/{name}/run",
+    params(
+        ("name" = String, Path, description = "Job name")
+    ),
+    responses(
+        (status = 202, description = "Job started", body = JobExecution),
+        (status = 404, description = "Unknown job"),
+        (status = 409, description = "Job is at its concurrency limit"),
+    ),
+    tag = "Admin"
+)]
+pub async fn trigger_job_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    let execution = state.job_service.trigger(&name).await?;
+    Ok((StatusCode::ACCEPTED, Json(execution)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/{name}/pause",
+    params(
+        ("name" = String, Path, description = "Job name")
+    ),
+    responses(
+        (status = 200, description = "Scheduled runs paused", body = JobScheduleStatus),
+        (status = 404, description = "Unknown job"),
+    ),
+    tag = "Admin"
+)]
+pub async fn pause_job_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.job_service.pause(&name)?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/jobs/{name}/resume",
+    params(
+        ("name" = String, Path, description = "Job name")
+    ),
+    responses(
+        (status = 200, description = "Scheduled runs resumed", body = JobScheduleStatus),
+        (status = 404, description = "Unknown job"),
+    ),
+    tag = "Admin"
+)]
+pub async fn resume_job_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<impl IntoResponse> {
+    Ok(Json(state.job_service.resume(&name)?))
+}
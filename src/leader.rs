@@ -0,0 +1,271 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::{PgConnectOptions, PgConnection};
+use sqlx::{Connection, Postgres};
+use tokio::sync::watch;
+
+use crate::config::LeaderElectionConfig;
+use crate::models::LeaderStatus;
+use crate::pool_manager::PoolHandle;
+
+const LEADER_LOCK_KEY: i32 = 0;
+const CONNECTION_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Elects one replica to run scheduled jobs. The leader holds a session-level
+/// advisory lock on a dedicated connection; if it dies its session ends,
+/// Postgres releases the lock and another replica takes over on its next
+/// attempt.
+pub struct LeaderElection {
+    instance_id: String,
+    lock_namespace: i32,
+    connect_options: PgConnectOptions,
+    pool: PoolHandle,
+    retry_interval: Duration,
+    is_leader: AtomicBool,
+    leader_since: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl LeaderElection {
+    pub fn new(pool: PoolHandle, config: &LeaderElectionConfig) -> Self {
+        let instance_id = config.instance_id();
+        let connect_options = pool
+            .get()
+            .connect_options()
+            .as_ref()
+            .clone()
+            .application_name(&instance_id);
+
+        Self {
+            instance_id,
+            lock_namespace: config.lock_namespace,
+            connect_options,
+            pool,
+            retry_interval: config.retry_interval(),
+            is_leader: AtomicBool::new(false),
+            leader_since: Mutex::new(None),
+        }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Acquire)
+    }
+
+    fn set_leader(&self, leader: bool) {
+        if self.is_leader.swap(leader, Ordering::AcqRel) == leader {
+            return;
+        }
+
+        *self.leader_since.lock().expect("leader state poisoned") = leader.then(Utc::now);
+        metrics::gauge!("scheduler_is_leader").set(if leader { 1.0 } else { 0.0 });
+
+        if leader {
+            tracing::info!(instance_id = %self.instance_id, "Acquired scheduler leadership");
+        } else {
+            tracing::warn!(instance_id = %self.instance_id, "Gave up scheduler leadership");
+        }
+    }
+
+    /// Campaigns for leadership until shutdown, then releases the lock so a
+    /// standby can take over without waiting for the session to time out.
+    pub async fn run(&self, mut shutdown_rx: watch::Receiver<bool>) {
+        tracing::info!(
+            instance_id = %self.instance_id,
+            retry_interval_ms = self.retry_interval.as_millis() as u64,
+            "Leader election started"
+        );
+
+        let mut connection: Option<PgConnection> = None;
+
+        loop {
+            self.campaign(&mut connection).await;
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.retry_interval) => {}
+                changed = shutdown_rx.changed() => {
+                    if changed.is_err() || *shutdown_rx.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(mut conn) = connection.take() {
+            if self.is_leader() {
+                let _ = sqlx::query("SELECT pg_advisory_unlock($1, $2)")
+                    .bind(self.lock_namespace)
+                    .bind(LEADER_LOCK_KEY)
+                    .execute(&mut conn)
+                    .await;
+            }
+            let _ = conn.close().await;
+        }
+        self.set_leader(false);
+
+        tracing::info!(instance_id = %self.instance_id, "Leader election stopped");
+    }
+
+    async fn campaign(&self, connection: &mut Option<PgConnection>) {
+        if self.is_leader() {
+            let alive = match connection.as_mut() {
+                Some(conn) => matches!(
+                    tokio::time::timeout(CONNECTION_CHECK_TIMEOUT, conn.ping()).await,
+                    Ok(Ok(()))
+                ),
+                None => false,
+            };
+
+            if !alive {
+                tracing::warn!(
+                    instance_id = %self.instance_id,
+                    "Leader connection lost, stepping down"
+                );
+                *connection = None;
+                self.set_leader(false);
+            }
+            return;
+        }
+
+        if connection.is_none() {
+            match tokio::time::timeout(
+                CONNECTION_CHECK_TIMEOUT,
+                PgConnection::connect_with(&self.connect_options),
+            )
+            .await
+            {
+                Ok(Ok(conn)) => *connection = Some(conn),
+                Ok(Err(e)) => {
+                    tracing::debug!(error = %e, "Leader election connection failed");
+                    return;
+                }
+                Err(_) => {
+                    tracing::debug!("Leader election connection timed out");
+                    return;
+                }
+            }
+        }
+
+        let Some(conn) = connection.as_mut() else {
+            return;
+        };
+
+        match sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock($1, $2)")
+            .bind(self.lock_namespace)
+            .bind(LEADER_LOCK_KEY)
+            .fetch_one(&mut *conn)
+            .await
+        {
+            Ok(acquired) => self.set_leader(acquired),
+            Err(e) => {
+                tracing::warn!(error = %e, "Leader election lock attempt failed");
+                *connection = None;
+            }
+        }
+    }
+
+    /// Instance currently holding the leader lock, from any replica's view.
+    pub async fn current_leader(&self) -> sqlx::Result<Option<String>> {
+        sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT a.application_name
+            FROM pg_locks l
+            JOIN pg_stat_activity a ON a.pid = l.pid
+            WHERE l.locktype = 'advisory'
+              AND l.granted
+              AND l.classid::bigint = $1
+              AND l.objid::bigint = $2
+              AND l.objsubid = 2
+            LIMIT 1
+            "#,
+        )
+        .bind(self.lock_namespace as i64)
+        .bind(LEADER_LOCK_KEY as i64)
+        .fetch_optional(&self.pool.get())
+        .await
+    }
+
+    pub async fn status(&self) -> LeaderStatus {
+        let current_leader =
+            match tokio::time::timeout(CONNECTION_CHECK_TIMEOUT, self.current_leader()).await {
+                Ok(Ok(leader)) => leader,
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %e, "Failed to look up current scheduler leader");
+                    None
+                }
+                Err(_) => None,
+            };
+
+        LeaderStatus {
+            instance_id: self.instance_id.clone(),
+            is_leader: self.is_leader(),
+            leader_since: *self.leader_since.lock().expect("leader state poisoned"),
+            current_leader,
+        }
+    }
+
+    /// Cluster-wide lock for a single job run, so a manual trigger or a
+    /// leadership handover never runs the same job on two replicas at once.
+    pub async fn try_job_lock(&self, job: &'static str) -> sqlx::Result<Option<JobLock>> {
+        let mut conn = self.pool.get().acquire().await?;
+        let namespace = self.job_lock_namespace();
+
+        let acquired =
+            sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock($1, hashtext($2))")
+                .bind(namespace)
+                .bind(job)
+                .fetch_one(&mut *conn)
+                .await?;
+
+        Ok(acquired.then(|| JobLock {
+            conn: Some(conn),
+            namespace,
+            job,
+        }))
+    }
+
+    fn job_lock_namespace(&self) -> i32 {
+        self.lock_namespace.wrapping_add(1)
+    }
+}
+
+pub struct JobLock {
+    conn: Option<PoolConnection<Postgres>>,
+    namespace: i32,
+    job: &'static str,
+}
+
+impl JobLock {
+    pub async fn release(mut self) {
+        let Some(mut conn) = self.conn.take() else {
+            return;
+        };
+
+        let unlocked = sqlx::query("SELECT pg_advisory_unlock($1, hashtext($2))")
+            .bind(self.namespace)
+            .bind(self.job)
+            .execute(&mut *conn)
+            .await;
+
+        if let Err(e) = unlocked {
+            tracing::warn!(error = %e, job = self.job, "Failed to release job lock, closing session");
+            let _ = conn.detach().close().await;
+        }
+    }
+}
+
+impl Drop for JobLock {
+    fn drop(&mut self) {
+        // Not released explicitly: end the session so Postgres drops the lock
+        // instead of returning a locked connection to the pool.
+        if let Some(conn) = self.conn.take() {
+            drop(conn.detach());
+        }
+    }
+}
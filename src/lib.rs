@@ -1,16 +1,29 @@
+pub mod aging;
 pub mod background;
+pub mod bulkhead;
 pub mod cache;
 pub mod circuit_breaker;
+pub mod classification;
 pub mod config;
+pub mod depreciation;
 pub mod error;
 pub mod extractors;
+pub mod forecasting;
+pub mod fulfilment;
 pub mod handlers;
+pub mod leader;
+pub mod lifecycle;
 pub mod middleware;
 pub mod models;
 pub mod observability;
 pub mod pool_manager;
+pub mod pricing;
+pub mod rate_limit;
+pub mod rebalancing;
 pub mod repositories;
+pub mod resilience;
 pub mod routes;
+pub mod scheduler;
 pub mod services;
 pub mod state;
 pub mod uow;
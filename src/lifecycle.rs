@@ -0,0 +1,79 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use sqlx::migrate::Migrator;
+
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+const NO_HEARTBEAT: u64 = u64::MAX;
+
+/// Process lifecycle flags shared by the probes, the background worker and
+/// the shutdown path.
+pub struct Lifecycle {
+    created_at: Instant,
+    startup_complete: AtomicBool,
+    draining: AtomicBool,
+    last_heartbeat_ms: AtomicU64,
+    heartbeat_max_age: Duration,
+}
+
+impl Lifecycle {
+    pub fn new(heartbeat_max_age: Duration) -> Self {
+        Self {
+            created_at: Instant::now(),
+            startup_complete: AtomicBool::new(false),
+            draining: AtomicBool::new(false),
+            last_heartbeat_ms: AtomicU64::new(NO_HEARTBEAT),
+            heartbeat_max_age,
+        }
+    }
+
+    pub fn mark_started(&self) {
+        if !self.startup_complete.swap(true, Ordering::AcqRel) {
+            tracing::info!(
+                startup_ms = self.created_at.elapsed().as_millis() as u64,
+                "Startup complete"
+            );
+        }
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.startup_complete.load(Ordering::Acquire)
+    }
+
+    pub fn start_draining(&self) {
+        if !self.draining.swap(true, Ordering::AcqRel) {
+            tracing::info!("Readiness set to failing, draining traffic");
+        }
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    pub fn record_heartbeat(&self) {
+        self.last_heartbeat_ms.store(
+            self.created_at.elapsed().as_millis() as u64,
+            Ordering::Release,
+        );
+    }
+
+    pub fn heartbeat_age(&self) -> Option<Duration> {
+        match self.last_heartbeat_ms.load(Ordering::Acquire) {
+            NO_HEARTBEAT => None,
+            ms => Some(
+                self.created_at
+                    .elapsed()
+                    .saturating_sub(Duration::from_millis(ms)),
+            ),
+        }
+    }
+
+    pub fn heartbeat_max_age(&self) -> Duration {
+        self.heartbeat_max_age
+    }
+
+    pub fn uptime(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+}
@@ -16,23 +16,37 @@ use tower_http::compression::CompressionLayer;
 use tracing::warn;
 
 use automobile_inventory::{
-    background::BackgroundWorker,
-    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+    background::{
+        ExpiredReservationsJob, InventoryClassificationJob, InventoryMetricsJob,
+        ReorderPlanningJob, ReplenishmentDraftJob, ScheduledPriceChangesJob,
+    },
+    cache::QueryCache,
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerRegistry},
     config::{AppConfig, create_cors_layer, load_config},
     error::{AppError, ErrorExposure, set_error_exposure},
+    leader::LeaderElection,
+    lifecycle::{Lifecycle, MIGRATOR},
     middleware::request_context_middleware,
-    observability::init_tracing,
+    observability::{init_metrics, init_tracing},
     pool_manager::{DynamicPoolConfig, PoolBuilder},
     repositories::{
-        PgCarCommandRepository, PgCarQueryRepository, PgCarRepository,
-        PgInventoryAnalyticsRepository, PgReservationRepository, PgWarehouseRepository,
+        PgAgingRepository, PgCarCommandRepository, PgCarQueryRepository, PgCarRepository,
+        PgClassificationRepository, PgInventoryAnalyticsRepository, PgJobExecutionRepository,
+        PgPriceHistoryRepository, PgPricingRepository, PgPurchasingRepository,
+        PgRebalancingRepository, PgReorderRepository, PgReservationRepository, PgSalesRepository,
+        PgWarehouseRepository,
     },
+    resilience::ResilientRepository,
     routes::create_router,
+    scheduler::{HEARTBEAT_INTERVAL, Scheduler},
     services::{
-        CarService, HealthCheckServiceImpl, InventoryAnalyticsService, ReservationService,
+        AgingService, CarService, ClassificationService, HealthCheckServiceImpl,
+        InventoryAnalyticsService, JobService, PriceHistoryService, PricingService,
+        PurchasingService, RebalancingService, ReorderService, ReservationService, SaleService,
         WarehouseService,
     },
     state::AppState,
+    uow::PgUnitOfWorkFactory,
 };
 
 static ACTIVE_REQUESTS: AtomicUsize = AtomicUsize::new(0);
@@ -94,7 +108,18 @@ async fn async_main(config: AppConfig) {
 }
 
 async fn run_application(config: AppConfig) -> Result<(), AppError> {
-    init_tracing(config.environment.as_str());
+    let tracing_guard = init_tracing(
+        config.environment.as_str(),
+        &config.app.name,
+        &config.observability,
+    )?;
+
+    let metrics_handle = if config.observability.enable_metrics {
+        Some(init_metrics(&config.app.name)?)
+    } else {
+        tracing::info!("Prometheus metrics disabled");
+        None
+    };
 
     match config.environment {
         automobile_inventory::config::EnvironmentType::Development
@@ -117,28 +142,27 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
 
     let cors_layer = create_cors_layer(&config.cors)?;
 
-    let dynamic_pool_config = match config.environment {
-        automobile_inventory::config::EnvironmentType::Production => {
-            tracing::info!("Using conservative pool configuration for production");
-            DynamicPoolConfig::conservative()
-        }
-        automobile_inventory::config::EnvironmentType::Staging => {
-            tracing::info!("Using default pool configuration for staging");
-            DynamicPoolConfig::default()
-        }
-        _ => {
-            tracing::info!("Using default pool configuration for development");
-            DynamicPoolConfig::default()
-        }
-    };
+    let dynamic_pool_config = DynamicPoolConfig::from_database_config(&config.database);
+
+    tracing::info!(
+        scaling_enabled = dynamic_pool_config.scaling_enabled,
+        min_connections = dynamic_pool_config.min_connections,
+        max_connections = dynamic_pool_config.max_connections,
+        "Pool scaling policy loaded"
+    );
 
     let pool_manager = PoolBuilder::new(config.database.clone())
         .with_dynamic_config(dynamic_pool_config)
+        .with_replicas(
+            config.database.replica_urls(),
+            config.database.max_replica_lag(),
+        )
         .build()
         .await
-        .map_err(|e| AppError::DatabaseError(e))?;
+        .map_err(AppError::DatabaseError)?;
 
-    let pool = pool_manager.pool().clone();
+    let pool = pool_manager.pool();
+    let pool_handle = pool_manager.handle();
 
     tracing::info!(
         pool_size = pool.size(),
@@ -146,7 +170,7 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
         "Database pool with dynamic monitoring initialized"
     );
 
-    sqlx::migrate!("./migrations")
+    MIGRATOR
         .run(&pool)
         .await
         .map_err(AppError::MigrationError)?;
@@ -156,45 +180,201 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
         CircuitBreakerConfig::default(),
     ));
 
+    let circuit_breakers = Arc::new(CircuitBreakerRegistry::new());
+    circuit_breakers.register(Arc::clone(&db_circuit_breaker));
+
     tracing::info!(
         circuit_breaker = "database_operations",
         "Circuit breaker initialized"
     );
 
-    let car_query_repo = Arc::new(PgCarQueryRepository::new(pool.clone()));
-    let car_command_repo = Arc::new(PgCarCommandRepository::new(pool.clone()));
-    let _car_repo_facade = Arc::new(PgCarRepository::new(pool.clone()));
+    let query_cache = QueryCache::from_config(&config.cache).await?;
 
-    let reservation_repo = Arc::new(PgReservationRepository::new(pool.clone()));
-    let warehouse_repo = Arc::new(PgWarehouseRepository::new(pool.clone()));
-    let analytics_repo = Arc::new(PgInventoryAnalyticsRepository::new(pool.clone()));
+    tracing::info!(
+        cache_backend = query_cache.backend_name(),
+        "Query cache initialized"
+    );
+
+    let read_router = pool_manager.read_router();
+    let guard = |name: &str| circuit_breakers.get_or_create(name, CircuitBreakerConfig::default());
+
+    let car_query_repo = Arc::new(
+        ResilientRepository::new(
+            Arc::new(PgCarQueryRepository::with_router(read_router.clone())),
+            guard("car_queries"),
+        )
+        .with_stale_cache(query_cache.clone()),
+    );
+    let car_command_repo = Arc::new(ResilientRepository::new(
+        Arc::new(PgCarCommandRepository::new(pool_handle.clone())),
+        guard("car_commands"),
+    ));
+    let car_repo = Arc::new(PgCarRepository::new(pool_handle.clone()));
+
+    let reservation_repo = Arc::new(
+        ResilientRepository::new(
+            Arc::new(PgReservationRepository::new(pool_handle.clone())),
+            guard("reservations"),
+        )
+        .with_stale_cache(query_cache.clone()),
+    );
+    let warehouse_repo = Arc::new(
+        ResilientRepository::new(
+            Arc::new(PgWarehouseRepository::new(pool_handle.clone())),
+            guard("warehouses"),
+        )
+        .with_stale_cache(query_cache.clone()),
+    );
+    let analytics_repo = Arc::new(
+        ResilientRepository::new(
+            Arc::new(PgInventoryAnalyticsRepository::with_router(read_router)),
+            guard("inventory_analytics"),
+        )
+        .with_stale_cache(query_cache.clone()),
+    );
 
-    let car_service = CarService::new(car_query_repo, car_command_repo);
-    let reservation_service = Arc::new(ReservationService::new(reservation_repo));
+    let car_service = CarService::with_cache(car_query_repo, car_command_repo, query_cache.clone())
+        .with_depreciation(config.depreciation.clone());
+    let reservation_service = Arc::new(ReservationService::new(reservation_repo.clone()));
     let warehouse_service = Arc::new(WarehouseService::new(warehouse_repo));
-    let inventory_analytics_service = Arc::new(InventoryAnalyticsService::new(analytics_repo));
+    let inventory_analytics_service =
+        Arc::new(InventoryAnalyticsService::new(analytics_repo.clone()));
+    let reorder_service = Arc::new(ReorderService::new(Arc::new(PgReorderRepository::new(
+        pool_handle.clone(),
+    ))));
+    let purchasing_service = Arc::new(PurchasingService::new(Arc::new(
+        PgPurchasingRepository::new(pool_handle.clone()),
+    )));
+    let rebalancing_service = Arc::new(RebalancingService::new(
+        Arc::new(PgRebalancingRepository::new(pool_handle.clone())),
+        Arc::clone(&warehouse_service),
+        config.rebalancing.clone(),
+    ));
+    let classification_service = Arc::new(ClassificationService::new(
+        Arc::new(PgClassificationRepository::new(pool_handle.clone())),
+        query_cache.clone(),
+        config.classification.clone(),
+    ));
+    let aging_service = Arc::new(AgingService::new(
+        Arc::new(PgAgingRepository::new(pool_handle.clone())),
+        config.aging.clone(),
+        config.reorder_planning.holding_cost_rate,
+    ));
+    let price_history_service = Arc::new(PriceHistoryService::new(
+        Arc::new(PgPriceHistoryRepository::new(pool_handle.clone())),
+        query_cache.clone(),
+        config.price_history.clone(),
+    ));
+    let pricing_service = Arc::new(PricingService::new(
+        Arc::new(PgPricingRepository::new(pool_handle.clone())),
+        Arc::new(PgAgingRepository::new(pool_handle.clone())),
+        config.pricing.clone(),
+    ));
+    let sale_service = Arc::new(
+        SaleService::new(
+            Arc::new(PgUnitOfWorkFactory::new(pool_handle.clone())),
+            car_repo,
+            reservation_repo,
+            Arc::new(PgSalesRepository),
+            Arc::clone(&pricing_service),
+        )
+        .with_cache(query_cache.clone()),
+    );
+
+    let (bg_shutdown_tx, bg_shutdown_rx) = watch::channel(false);
+    let scheduler_config = &config.scheduler;
+    let leader_election = scheduler_config.leader_election.enabled.then(|| {
+        Arc::new(LeaderElection::new(
+            pool_handle.clone(),
+            &scheduler_config.leader_election,
+        ))
+    });
+
+    let mut scheduler_builder = Scheduler::builder(
+        Arc::new(PgJobExecutionRepository::new(pool_handle.clone())),
+        bg_shutdown_rx,
+    )
+    .register(
+        Arc::new(ExpiredReservationsJob::new(pool_handle.clone())),
+        &scheduler_config.job("expired_reservations"),
+    )?
+    .register(
+        Arc::new(InventoryMetricsJob::new(pool_handle.clone())),
+        &scheduler_config.job("inventory_metrics"),
+    )?
+    .register(
+        Arc::new(ReorderPlanningJob::new(
+            pool_handle.clone(),
+            analytics_repo,
+            query_cache,
+            config.reorder_planning.clone(),
+        )),
+        &scheduler_config.job("reorder_planning"),
+    )?
+    .register(
+        Arc::new(ReplenishmentDraftJob::new(
+            pool_handle.clone(),
+            Arc::clone(&inventory_analytics_service),
+        )),
+        &scheduler_config.job("replenishment_drafts"),
+    )?
+    .register(
+        Arc::new(InventoryClassificationJob::new(Arc::clone(
+            &classification_service,
+        ))),
+        &scheduler_config.job("inventory_classification"),
+    )?
+    .register(
+        Arc::new(ScheduledPriceChangesJob::new(Arc::clone(
+            &price_history_service,
+        ))),
+        &scheduler_config.job("scheduled_price_changes"),
+    )?
+    .schedules_enabled(scheduler_config.enabled);
+    if let Some(leader_election) = &leader_election {
+        scheduler_builder = scheduler_builder.with_leader_election(Arc::clone(leader_election));
+    }
+    let scheduler = scheduler_builder.build();
+    let job_service = Arc::new(JobService::new(scheduler.clone()));
+
+    let lifecycle = Arc::new(Lifecycle::new(HEARTBEAT_INTERVAL * 3));
 
     let app_state = AppState {
         health_check_service: Arc::new(HealthCheckServiceImpl::new(
-            pool.clone(),
+            pool_handle.clone(),
             config.database.clone(),
         )),
         car_service,
         reservation_service,
         warehouse_service,
         inventory_analytics_service,
+        job_service,
+        reorder_service,
+        purchasing_service,
+        rebalancing_service,
+        classification_service,
+        aging_service,
+        price_history_service,
+        pricing_service,
+        sale_service,
         config: config.clone(),
         start_time: std::time::Instant::now(),
         db_circuit_breaker,
+        circuit_breakers,
         pool_manager: Some(pool_manager),
+        metrics_handle,
+        lifecycle: Arc::clone(&lifecycle),
+        leader_election: leader_election.clone(),
     };
 
     let (shutdown_tx, mut shutdown_rx) = mpsc::channel(1);
-    let (bg_shutdown_tx, bg_shutdown_rx) = watch::channel(false);
     SHUTDOWN_TX.set(shutdown_tx).ok();
 
-    let bg_worker = BackgroundWorker::new(pool.clone(), 60, bg_shutdown_rx);
-    let bg_handle = tokio::spawn(bg_worker.start());
+    let bg_handle = tokio::spawn(scheduler.start(Some(Arc::clone(&lifecycle))));
+    let leader_handle = leader_election.map(|leader_election| {
+        let shutdown_rx = bg_shutdown_tx.subscribe();
+        tokio::spawn(async move { leader_election.run(shutdown_rx).await })
+    });
 
     let app = create_router(app_state).layer(
         ServiceBuilder::new()
@@ -224,11 +404,18 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
         listener,
         app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
     )
-    .with_graceful_shutdown(async {
-        shutdown_signal().await;
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    .with_graceful_shutdown({
+        let lifecycle = Arc::clone(&lifecycle);
+        let drain_delay = config.server.readiness_drain_delay();
+        async move {
+            shutdown_signal().await;
+            lifecycle.start_draining();
+            tokio::time::sleep(drain_delay).await;
+        }
     });
 
+    lifecycle.mark_started();
+
     tokio::select! {
         result = server => {
             result.map_err(|e| AppError::ConfigError(format!("Server error: {}", e)))?;
@@ -238,7 +425,9 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
         }
     }
 
-    tracing::info!("Signaling background worker to stop...");
+    lifecycle.start_draining();
+
+    tracing::info!("Signaling job scheduler to stop...");
     let _ = bg_shutdown_tx.send(true);
 
     tracing::info!("Draining active requests...");
@@ -256,12 +445,18 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
         tokio::time::sleep(std::time::Duration::from_millis(100)).await;
     }
 
-    tracing::info!("Waiting for background worker to complete...");
+    tracing::info!("Waiting for job scheduler to complete...");
     let bg_timeout = tokio::time::Duration::from_secs(5);
     match tokio::time::timeout(bg_timeout, bg_handle).await {
-        Ok(Ok(())) => tracing::info!("Background worker stopped gracefully"),
-        Ok(Err(e)) => tracing::error!("Background worker panicked: {}", e),
-        Err(_) => tracing::warn!("Background worker stop timeout, forcing shutdown"),
+        Ok(Ok(())) => tracing::info!("Job scheduler stopped gracefully"),
+        Ok(Err(e)) => tracing::error!("Job scheduler panicked: {}", e),
+        Err(_) => tracing::warn!("Job scheduler stop timeout, forcing shutdown"),
+    }
+
+    if let Some(handle) = leader_handle
+        && tokio::time::timeout(bg_timeout, handle).await.is_err()
+    {
+        tracing::warn!("Leader election stop timeout, lock released with the session");
     }
 
     tracing::info!(
@@ -270,8 +465,9 @@ async fn run_application(config: AppConfig) -> Result<(), AppError> {
     );
 
     tracing::info!("Shutting down gracefully...");
-    pool.close().await;
+    pool_handle.get().close().await;
     tracing::info!("Shutdown complete");
+    tracing_guard.shutdown();
 
     Ok(())
 }
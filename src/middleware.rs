@@ -1,5 +1,5 @@
 use axum::{
-    extract::Request,
+    extract::{MatchedPath, Request},
     http::{HeaderName, HeaderValue},
     middleware::Next,
     response::Response,
@@ -10,7 +10,9 @@ use uuid::Uuid;
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 pub const TENANT_ID_HEADER: &str = "x-tenant-id";
 pub const USER_ID_HEADER: &str = "x-user-id";
+pub const API_KEY_HEADER: &str = "x-api-key";
 pub const RESPONSE_TIME_HEADER: &str = "x-response-time-ms";
+const UNMATCHED_ROUTE: &str = "unmatched";
 
 #[derive(Debug, Clone)]
 pub struct RequestContext {
@@ -127,30 +129,51 @@ pub async fn request_context_middleware(mut request: Request, next: Next) -> Res
         );
     }
 
-    tracing::info!(
-        target: "metrics",
-        metric_type = "histogram",
-        name = "http_request_duration_ms",
-        value = %duration_ms,
-        request_id = %context.request_id,
-        method = %context.method,
-        path = %context.path,
-        status = %status.as_u16(),
-        tenant_id = ?context.tenant_id,
-    );
-
-    tracing::info!(
-        target: "metrics",
-        metric_type = "counter",
-        name = "http_requests_total",
-        value = 1,
-        status = %status.as_str(),
-        tenant_id = ?context.tenant_id,
-    );
-
     response
 }
 
 pub fn extract_context(req: &Request) -> Option<RequestContext> {
     req.extensions().get::<RequestContext>().cloned()
 }
+
+/// Holds one slot of `http_requests_in_flight`; released on drop so requests
+/// abandoned mid-flight (client disconnect, timeout) do not leak the gauge.
+struct InFlightGuard;
+
+impl InFlightGuard {
+    fn enter() -> Self {
+        metrics::gauge!("http_requests_in_flight").increment(1.0);
+        Self
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        metrics::gauge!("http_requests_in_flight").decrement(1.0);
+    }
+}
+
+pub async fn http_metrics_middleware(request: Request, next: Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+    let in_flight = InFlightGuard::enter();
+    let response = next.run(request).await;
+    drop(in_flight);
+
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    metrics::counter!("http_requests_total", &labels).increment(1);
+    metrics::histogram!("http_request_duration_seconds", &labels)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
-use bigdecimal::BigDecimal;
-use chrono::{DateTime, Utc};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, types::Uuid};
 use utoipa::ToSchema;
@@ -21,6 +21,18 @@ pub struct HealthResponse {
     pub uptime_seconds: u64,
     pub version: String,
     pub timestamp: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduler_leader: Option<LeaderStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LeaderStatus {
+    pub instance_id: String,
+    pub is_leader: bool,
+    pub leader_since: Option<DateTime<Utc>>,
+    /// Instance currently holding the scheduler lock, as seen by Postgres.
+    pub current_leader: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -210,6 +222,24 @@ pub enum EngineType {
     Petrol,
 }
 
+/// Revenue class: A cars make up the bulk of revenue, C cars the tail.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "abc_class")]
+pub enum AbcClass {
+    A,
+    B,
+    C,
+}
+
+/// Demand variability class: X sells steadily, Z sporadically.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "xyz_class")]
+pub enum XyzClass {
+    X,
+    Y,
+    Z,
+}
+
 #[derive(Debug, FromRow, Serialize, Clone, ToSchema)]
 pub struct Car {
     pub car_id: CarId,
@@ -286,9 +316,14 @@ pub struct CreateCarDto {
     /// Current availability or maintenance status
     #[schema(example = "New", min_length = 3, max_length = 20)]
     pub status: CarStatus,
+
+    /// Odometer reading; leave empty when unknown
+    #[schema(example = 12000, minimum = 0)]
+    #[validate(range(min = 0))]
+    pub mileage_km: Option<i32>,
 }
 
-#[derive(Debug, FromRow, Clone)]
+#[derive(Debug, FromRow, Clone, Serialize, Deserialize)]
 pub struct CarEntity {
     pub car_id: String,
     pub brand: String,
@@ -303,9 +338,12 @@ pub struct CarEntity {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    pub abc_class: Option<AbcClass>,
+    pub xyz_class: Option<XyzClass>,
+    pub mileage_km: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CarResponse {
     pub id: String,
     pub brand: String,
@@ -316,6 +354,10 @@ pub struct CarResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Set by the nightly `inventory_classification` job.
+    pub abc_class: Option<AbcClass>,
+    pub xyz_class: Option<XyzClass>,
+    pub mileage_km: Option<i32>,
 }
 
 impl From<CarEntity> for CarResponse {
@@ -330,6 +372,9 @@ impl From<CarEntity> for CarResponse {
             created_at: entity.created_at,
             updated_at: entity.updated_at,
             deleted_at: entity.deleted_at,
+            abc_class: entity.abc_class,
+            xyz_class: entity.xyz_class,
+            mileage_km: entity.mileage_km,
         }
     }
 }
@@ -338,6 +383,8 @@ impl From<CarEntity> for CarResponse {
 pub struct CarFilter {
     pub brand: Option<String>,
     pub status: Option<String>,
+    pub abc_class: Option<AbcClass>,
+    pub xyz_class: Option<XyzClass>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -346,6 +393,8 @@ pub struct CarSearchQuery {
     pub page_size: Option<u32>,
     pub brand: Option<String>,
     pub status: Option<String>,
+    pub abc_class: Option<AbcClass>,
+    pub xyz_class: Option<XyzClass>,
 }
 
 impl CarSearchQuery {
@@ -360,6 +409,8 @@ impl CarSearchQuery {
         CarFilter {
             brand: self.brand.clone(),
             status: self.status.clone(),
+            abc_class: self.abc_class,
+            xyz_class: self.xyz_class,
         }
     }
 }
@@ -431,7 +482,7 @@ pub struct CarSearchResult {
     pub search_highlights: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct InventoryStatusStat {
     pub status: CarStatus,
     pub total_units: i64,
@@ -439,7 +490,7 @@ pub struct InventoryStatusStat {
     pub inventory_value: BigDecimal,
 }
 
-#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct DashboardStats {
     pub status_distribution: Vec<InventoryStatusStat>,
     #[schema(value_type = String)]
@@ -456,7 +507,7 @@ pub enum ReservationStatus {
     Completed,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct Reservation {
     pub id: Uuid,
     pub car_id: CarId,
@@ -557,6 +608,9 @@ pub struct UpdateCarDto {
 
     pub status: Option<CarStatus>,
 
+    #[validate(range(min = 0))]
+    pub mileage_km: Option<i32>,
+
     pub expected_version: Option<i64>,
 }
 
@@ -571,6 +625,7 @@ pub struct CarUpdateData {
     pub price: BigDecimal,
     pub quantity_in_stock: i32,
     pub status: CarStatus,
+    pub mileage_km: Option<i32>,
 }
 
 impl UpdateCarDto {
@@ -593,6 +648,7 @@ impl UpdateCarDto {
             price: self.price.unwrap_or_else(|| current.price.clone()),
             quantity_in_stock: self.quantity_in_stock.unwrap_or(current.quantity_in_stock),
             status: self.status.unwrap_or_else(|| current.status.clone()),
+            mileage_km: self.mileage_km.or(current.mileage_km),
         }
     }
 }
@@ -652,7 +708,7 @@ impl TryFrom<String> for WarehouseId {
     }
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct Warehouse {
     pub warehouse_id: WarehouseId,
     pub name: String,
@@ -735,7 +791,7 @@ pub struct StockTransferDto {
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
 pub struct TransferOrder {
     pub transfer_id: Uuid,
     pub from_warehouse_id: WarehouseId,
@@ -756,112 +812,1349 @@ pub enum TransferStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq, sqlx::Type)]
+#[sqlx(transparent)]
+#[serde(try_from = "String")]
+pub struct SupplierId(String);
+
+impl SupplierId {
+    pub fn new(id: String) -> Result<Self, AppError> {
+        <Self as ValidatedId>::new(id)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ValidatedId for SupplierId {
+    const PREFIX: char = 'S';
+    const MIN_LENGTH: usize = 4;
+
+    fn new(id: String) -> Result<Self, AppError> {
+        Self::validate_format(&id).map_err(|e| {
+            AppError::InvalidJson(format!(
+                "Invalid SupplierId format: {} - {}",
+                id,
+                e.message.unwrap_or_default()
+            ))
+        })?;
+        Ok(Self(id))
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SupplierId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for SupplierId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct Supplier {
+    pub supplier_id: SupplierId,
+    pub name: String,
+    pub contact_email: Option<String>,
+    pub lead_time_days: i32,
+    pub min_order_qty: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateSupplierDto {
+    #[validate(length(min = 4, max = 20))]
+    pub supplier_id: String,
+
+    #[validate(length(min = 3, max = 100))]
+    pub name: String,
+
+    #[validate(email)]
+    pub contact_email: Option<String>,
+
+    #[validate(range(min = 1, max = 365))]
+    pub lead_time_days: i32,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub min_order_qty: Option<i32>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct SupplierPrice {
+    pub supplier_id: SupplierId,
+    pub car_id: CarId,
+
+    #[schema(value_type = f64, example = 21500.00)]
+    pub unit_cost: BigDecimal,
+
+    /// Overrides the supplier minimum for this car.
+    pub min_order_qty: Option<i32>,
+
+    /// Overrides the supplier lead time for this car.
+    pub lead_time_days: Option<i32>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetSupplierPriceDto {
+    #[validate(range(min = 0.0))]
+    pub unit_cost: f64,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub min_order_qty: Option<i32>,
+
+    #[validate(range(min = 1, max = 365))]
+    pub lead_time_days: Option<i32>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
-pub struct InventoryMetrics {
-    pub total_cars: i64,
-    #[schema(value_type = String, example = "1250000.00")]
-    pub total_value: BigDecimal,
-    pub total_warehouses: i64,
-    pub active_reservations: i64,
-    pub reserved_units: i64,
-    pub low_stock_items: i64,
-    pub stock_turnover_rate: f64,
+pub struct SupplierDetail {
+    #[serde(flatten)]
+    pub supplier: Supplier,
+    pub prices: Vec<SupplierPrice>,
 }
 
-#[derive(Debug, FromRow, Serialize, ToSchema)]
-pub struct SalesVelocity {
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "purchase_order_status")]
+pub enum PurchaseOrderStatus {
+    Draft,
+    Submitted,
+    PartiallyReceived,
+    Received,
+    Cancelled,
+}
+
+pub const PURCHASE_ORDER_ORIGIN_MANUAL: &str = "manual";
+pub const PURCHASE_ORDER_ORIGIN_REPLENISHMENT: &str = "replenishment";
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct PurchaseOrder {
+    pub po_id: Uuid,
+    pub supplier_id: SupplierId,
+    pub warehouse_id: WarehouseId,
+    pub status: PurchaseOrderStatus,
+    /// `manual`, or `replenishment` for drafts raised from critical stock
+    /// alerts that wait for approval.
+    pub origin: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    /// Submission time plus the longest lead time among the lines.
+    pub expected_at: Option<DateTime<Utc>>,
+    pub received_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct PurchaseOrderLine {
+    pub line_id: Uuid,
     pub car_id: CarId,
-    pub brand: String,
-    pub model: String,
-    pub avg_daily_sales: f64,
-    pub sales_volatility: f64,
-    pub last_30_days_sales: i64,
-    pub last_7_days_sales: i64,
-    pub trend_direction: String,
+    pub quantity: i32,
+    pub received_quantity: i32,
+
+    #[schema(value_type = f64, example = 21500.00)]
+    pub unit_cost: BigDecimal,
+
+    /// Why the line was drafted.
+    pub note: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, ToSchema)]
-pub enum StockTrend {
-    Increasing(f64),
-    Decreasing(f64),
-    Stable,
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PurchaseOrderResponse {
+    #[serde(flatten)]
+    pub order: PurchaseOrder,
+    pub lines: Vec<PurchaseOrderLine>,
+
+    #[schema(value_type = f64, example = 215000.00)]
+    pub total_cost: BigDecimal,
+}
+
+impl PurchaseOrderResponse {
+    pub fn new(order: PurchaseOrder, lines: Vec<PurchaseOrderLine>) -> Self {
+        let total_cost = lines
+            .iter()
+            .map(|line| &line.unit_cost * BigDecimal::from(line.quantity))
+            .sum();
+        Self {
+            order,
+            lines,
+            total_cost,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreatePurchaseOrderDto {
+    #[validate(length(min = 4, max = 20))]
+    pub supplier_id: String,
+
+    #[validate(length(min = 4, max = 20))]
+    pub warehouse_id: String,
+
+    #[validate(length(max = 1000))]
+    pub notes: Option<String>,
+
+    #[validate(length(min = 1, max = 200), nested)]
+    pub lines: Vec<PurchaseOrderLineDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct PurchaseOrderLineDto {
+    #[validate(length(min = 1))]
+    pub car_id: String,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub quantity: i32,
+
+    /// Defaults to the supplier price list.
+    #[validate(range(min = 0.0))]
+    pub unit_cost: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPurchaseOrderLine {
+    pub car_id: CarId,
+    pub quantity: i32,
+    pub unit_cost: Option<BigDecimal>,
+    pub note: Option<String>,
+}
+
+/// Preferred supplier and receiving warehouse of a car, with the units
+/// already on their way to it.
+#[derive(Debug, Clone, FromRow)]
+pub struct ReplenishmentCandidate {
+    pub car_id: CarId,
+    pub supplier_id: SupplierId,
+    pub unit_cost: BigDecimal,
+    pub min_order_qty: i32,
+    pub warehouse_id: WarehouseId,
+    /// Outstanding units on draft and open purchase orders.
+    pub on_order: i64,
+    /// Units on pending or in-transit transfers into `warehouse_id`.
+    pub in_transit: i64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PurchaseOrderListQuery {
+    pub status: Option<PurchaseOrderStatus>,
+    pub supplier_id: Option<String>,
+    /// `manual` or `replenishment`.
+    pub origin: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct GoodsReceiptDto {
+    #[validate(length(min = 1, max = 200), nested)]
+    pub lines: Vec<GoodsReceiptLineDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct GoodsReceiptLineDto {
+    #[validate(length(min = 1))]
+    pub car_id: String,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub quantity: i32,
+}
+
+/// Per-warehouse availability and demand of a car; demand is the car's
+/// recent daily sales fulfilled from that warehouse.
+#[derive(Debug, Clone, FromRow)]
+pub struct StockLocationDemand {
+    pub car_id: CarId,
+    pub warehouse_id: WarehouseId,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub available: i32,
+    pub daily_demand: f64,
+    /// Capacity left in the whole warehouse.
+    pub free_capacity: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferProposal {
+    pub car_id: CarId,
+    pub from_warehouse_id: WarehouseId,
+    pub to_warehouse_id: WarehouseId,
+    pub quantity: i32,
+    pub distance_km: Option<f64>,
+    pub destination_cover_days: f64,
+    pub destination_cover_days_after: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "rebalancing_status")]
+pub enum RebalancingStatus {
+    Proposed,
+    Accepted,
+    Rejected,
+    Superseded,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct RebalancingProposal {
+    pub proposal_id: Uuid,
+    pub car_id: CarId,
+    pub from_warehouse_id: WarehouseId,
+    pub to_warehouse_id: WarehouseId,
+    pub quantity: i32,
+    /// Great-circle distance; unknown when a warehouse has no coordinates.
+    pub distance_km: Option<f64>,
+    /// Days of demand the destination holds before and after the transfer.
+    pub destination_cover_days: f64,
+    pub destination_cover_days_after: f64,
+    pub status: RebalancingStatus,
+    pub transfer_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RebalancingProposalQuery {
+    pub status: Option<RebalancingStatus>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RebalancingAcceptance {
+    pub proposal: RebalancingProposal,
+    pub transfer: TransferOrder,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ClassificationInput {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    /// Units sold in the window valued at the current list price.
+    pub revenue: BigDecimal,
+    pub units_sold: i64,
+    /// Units sold per week, oldest first, zero-filled.
+    pub weekly_units: Vec<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
-pub struct StockAlert {
+pub struct CarClassification {
     pub car_id: CarId,
     pub brand: String,
     pub model: String,
-    pub current_stock: i32,
-    pub reserved_stock: i64,
-    pub available_stock: i32,
-    pub reorder_point: i32,
-    pub economic_order_qty: i32,
-    pub alert_level: AlertLevel,
-    pub trend: StockTrend,
-    pub avg_daily_sales: Option<f64>,
-    pub days_until_stockout: Option<i32>,
-    pub suggested_action: SuggestedAction,
+    #[schema(value_type = String)]
+    pub revenue: BigDecimal,
+    pub units_sold: i64,
+    pub revenue_share: f64,
+    /// Share of all revenue covered by this car and every car ranked above it.
+    pub cumulative_share: f64,
+    /// Coefficient of variation of weekly demand; unknown without sales.
+    pub demand_cv: Option<f64>,
+    pub abc_class: AbcClass,
+    pub xyz_class: XyzClass,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClassificationReport {
+    pub history_weeks: u32,
+    pub generated_at: DateTime<Utc>,
+    /// Ranked by revenue, highest first.
+    pub cars: Vec<CarClassification>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct AlertThreshold {
+    pub abc_class: AbcClass,
+    /// Stock at or below `reorder_point * critical_ratio` is `Critical`.
+    pub critical_ratio: f64,
+    /// Stock at or below `reorder_point * warning_ratio` is `Warning`.
+    pub warning_ratio: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetAlertThresholdDto {
+    #[validate(range(min = 0.1, max = 10.0))]
+    pub critical_ratio: f64,
+
+    #[validate(range(min = 0.1, max = 10.0))]
+    pub warning_ratio: f64,
 }
 
 #[derive(Debug, Clone, FromRow)]
-pub struct StockAlertRow {
+pub struct AgingInput {
     pub car_id: CarId,
     pub brand: String,
     pub model: String,
-    pub current_stock: i32,
-    pub reserved_stock: i64,
-    pub available_stock: i32,
-    pub reorder_point: i32,
-    pub economic_order_qty: i32,
-    pub alert_level: AlertLevel,
-    pub trend_direction: String,
-    pub trend_percentage: f64,
-    pub avg_daily_sales: Option<f64>,
-    pub days_until_stockout: Option<i32>,
-    pub suggested_action_type: String,
-    pub suggested_description: String,
-    pub suggested_priority: i32,
+    pub price: BigDecimal,
+    pub units_on_hand: i32,
+    /// Receipt date of opening stock not covered by any goods receipt.
+    pub stocked_since: DateTime<Utc>,
+    /// Goods receipts, newest first.
+    pub receipt_dates: Vec<DateTime<Utc>>,
+    pub receipt_quantities: Vec<i32>,
+    pub units_sold: i64,
 }
 
-impl From<StockAlertRow> for StockAlert {
-    fn from(row: StockAlertRow) -> Self {
-        let trend = match row.trend_direction.as_str() {
-            "UP" => StockTrend::Increasing(row.trend_percentage),
-            "DOWN" => StockTrend::Decreasing(row.trend_percentage),
-            _ => StockTrend::Stable,
-        };
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AgingBucketStock {
+    /// `0-30`, `31-60`, `61-90` or `90+` days in stock.
+    pub bucket: String,
+    pub units: i64,
+    #[schema(value_type = String)]
+    pub inventory_value: BigDecimal,
+    /// Holding cost accrued so far by the units in this bucket.
+    #[schema(value_type = String)]
+    pub holding_cost: BigDecimal,
+}
 
-        Self {
-            car_id: row.car_id,
-            brand: row.brand,
-            model: row.model,
-            current_stock: row.current_stock,
-            reserved_stock: row.reserved_stock,
-            available_stock: row.available_stock,
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CarAging {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub units_on_hand: i32,
+    pub oldest_unit_days: i64,
+    /// Unit-weighted average days in stock.
+    pub avg_days_in_stock: f64,
+    pub avg_daily_sales: f64,
+    /// Days the stock lasts at `avg_daily_sales`; unknown without sales.
+    pub days_of_supply: Option<f64>,
+    #[schema(value_type = String)]
+    pub holding_cost: BigDecimal,
+    pub buckets: Vec<AgingBucketStock>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InventoryAgingReport {
+    pub generated_at: DateTime<Utc>,
+    /// Annual holding cost as a fraction of the unit price.
+    pub holding_cost_rate: f64,
+    pub buckets: Vec<AgingBucketStock>,
+    /// Oldest stock first.
+    pub cars: Vec<CarAging>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SlowMover {
+    #[serde(flatten)]
+    pub aging: CarAging,
+    pub suggested_action: SuggestedAction,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DepreciationInput {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub year: i32,
+    pub engine_type: EngineType,
+    pub mileage_km: Option<i32>,
+    pub price: BigDecimal,
+    pub quantity_in_stock: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CarDepreciation {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub year: i32,
+    pub engine_type: EngineType,
+    pub mileage_km: Option<i32>,
+    /// Years since the start of the model year.
+    pub age_years: f64,
+    /// Curve applied for the engine type: `straight_line`, `declining_balance` or `custom`.
+    pub method: String,
+    /// Share of the list price retained at this age, before the mileage adjustment.
+    pub retained_ratio: f64,
+    /// Relative change for mileage above or below normal; zero when mileage is unknown.
+    pub mileage_adjustment: f64,
+    #[schema(value_type = String)]
+    pub list_price: BigDecimal,
+    /// Current estimated value of one unit.
+    #[schema(value_type = String)]
+    pub estimated_value: BigDecimal,
+    pub units_in_stock: i32,
+    #[schema(value_type = String)]
+    pub book_value: BigDecimal,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct WarehouseStockRow {
+    pub warehouse_id: WarehouseId,
+    pub name: String,
+    pub car_id: CarId,
+    pub units: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct ValuationTotals {
+    pub units: i64,
+    #[schema(value_type = String)]
+    pub list_value: BigDecimal,
+    #[schema(value_type = String)]
+    pub book_value: BigDecimal,
+    /// `list_value - book_value`.
+    #[schema(value_type = String)]
+    pub depreciation: BigDecimal,
+    /// Depreciation as a percentage of list value.
+    pub depreciation_pct: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WarehouseValuation {
+    pub warehouse_id: WarehouseId,
+    pub name: String,
+    #[serde(flatten)]
+    pub totals: ValuationTotals,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InventoryValuation {
+    pub generated_at: DateTime<Utc>,
+    /// Whole inventory, from `cars.quantity_in_stock`.
+    #[serde(flatten)]
+    pub totals: ValuationTotals,
+    /// Per warehouse, from `stock_locations`.
+    pub warehouses: Vec<WarehouseValuation>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
+#[sqlx(type_name = "price_change_status")]
+pub enum PriceChangeStatus {
+    Pending,
+    Applied,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct ScheduledPriceChange {
+    pub change_id: Uuid,
+    pub car_id: CarId,
+    #[schema(value_type = String)]
+    pub new_price: BigDecimal,
+    pub effective_at: DateTime<Utc>,
+    pub reason: Option<String>,
+    pub status: PriceChangeStatus,
+    pub created_at: DateTime<Utc>,
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SchedulePriceChangeDto {
+    #[schema(value_type = f64, example = 27999.99)]
+    pub new_price: BigDecimal,
+
+    /// Must be in the future; the scheduled price job applies it once due.
+    pub effective_at: DateTime<Utc>,
+
+    #[validate(length(max = 200))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PriceChangeRow {
+    pub id: Uuid,
+    pub old_price: BigDecimal,
+    pub new_price: BigDecimal,
+    pub source: String,
+    pub scheduled_change_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+    pub days_before: f64,
+    pub units_before: i64,
+    pub days_after: f64,
+    pub units_after: i64,
+}
+
+/// Units sold in the days on one side of a price change.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SalesWindow {
+    pub days: f64,
+    pub units: i64,
+    /// Unknown while the window is shorter than a day.
+    pub avg_daily_sales: Option<f64>,
+}
+
+impl SalesWindow {
+    fn new(days: f64, units: i64) -> Self {
+        Self {
+            days,
+            units,
+            avg_daily_sales: (days >= 1.0).then(|| units as f64 / days),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PriceChange {
+    pub id: Uuid,
+    #[schema(value_type = String)]
+    pub old_price: BigDecimal,
+    #[schema(value_type = String)]
+    pub new_price: BigDecimal,
+    pub price_change_pct: f64,
+    /// `manual` for car updates, `scheduled` when applied by the scheduled price job.
+    pub source: String,
+    pub scheduled_change_id: Option<Uuid>,
+    pub changed_at: DateTime<Utc>,
+    /// Bounded by the previous change.
+    pub sales_before: SalesWindow,
+    /// Bounded by the next change and the current time.
+    pub sales_after: SalesWindow,
+    /// Relative change in average daily sales over relative change in price;
+    /// unknown without sales before the change or a full day after it.
+    pub price_elasticity: Option<f64>,
+}
+
+impl From<PriceChangeRow> for PriceChange {
+    fn from(row: PriceChangeRow) -> Self {
+        let old_price = row.old_price.to_f64().unwrap_or(0.0);
+        let new_price = row.new_price.to_f64().unwrap_or(0.0);
+        let price_change = if old_price > 0.0 {
+            (new_price - old_price) / old_price
+        } else {
+            0.0
+        };
+
+        let sales_before = SalesWindow::new(row.days_before, row.units_before);
+        let sales_after = SalesWindow::new(row.days_after, row.units_after);
+        let price_elasticity = match (sales_before.avg_daily_sales, sales_after.avg_daily_sales) {
+            (Some(before), Some(after)) if before > 0.0 && price_change != 0.0 => {
+                Some((after - before) / before / price_change)
+            }
+            _ => None,
+        };
+
+        Self {
+            id: row.id,
+            old_price: row.old_price,
+            new_price: row.new_price,
+            price_change_pct: price_change * 100.0,
+            source: row.source,
+            scheduled_change_id: row.scheduled_change_id,
+            changed_at: row.changed_at,
+            sales_before,
+            sales_after,
+            price_elasticity,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CarPriceHistory {
+    pub car_id: CarId,
+    #[schema(value_type = String)]
+    pub current_price: BigDecimal,
+    /// Most recent first.
+    pub changes: Vec<PriceChange>,
+    /// Pending changes, soonest first.
+    pub scheduled: Vec<ScheduledPriceChange>,
+}
+
+/// A discount applied at checkout. Conditions left empty match every sale.
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct PricingRule {
+    pub rule_id: Uuid,
+    pub name: String,
+    /// Set for promotions, which only apply when the code is given at checkout.
+    pub promo_code: Option<String>,
+    pub brand: Option<String>,
+    pub engine_type: Option<EngineType>,
+    pub min_year: Option<i32>,
+    pub max_year: Option<i32>,
+    /// Aging bucket of the oldest unit in stock: `0-30`, `31-60`, `61-90` or `90+`.
+    pub aging_bucket: Option<String>,
+    pub min_quantity: Option<i32>,
+    pub discount_percent: Option<f64>,
+    /// Per unit.
+    #[schema(value_type = Option<String>)]
+    pub discount_amount: Option<BigDecimal>,
+    /// Lowest unit price this rule may bring the price down to.
+    #[schema(value_type = Option<String>)]
+    pub floor_price: Option<BigDecimal>,
+    /// Stackable rules combine; the others only apply on their own.
+    pub stackable: bool,
+    /// Higher priority stackable rules apply first.
+    pub priority: i32,
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreatePricingRuleDto {
+    #[validate(length(min = 3, max = 100))]
+    pub name: String,
+
+    #[validate(length(min = 3, max = 40))]
+    pub promo_code: Option<String>,
+
+    #[validate(length(min = 1, max = 50))]
+    pub brand: Option<String>,
+
+    pub engine_type: Option<EngineType>,
+
+    #[validate(range(min = 1886, max = 2100))]
+    pub min_year: Option<i32>,
+
+    #[validate(range(min = 1886, max = 2100))]
+    pub max_year: Option<i32>,
+
+    pub aging_bucket: Option<String>,
+
+    #[validate(range(min = 1))]
+    pub min_quantity: Option<i32>,
+
+    /// Exactly one of `discount_percent` and `discount_amount` is required.
+    #[validate(range(exclusive_min = 0.0, max = 100.0))]
+    pub discount_percent: Option<f64>,
+
+    #[schema(value_type = Option<f64>, example = 500.0)]
+    pub discount_amount: Option<BigDecimal>,
+
+    #[schema(value_type = Option<f64>)]
+    pub floor_price: Option<BigDecimal>,
+
+    #[serde(default = "default_stackable")]
+    pub stackable: bool,
+
+    #[serde(default)]
+    pub priority: i32,
+
+    pub starts_at: Option<DateTime<Utc>>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+fn default_stackable() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AppliedPricingRule {
+    pub rule_id: Uuid,
+    pub name: String,
+    pub promo_code: Option<String>,
+    #[schema(value_type = String)]
+    pub unit_discount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PriceQuote {
+    #[schema(value_type = String)]
+    pub list_price: BigDecimal,
+    #[schema(value_type = String)]
+    pub unit_price: BigDecimal,
+    /// In the order they were applied.
+    pub applied_rules: Vec<AppliedPricingRule>,
+    /// Whether a rule or the global floor limited the discount.
+    pub floor_applied: bool,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckoutDto {
+    #[validate(length(min = 1, max = 100))]
+    pub customer_id: String,
+
+    #[validate(length(min = 3, max = 40))]
+    pub promo_code: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SaleReceipt {
+    pub sale_id: Uuid,
+    pub reservation_id: Uuid,
+    pub car_id: CarId,
+    pub quantity: i32,
+    pub promo_code: Option<String>,
+    #[serde(flatten)]
+    pub pricing: PriceQuote,
+    #[schema(value_type = String)]
+    pub total_price: BigDecimal,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq)]
+#[sqlx(type_name = "job_status")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct JobExecution {
+    pub job_id: Uuid,
+    pub job_type: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub status: JobStatus,
+    pub items_processed: Option<i32>,
+    pub error_message: Option<String>,
+    pub triggered_by: String,
+    pub attempts: i32,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JobScheduleStatus {
+    pub name: String,
+    /// Cron expression or `every <n>s`.
+    pub schedule: String,
+    pub paused: bool,
+    pub running: usize,
+    pub max_concurrent: usize,
+    pub timeout_seconds: u64,
+    pub max_retries: u32,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub struct JobExecutionFilter {
+    pub job_type: Option<String>,
+    pub status: Option<JobStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JobExecutionQuery {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    pub job_type: Option<String>,
+    pub status: Option<JobStatus>,
+}
+
+impl JobExecutionQuery {
+    pub fn pagination(&self) -> PaginationParams {
+        PaginationParams {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+
+    pub fn filter(&self) -> JobExecutionFilter {
+        JobExecutionFilter {
+            job_type: self.job_type.clone(),
+            status: self.status,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InventoryMetrics {
+    pub total_cars: i64,
+    #[schema(value_type = String, example = "1250000.00")]
+    pub total_value: BigDecimal,
+    pub total_warehouses: i64,
+    pub active_reservations: i64,
+    pub reserved_units: i64,
+    pub low_stock_items: i64,
+    pub stock_turnover_rate: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsBucket {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl MetricsBucket {
+    const ALL: [MetricsBucket; 4] = [Self::Hour, Self::Day, Self::Week, Self::Month];
+
+    /// Field name understood by Postgres `date_trunc`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    pub fn approx_duration(&self) -> chrono::Duration {
+        match self {
+            Self::Hour => chrono::Duration::hours(1),
+            Self::Day => chrono::Duration::days(1),
+            Self::Week => chrono::Duration::weeks(1),
+            Self::Month => chrono::Duration::days(30),
+        }
+    }
+
+    pub fn bucket_count(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+        let span = (to - from).num_seconds().max(0);
+        let width = self.approx_duration().num_seconds();
+        (span + width - 1) / width + 1
+    }
+
+    /// Finest bucket that keeps the series within `max_points`.
+    pub fn fit(from: DateTime<Utc>, to: DateTime<Utc>, max_points: i64) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.bucket_count(from, to) <= max_points)
+            .unwrap_or(Self::Month)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsAggregation {
+    #[default]
+    Avg,
+    Min,
+    Max,
+    Sum,
+    /// Latest hourly snapshot in the bucket.
+    Last,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsGapFill {
+    /// Drop buckets without snapshots.
+    None,
+    /// Keep empty buckets with null values.
+    #[default]
+    Null,
+    Zero,
+    /// Carry the last known values forward.
+    Previous,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MetricsHistoryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub bucket: Option<MetricsBucket>,
+    pub aggregation: Option<MetricsAggregation>,
+    pub fill: Option<MetricsGapFill>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsHistoryRange {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket: MetricsBucket,
+    pub aggregation: MetricsAggregation,
+}
+
+#[derive(Debug, Clone, Default, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct MetricValues {
+    pub total_cars: Option<f64>,
+    pub total_value: Option<f64>,
+    pub active_reservations: Option<f64>,
+    pub reserved_units: Option<f64>,
+    pub low_stock_count: Option<f64>,
+    pub available_stock_value: Option<f64>,
+}
+
+impl MetricValues {
+    pub const NAMES: [&'static str; 6] = [
+        "total_cars",
+        "total_value",
+        "active_reservations",
+        "reserved_units",
+        "low_stock_count",
+        "available_stock_value",
+    ];
+
+    pub fn as_array(&self) -> [Option<f64>; 6] {
+        [
+            self.total_cars,
+            self.total_value,
+            self.active_reservations,
+            self.reserved_units,
+            self.low_stock_count,
+            self.available_stock_value,
+        ]
+    }
+
+    pub fn zeroed(&self) -> Self {
+        Self {
+            total_cars: self.total_cars.or(Some(0.0)),
+            total_value: self.total_value.or(Some(0.0)),
+            active_reservations: self.active_reservations.or(Some(0.0)),
+            reserved_units: self.reserved_units.or(Some(0.0)),
+            low_stock_count: self.low_stock_count.or(Some(0.0)),
+            available_stock_value: self.available_stock_value.or(Some(0.0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct MetricsHistoryPoint {
+    pub bucket_start: DateTime<Utc>,
+    /// Hourly snapshots aggregated into this bucket; 0 for filled gaps.
+    pub samples: i64,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub values: MetricValues,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MetricsPeriod {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub samples: i64,
+    pub values: MetricValues,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MetricDelta {
+    pub metric: String,
+    pub current: Option<f64>,
+    pub previous: Option<f64>,
+    pub change: Option<f64>,
+    pub change_percent: Option<f64>,
+}
+
+impl MetricDelta {
+    pub fn between(current: &MetricValues, previous: &MetricValues) -> Vec<Self> {
+        MetricValues::NAMES
+            .into_iter()
+            .zip(current.as_array().into_iter().zip(previous.as_array()))
+            .map(|(metric, (current, previous))| {
+                let change = current.zip(previous).map(|(c, p)| c - p);
+                let change_percent = change
+                    .zip(previous)
+                    .filter(|(_, p)| *p != 0.0)
+                    .map(|(delta, p)| delta / p.abs() * 100.0);
+
+                Self {
+                    metric: metric.to_string(),
+                    current,
+                    previous,
+                    change,
+                    change_percent,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MetricsHistoryResponse {
+    pub bucket: MetricsBucket,
+    pub aggregation: MetricsAggregation,
+    pub fill: MetricsGapFill,
+    pub points: Vec<MetricsHistoryPoint>,
+    pub current_period: MetricsPeriod,
+    /// Window of the same length ending where the current one starts.
+    pub previous_period: MetricsPeriod,
+    pub deltas: Vec<MetricDelta>,
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, ToSchema)]
+pub struct SalesVelocity {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub avg_daily_sales: f64,
+    pub sales_volatility: f64,
+    pub last_30_days_sales: i64,
+    pub last_7_days_sales: i64,
+    pub trend_direction: String,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct DailySalesRow {
+    pub car_id: CarId,
+    pub day: NaiveDate,
+    pub units: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastModel {
+    MovingAverage,
+    HoltWinters,
+    Croston,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    pub horizon_days: Option<u32>,
+    pub history_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastPoint {
+    pub date: NaiveDate,
+    pub forecast: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ForecastModelScore {
+    pub model: ForecastModel,
+    /// Mean absolute error in units per day on the held-out days.
+    pub backtest_mae: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DemandForecast {
+    pub car_id: CarId,
+    pub model: ForecastModel,
+    pub history_days: u32,
+    pub horizon_days: u32,
+    pub interval_level: f64,
+    pub total_forecast: f64,
+    pub points: Vec<ForecastPoint>,
+    /// Empty when the history is too short to backtest.
+    pub candidates: Vec<ForecastModelScore>,
+}
+
+pub const REORDER_SOURCE_JOB: &str = "job";
+pub const REORDER_SOURCE_OVERRIDE: &str = "override";
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ReorderPlanningInput {
+    pub car_id: CarId,
+    pub unit_price: f64,
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub safety_stock: i32,
+    pub lead_time_days: Option<i32>,
+    pub reorder_point_override: Option<i32>,
+    pub economic_order_qty_override: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReorderParameterUpdate {
+    pub car_id: CarId,
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub safety_stock: i32,
+    pub avg_daily_demand: Option<f64>,
+    pub demand_std_dev: Option<f64>,
+    pub lead_time_days: Option<i32>,
+    pub source: &'static str,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct ReorderSettings {
+    pub car_id: CarId,
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub safety_stock: i32,
+    /// Supplier lead time; the planning default applies when unset.
+    pub lead_time_days: Option<i32>,
+    pub reorder_point_override: Option<i32>,
+    pub economic_order_qty_override: Option<i32>,
+    pub reorder_params_updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+pub struct ReorderParameterChange {
+    pub id: Uuid,
+    /// `job` or `override`.
+    pub source: String,
+    pub previous_reorder_point: i32,
+    pub reorder_point: i32,
+    pub previous_economic_order_qty: i32,
+    pub economic_order_qty: i32,
+    pub previous_safety_stock: i32,
+    pub safety_stock: i32,
+    pub avg_daily_demand: Option<f64>,
+    pub demand_std_dev: Option<f64>,
+    pub lead_time_days: Option<i32>,
+    pub changed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReorderParametersResponse {
+    #[serde(flatten)]
+    pub settings: ReorderSettings,
+    pub history: Vec<ReorderParameterChange>,
+}
+
+/// Replaces the manual overrides of a car; omitted fields are cleared and
+/// left to the planning job.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReorderOverrideDto {
+    #[validate(range(min = 0, max = 100000))]
+    pub reorder_point: Option<i32>,
+
+    #[validate(range(min = 1, max = 100000))]
+    pub economic_order_qty: Option<i32>,
+
+    #[validate(range(min = 1, max = 365))]
+    pub lead_time_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum StockTrend {
+    Increasing(f64),
+    Decreasing(f64),
+    Stable,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StockAlert {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub current_stock: i32,
+    pub reserved_stock: i64,
+    pub available_stock: i32,
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub alert_level: AlertLevel,
+    /// Class whose alert thresholds produced `alert_level`.
+    pub abc_class: Option<AbcClass>,
+    pub trend: StockTrend,
+    pub avg_daily_sales: Option<f64>,
+    pub days_until_stockout: Option<i32>,
+    /// Model behind `days_until_stockout`.
+    pub forecast_model: Option<ForecastModel>,
+    pub suggested_action: SuggestedAction,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct StockAlertRow {
+    pub car_id: CarId,
+    pub brand: String,
+    pub model: String,
+    pub current_stock: i32,
+    pub reserved_stock: i64,
+    pub available_stock: i32,
+    pub reorder_point: i32,
+    pub economic_order_qty: i32,
+    pub alert_level: AlertLevel,
+    pub abc_class: Option<AbcClass>,
+    pub trend_direction: String,
+    /// Units sold in the last 7 days against the 7 days before, in percent.
+    pub trend_percentage: f64,
+    pub avg_daily_sales: Option<f64>,
+    pub days_until_stockout: Option<i32>,
+    /// Location with the most unreserved units of this car.
+    pub surplus_warehouse_id: Option<String>,
+    pub surplus_available: Option<i32>,
+    /// Locations holding this car with nothing left to sell.
+    pub empty_locations: i64,
+}
+
+/// Week-over-week demand change at which a rising trend justifies a price increase.
+const PRICE_INCREASE_TREND_PCT: f64 = 25.0;
+/// Days of supply under which rising demand will cause a stockout before a reorder lands.
+const PRICE_INCREASE_MAX_DAYS_OF_SUPPLY: i32 = 14;
+/// Week-over-week demand drop at which reordering is deferred in favour of a promotion.
+const MARKETING_PUSH_TREND_PCT: f64 = -25.0;
+
+impl SuggestedAction {
+    fn for_alert(row: &StockAlertRow) -> Self {
+        let critical = matches!(row.alert_level, AlertLevel::Critical);
+        let selling = row.avg_daily_sales.is_some_and(|sales| sales > 0.0);
+
+        if row.available_stock <= 0 && row.reserved_stock > 0 {
+            return Self {
+                action_type: ActionType::CancelPendingReservations,
+                description: format!(
+                    "{} units are held by pending reservations and none are available",
+                    row.reserved_stock
+                ),
+                priority: 1,
+            };
+        }
+
+        if let (Some(warehouse_id), Some(surplus)) =
+            (&row.surplus_warehouse_id, row.surplus_available)
+            && row.empty_locations > 0
+            && surplus > 1
+        {
+            return Self {
+                action_type: ActionType::TransferFromWarehouse,
+                description: format!(
+                    "{} location(s) are out of stock while {warehouse_id} has {surplus} available",
+                    row.empty_locations
+                ),
+                priority: if critical { 1 } else { 2 },
+            };
+        }
+
+        if row.trend_percentage >= PRICE_INCREASE_TREND_PCT
+            && let Some(days) = row.days_until_stockout
+            && days < PRICE_INCREASE_MAX_DAYS_OF_SUPPLY
+        {
+            return Self {
+                action_type: ActionType::IncreasePrice,
+                description: format!(
+                    "Demand up {:.0}% week over week with {days} days of supply left",
+                    row.trend_percentage
+                ),
+                priority: 2,
+            };
+        }
+
+        if !selling || row.trend_percentage <= MARKETING_PUSH_TREND_PCT {
+            let description = if selling {
+                format!(
+                    "Demand down {:.0}% week over week, promote remaining stock before reordering",
+                    row.trend_percentage.abs()
+                )
+            } else {
+                "No sales in the last 30 days, promote remaining stock before reordering"
+                    .to_string()
+            };
+            return Self {
+                action_type: ActionType::MarketingPush,
+                description,
+                priority: 3,
+            };
+        }
+
+        let description = match row.days_until_stockout {
+            Some(days) => format!(
+                "Order {} units, {days} days of supply left",
+                row.economic_order_qty
+            ),
+            None => format!("Order {} units", row.economic_order_qty),
+        };
+        Self {
+            action_type: ActionType::Reorder,
+            description,
+            priority: if critical { 1 } else { 2 },
+        }
+    }
+}
+
+impl From<StockAlertRow> for StockAlert {
+    fn from(row: StockAlertRow) -> Self {
+        let trend = match row.trend_direction.as_str() {
+            "UP" => StockTrend::Increasing(row.trend_percentage),
+            "DOWN" => StockTrend::Decreasing(row.trend_percentage.abs()),
+            _ => StockTrend::Stable,
+        };
+        let suggested_action = SuggestedAction::for_alert(&row);
+
+        Self {
+            car_id: row.car_id,
+            brand: row.brand,
+            model: row.model,
+            current_stock: row.current_stock,
+            reserved_stock: row.reserved_stock,
+            available_stock: row.available_stock,
             reorder_point: row.reorder_point,
             economic_order_qty: row.economic_order_qty,
             alert_level: row.alert_level,
+            abc_class: row.abc_class,
             trend,
             avg_daily_sales: row.avg_daily_sales,
             days_until_stockout: row.days_until_stockout,
-            suggested_action: SuggestedAction {
-                action_type: match row.suggested_action_type.as_str() {
-                    "Reorder" => ActionType::Reorder,
-                    "TransferFromWarehouse" => ActionType::TransferFromWarehouse,
-                    "CancelPendingReservations" => ActionType::CancelPendingReservations,
-                    "IncreasePrice" => ActionType::IncreasePrice,
-                    "MarketingPush" => ActionType::MarketingPush,
-                    _ => ActionType::Reorder,
-                },
-                description: row.suggested_description,
-                priority: row.suggested_priority,
-            },
+            forecast_model: None,
+            suggested_action,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, ToSchema, sqlx::Type)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::Type)]
 #[sqlx(type_name = "alert_level")]
 pub enum AlertLevel {
     Critical,
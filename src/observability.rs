@@ -1,3 +1,16 @@
+use std::time::Duration;
+
+use axum::http::HeaderMap;
+use metrics::Unit;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use opentelemetry::{global, trace::TracerProvider};
+use opentelemetry_http::HeaderExtractor;
+use opentelemetry_otlp::{
+    Protocol, SpanExporter, WithExportConfig, WithTonicConfig,
+    tonic_types::transport::ClientTlsConfig,
+};
+use opentelemetry_sdk::{Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
 use tracing_subscriber::{
     EnvFilter,
     fmt::{self, format::FmtSpan},
@@ -5,7 +18,30 @@ use tracing_subscriber::{
     util::SubscriberInitExt,
 };
 
-pub fn init_tracing(environment: &str) {
+use crate::config::{ObservabilityConfig, OtelProtocol};
+use crate::error::AppError;
+
+const TRACER_NAME: &str = "automobile_inventory";
+
+pub struct TracingGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl TracingGuard {
+    pub fn shutdown(self) {
+        if let Some(provider) = self.provider
+            && let Err(e) = provider.shutdown()
+        {
+            tracing::warn!(error = %e, "Failed to flush pending spans on shutdown");
+        }
+    }
+}
+
+pub fn init_tracing(
+    environment: &str,
+    service_name: &str,
+    config: &ObservabilityConfig,
+) -> Result<TracingGuard, AppError> {
     let is_production = environment == "production";
 
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
@@ -16,9 +52,20 @@ pub fn init_tracing(environment: &str) {
         }
     });
 
+    let provider = if config.enable_tracing {
+        Some(build_tracer_provider(service_name, config)?)
+    } else {
+        None
+    };
+
+    let otel_layer = provider
+        .as_ref()
+        .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME)));
+
     if is_production {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(otel_layer)
             .with(
                 fmt::layer()
                     .json()
@@ -32,6 +79,7 @@ pub fn init_tracing(environment: &str) {
     } else {
         tracing_subscriber::registry()
             .with(env_filter)
+            .with(otel_layer)
             .with(
                 fmt::layer()
                     .pretty()
@@ -43,13 +91,300 @@ pub fn init_tracing(environment: &str) {
     }
 
     tracing::info!("Tracing initialized for environment: {}", environment);
+
+    if provider.is_some() {
+        tracing::info!(
+            endpoint = %config.otel_endpoint,
+            protocol = ?config.otel_protocol,
+            "OpenTelemetry trace export enabled"
+        );
+    }
+
+    Ok(TracingGuard { provider })
+}
+
+fn build_tracer_provider(
+    service_name: &str,
+    config: &ObservabilityConfig,
+) -> Result<SdkTracerProvider, AppError> {
+    let exporter = match config.otel_protocol {
+        OtelProtocol::Grpc => {
+            let builder = SpanExporter::builder()
+                .with_tonic()
+                .with_endpoint(config.otel_endpoint.clone());
+
+            if config.otel_endpoint.starts_with("https://") {
+                builder
+                    .with_tls_config(ClientTlsConfig::new().with_webpki_roots())
+                    .build()
+            } else {
+                builder.build()
+            }
+        }
+        OtelProtocol::Http => {
+            let endpoint = config.otel_endpoint.trim_end_matches('/');
+            let endpoint = if endpoint.ends_with(OTLP_HTTP_TRACES_PATH) {
+                endpoint.to_string()
+            } else {
+                format!("{}{}", endpoint, OTLP_HTTP_TRACES_PATH)
+            };
+
+            SpanExporter::builder()
+                .with_http()
+                .with_protocol(Protocol::HttpBinary)
+                .with_endpoint(endpoint)
+                .build()
+        }
+    }
+    .map_err(|e| AppError::ConfigError(format!("Failed to create OTLP span exporter: {}", e)))?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    global::set_tracer_provider(provider.clone());
+
+    Ok(provider)
+}
+
+const OTLP_HTTP_TRACES_PATH: &str = "/v1/traces";
+
+/// Continues an incoming W3C `traceparent`. The service makes no outbound
+/// HTTP calls, so there is no matching injection side.
+pub fn set_parent_from_headers(span: &tracing::Span, headers: &HeaderMap) {
+    let parent =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    let _ = span.set_parent(parent);
 }
 
 pub fn db_span(operation: &str, table: &str) -> tracing::Span {
     tracing::info_span!(
         "db_operation",
+        otel.name = %format_args!("{} {}", operation, table),
+        otel.kind = "client",
         db.operation = operation,
         db.table = table,
         db.system = "postgresql",
     )
 }
+
+const HTTP_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+const BATCH_DURATION_MS_BUCKETS: &[f64] = &[
+    10.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0, 30000.0,
+];
+
+pub fn init_metrics(app_name: &str) -> Result<PrometheusHandle, AppError> {
+    let handle = PrometheusBuilder::new()
+        .add_global_label("service", app_name)
+        .set_buckets_for_metric(
+            Matcher::Full("http_request_duration_seconds".to_string()),
+            HTTP_DURATION_BUCKETS,
+        )
+        .and_then(|builder| {
+            builder.set_buckets_for_metric(
+                Matcher::Full("batch_processing_cycle_duration_ms".to_string()),
+                BATCH_DURATION_MS_BUCKETS,
+            )
+        })
+        .and_then(|builder| {
+            builder.set_buckets_for_metric(
+                Matcher::Full("background_job_duration_ms".to_string()),
+                BATCH_DURATION_MS_BUCKETS,
+            )
+        })
+        .and_then(|builder| builder.install_recorder())
+        .map_err(|e| AppError::ConfigError(format!("Failed to install metrics recorder: {}", e)))?;
+
+    describe_metrics();
+
+    let upkeep_handle = handle.clone();
+    tokio::spawn(async move {
+        let mut upkeep_interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            upkeep_interval.tick().await;
+            upkeep_handle.run_upkeep();
+        }
+    });
+
+    tracing::info!("Prometheus metrics recorder installed");
+
+    Ok(handle)
+}
+
+fn describe_metrics() {
+    metrics::describe_counter!(
+        "http_requests_total",
+        Unit::Count,
+        "HTTP requests by method, matched route and status"
+    );
+    metrics::describe_histogram!(
+        "http_request_duration_seconds",
+        Unit::Seconds,
+        "HTTP request latency by method, matched route and status"
+    );
+    metrics::describe_gauge!(
+        "http_requests_in_flight",
+        Unit::Count,
+        "HTTP requests currently being served"
+    );
+    metrics::describe_gauge!(
+        "circuit_breaker_state",
+        "Circuit breaker state (0 = closed, 1 = half-open, 2 = open)"
+    );
+    metrics::describe_gauge!(
+        "circuit_breaker_failure_rate",
+        "Fraction of failed calls in the circuit breaker's rolling window"
+    );
+    metrics::describe_gauge!(
+        "circuit_breaker_slow_call_rate",
+        "Fraction of calls slower than the slow-call threshold in the rolling window"
+    );
+    metrics::describe_counter!(
+        "circuit_breaker_transitions_total",
+        Unit::Count,
+        "Circuit breaker state transitions by from, to and reason"
+    );
+    metrics::describe_gauge!(
+        "inventory_stock_value",
+        "Total value of stock on hand (price * quantity)"
+    );
+    metrics::describe_gauge!(
+        "inventory_active_reservations",
+        Unit::Count,
+        "Pending reservations that have not expired"
+    );
+    metrics::describe_counter!(
+        "background_job_runs_total",
+        Unit::Count,
+        "Scheduled and manual job runs by job and final status"
+    );
+    metrics::describe_counter!(
+        "background_job_skipped_total",
+        Unit::Count,
+        "Scheduled job runs skipped because the job was paused or at its concurrency limit"
+    );
+    metrics::describe_histogram!(
+        "background_job_duration_ms",
+        Unit::Milliseconds,
+        "Job run duration including retries"
+    );
+    metrics::describe_counter!(
+        "inventory_reservations_expired_total",
+        Unit::Count,
+        "Reservations expired by the background worker"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, body::Bytes, extract::State, http::Uri, routing::post};
+    use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+    use prost::Message;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    /// Local OTLP/HTTP receiver that forwards every export request it gets.
+    async fn start_collector() -> (String, mpsc::UnboundedReceiver<(Uri, Bytes)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new()
+            .route(
+                OTLP_HTTP_TRACES_PATH,
+                post(
+                    |State(tx): State<mpsc::UnboundedSender<(Uri, Bytes)>>,
+                     uri: Uri,
+                     body: Bytes| async move {
+                        let _ = tx.send((uri, body));
+                    },
+                ),
+            )
+            .with_state(tx);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        (endpoint, rx)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn db_span_is_exported_to_otlp_http_collector() {
+        let (endpoint, mut received) = start_collector().await;
+        let config = ObservabilityConfig {
+            log_level: "info".into(),
+            enable_metrics: false,
+            enable_tracing: true,
+            otel_endpoint: endpoint,
+            otel_protocol: OtelProtocol::Http,
+        };
+
+        let provider = build_tracer_provider("inventory-test", &config).unwrap();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME)));
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = db_span("SELECT", "cars").entered();
+        });
+
+        tokio::task::spawn_blocking(move || provider.force_flush())
+            .await
+            .unwrap()
+            .unwrap();
+
+        let (uri, body) = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .expect("collector received no export")
+            .unwrap();
+        assert_eq!(uri.path(), OTLP_HTTP_TRACES_PATH);
+
+        let request = ExportTraceServiceRequest::decode(body).unwrap();
+        let resource_spans = &request.resource_spans[0];
+        let service_name = resource_spans
+            .resource
+            .as_ref()
+            .unwrap()
+            .attributes
+            .iter()
+            .find(|kv| kv.key == "service.name")
+            .and_then(|kv| kv.value.as_ref())
+            .map(|value| format!("{:?}", value.value));
+        assert!(service_name.unwrap().contains("inventory-test"));
+
+        let span = &resource_spans.scope_spans[0].spans[0];
+        assert_eq!(span.name, "SELECT cars");
+        assert!(span.attributes.iter().any(|kv| kv.key == "db.table"));
+    }
+
+    #[test]
+    fn set_parent_from_headers_continues_incoming_trace() {
+        use opentelemetry::trace::TraceContextExt;
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer(TRACER_NAME)));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let trace_id = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("inbound");
+            set_parent_from_headers(&span, &headers);
+            span.context().span().span_context().trace_id().to_string()
+        });
+
+        assert_eq!(trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+    }
+}
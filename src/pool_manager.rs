@@ -1,11 +1,20 @@
+use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
-use secrecy::ExposeSecret;
-use sqlx::postgres::PgPool;
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use secrecy::{ExposeSecret, SecretString};
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
 use tokio::sync::RwLock;
 use tokio::time::interval;
 
+use crate::config::{DatabaseConfig, PoolScalingConfig};
+
+const MAX_RECORDED_SCALE_EVENTS: usize = 20;
+const POOL_DRAIN_GRACE: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone, Default)]
 pub struct PoolMetrics {
     pub size: u32,
@@ -13,11 +22,206 @@ pub struct PoolMetrics {
     pub active: u32,
     pub min_connections: u32,
     pub max_connections: u32,
+    pub capacity: u32,
     pub wait_time_ms: u128,
     pub usage_percent: f64,
     pub health_check_failures: u64,
     pub total_acquires: u64,
     pub slow_acquires: u64,
+    pub scale_ups: u64,
+    pub scale_downs: u64,
+    pub scale_events: Vec<ScaleEvent>,
+    pub replicas: Vec<ReplicaMetrics>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleDirection {
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for ScaleDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaleDirection::Up => write!(f, "up"),
+            ScaleDirection::Down => write!(f, "down"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScaleEvent {
+    pub timestamp: DateTime<Utc>,
+    pub direction: ScaleDirection,
+    pub from_size: u32,
+    pub to_size: u32,
+    pub usage_percent: f64,
+}
+
+#[derive(Clone)]
+pub struct PoolHandle {
+    current: Arc<ArcSwap<PgPool>>,
+}
+
+impl PoolHandle {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            current: Arc::new(ArcSwap::from_pointee(pool)),
+        }
+    }
+
+    pub fn get(&self) -> PgPool {
+        self.current.load().as_ref().clone()
+    }
+
+    fn swap(&self, pool: PgPool) -> Arc<PgPool> {
+        self.current.swap(Arc::new(pool))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicaMetrics {
+    pub name: String,
+    pub size: u32,
+    pub idle: u32,
+    pub active: u32,
+    pub healthy: bool,
+    pub replication_lag_ms: u64,
+    pub routed_queries: u64,
+    pub health_check_failures: u64,
+}
+
+const REPLICATION_LAG_QUERY: &str = r#"
+    SELECT CASE
+        WHEN NOT pg_is_in_recovery() THEN 0
+        WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+        ELSE COALESCE(EXTRACT(EPOCH FROM (NOW() - pg_last_xact_replay_timestamp())) * 1000, 0)
+    END::BIGINT AS lag_ms
+"#;
+
+pub struct ReplicaPool {
+    name: String,
+    pool: PgPool,
+    healthy: AtomicBool,
+    lag_ms: AtomicU64,
+    routed_queries: AtomicU64,
+    health_check_failures: AtomicU64,
+}
+
+impl ReplicaPool {
+    fn new(name: String, pool: PgPool) -> Self {
+        Self {
+            name,
+            pool,
+            healthy: AtomicBool::new(false),
+            lag_ms: AtomicU64::new(0),
+            routed_queries: AtomicU64::new(0),
+            health_check_failures: AtomicU64::new(0),
+        }
+    }
+
+    fn is_routable(&self, max_lag_ms: u64) -> bool {
+        self.healthy.load(Ordering::Relaxed) && self.lag_ms.load(Ordering::Relaxed) <= max_lag_ms
+    }
+
+    async fn check_lag(&self) {
+        let probe = tokio::time::timeout(
+            Duration::from_secs(3),
+            sqlx::query_scalar::<_, i64>(REPLICATION_LAG_QUERY).fetch_one(&self.pool),
+        )
+        .await;
+
+        match probe {
+            Ok(Ok(lag_ms)) => {
+                let lag_ms = lag_ms.max(0) as u64;
+                self.lag_ms.store(lag_ms, Ordering::Relaxed);
+                self.healthy.store(true, Ordering::Relaxed);
+            }
+            Ok(Err(e)) => {
+                tracing::warn!(replica = %self.name, error = %e, "Replica lag probe failed");
+                self.mark_unhealthy();
+            }
+            Err(_) => {
+                tracing::warn!(replica = %self.name, "Replica lag probe timed out");
+                self.mark_unhealthy();
+            }
+        }
+
+        metrics::gauge!("db_replica_lag_ms", "replica" => self.name.clone())
+            .set(self.lag_ms.load(Ordering::Relaxed) as f64);
+        metrics::gauge!("db_replica_healthy", "replica" => self.name.clone()).set(
+            if self.healthy.load(Ordering::Relaxed) {
+                1.0
+            } else {
+                0.0
+            },
+        );
+    }
+
+    fn mark_unhealthy(&self) {
+        self.healthy.store(false, Ordering::Relaxed);
+        self.health_check_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn metrics(&self) -> ReplicaMetrics {
+        let size = self.pool.size();
+        let idle = self.pool.num_idle() as u32;
+
+        ReplicaMetrics {
+            name: self.name.clone(),
+            size,
+            idle,
+            active: size.saturating_sub(idle),
+            healthy: self.healthy.load(Ordering::Relaxed),
+            replication_lag_ms: self.lag_ms.load(Ordering::Relaxed),
+            routed_queries: self.routed_queries.load(Ordering::Relaxed),
+            health_check_failures: self.health_check_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReadPoolRouter {
+    primary: PoolHandle,
+    replicas: Arc<[Arc<ReplicaPool>]>,
+    max_replica_lag_ms: u64,
+    next_replica: Arc<AtomicUsize>,
+}
+
+impl ReadPoolRouter {
+    pub fn primary_only(primary: PoolHandle) -> Self {
+        Self {
+            primary,
+            replicas: Arc::new([]),
+            max_replica_lag_ms: 0,
+            next_replica: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn primary(&self) -> PgPool {
+        self.primary.get()
+    }
+
+    /// Pool for list and report queries that tolerate replica lag. Point
+    /// lookups and reads feeding a write go through `primary()`.
+    pub fn reader(&self) -> PgPool {
+        if self.replicas.is_empty() {
+            return self.primary.get();
+        }
+
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..self.replicas.len() {
+            let replica = &self.replicas[(start + offset) % self.replicas.len()];
+            if replica.is_routable(self.max_replica_lag_ms) {
+                replica.routed_queries.fetch_add(1, Ordering::Relaxed);
+                return replica.pool.clone();
+            }
+        }
+
+        metrics::counter!("db_replica_fallback_total").increment(1);
+        tracing::debug!("No replica within lag budget, routing read to primary");
+        self.primary.get()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +234,8 @@ pub struct DynamicPoolConfig {
     pub check_interval_secs: u64,
     pub health_check_interval_secs: u64,
     pub slow_acquire_threshold_ms: u128,
+    pub scaling_enabled: bool,
+    pub scale_cooldown_secs: u64,
 }
 
 impl Default for DynamicPoolConfig {
@@ -43,11 +249,30 @@ impl Default for DynamicPoolConfig {
             check_interval_secs: 30,
             health_check_interval_secs: 10,
             slow_acquire_threshold_ms: 100,
+            scaling_enabled: true,
+            scale_cooldown_secs: 60,
         }
     }
 }
 
 impl DynamicPoolConfig {
+    pub fn from_database_config(config: &DatabaseConfig) -> Self {
+        let scaling: &PoolScalingConfig = &config.pool_scaling;
+
+        Self {
+            min_connections: scaling.min_connections.unwrap_or(config.min_connections),
+            max_connections: scaling.max_connections.unwrap_or(config.max_connections),
+            target_utilization: scaling.target_utilization,
+            scale_up_threshold: scaling.scale_up_threshold,
+            scale_down_threshold: scaling.scale_down_threshold,
+            check_interval_secs: scaling.check_interval_secs,
+            health_check_interval_secs: scaling.health_check_interval_secs,
+            slow_acquire_threshold_ms: scaling.slow_acquire_threshold_ms as u128,
+            scaling_enabled: scaling.enabled,
+            scale_cooldown_secs: scaling.cooldown_secs,
+        }
+    }
+
     pub fn conservative() -> Self {
         Self {
             min_connections: 5,
@@ -58,6 +283,8 @@ impl DynamicPoolConfig {
             check_interval_secs: 60,
             health_check_interval_secs: 15,
             slow_acquire_threshold_ms: 200,
+            scaling_enabled: true,
+            scale_cooldown_secs: 120,
         }
     }
 
@@ -71,21 +298,33 @@ impl DynamicPoolConfig {
             check_interval_secs: 15,
             health_check_interval_secs: 5,
             slow_acquire_threshold_ms: 50,
+            scaling_enabled: true,
+            scale_cooldown_secs: 30,
         }
     }
 }
 
 pub struct PoolManager {
-    pool: PgPool,
+    pool: PoolHandle,
+    router: ReadPoolRouter,
     config: DynamicPoolConfig,
+    base_config: DatabaseConfig,
+    connect_options: PgConnectOptions,
+    capacity: AtomicU32,
     metrics: Arc<RwLock<PoolMetrics>>,
     last_scale: Arc<RwLock<Instant>>,
     scale_cooldown: Duration,
 }
 
 impl PoolManager {
-    pub fn new(pool: PgPool, config: DynamicPoolConfig) -> Self {
-        let size = pool.size() as u32;
+    pub fn new(
+        pool: PgPool,
+        capacity: u32,
+        config: DynamicPoolConfig,
+        base_config: DatabaseConfig,
+        connect_options: PgConnectOptions,
+    ) -> Self {
+        let size = pool.size();
         let idle = pool.num_idle() as u32;
 
         let initial_metrics = PoolMetrics {
@@ -94,18 +333,35 @@ impl PoolManager {
             active: size.saturating_sub(idle),
             min_connections: config.min_connections,
             max_connections: config.max_connections,
+            capacity,
             ..Default::default()
         };
 
+        let pool = PoolHandle::new(pool);
+
         Self {
+            router: ReadPoolRouter::primary_only(pool.clone()),
             pool,
+            scale_cooldown: Duration::from_secs(config.scale_cooldown_secs),
             config,
+            base_config,
+            connect_options,
+            capacity: AtomicU32::new(capacity),
             metrics: Arc::new(RwLock::new(initial_metrics)),
             last_scale: Arc::new(RwLock::new(Instant::now())),
-            scale_cooldown: Duration::from_secs(60),
         }
     }
 
+    pub fn with_replicas(mut self, replicas: Vec<ReplicaPool>, max_replica_lag: Duration) -> Self {
+        self.router = ReadPoolRouter {
+            primary: self.pool.clone(),
+            replicas: replicas.into_iter().map(Arc::new).collect(),
+            max_replica_lag_ms: max_replica_lag.as_millis() as u64,
+            next_replica: Arc::new(AtomicUsize::new(0)),
+        };
+        self
+    }
+
     pub fn start_monitoring(self: Arc<Self>) {
         let metrics_clone = Arc::clone(&self);
         let config_clone = self.config.clone();
@@ -141,6 +397,8 @@ impl PoolManager {
                     let mut metrics = health_clone.metrics.write().await;
                     metrics.health_check_failures += 1;
                 }
+
+                health_clone.check_replicas().await;
             }
         });
     }
@@ -148,8 +406,8 @@ impl PoolManager {
     async fn collect_metrics(&self) -> Result<(), sqlx::Error> {
         let start = Instant::now();
 
-        let acquire_result =
-            tokio::time::timeout(Duration::from_secs(5), self.pool.acquire()).await;
+        let pool = self.pool.get();
+        let acquire_result = tokio::time::timeout(Duration::from_secs(5), pool.acquire()).await;
 
         let wait_time = start.elapsed();
 
@@ -185,15 +443,17 @@ impl PoolManager {
             }
         }
 
-        let size = self.pool.size() as u32;
-        let idle = self.pool.num_idle() as u32;
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        let capacity = self.capacity.load(Ordering::Relaxed);
 
         metrics.size = size;
         metrics.idle = idle;
         metrics.active = size.saturating_sub(idle);
+        metrics.capacity = capacity;
 
-        if self.config.max_connections > 0 {
-            metrics.usage_percent = metrics.active as f64 / self.config.max_connections as f64;
+        if capacity > 0 {
+            metrics.usage_percent = metrics.active as f64 / capacity as f64;
         }
 
         metrics::gauge!("db_pool_size").set(metrics.size as f64);
@@ -201,48 +461,106 @@ impl PoolManager {
         metrics::gauge!("db_pool_idle").set(metrics.idle as f64);
         metrics::gauge!("db_pool_usage_percent").set(metrics.usage_percent);
         metrics::gauge!("db_pool_wait_time_ms").set(metrics.wait_time_ms as f64);
+        metrics::gauge!("db_pool_max_connections").set(self.config.max_connections as f64);
+        metrics::gauge!("db_pool_capacity").set(capacity as f64);
+        metrics::gauge!("db_pool_health_check_failures").set(metrics.health_check_failures as f64);
+        metrics::gauge!("db_pool_slow_acquires").set(metrics.slow_acquires as f64);
 
         Ok(())
     }
 
     async fn evaluate_scaling(&self) {
-        let metrics = self.metrics.read().await;
-        let usage = metrics.usage_percent;
+        if !self.config.scaling_enabled {
+            return;
+        }
 
         let last_scale = *self.last_scale.read().await;
         if last_scale.elapsed() < self.scale_cooldown {
             return;
         }
 
-        drop(metrics);
+        let (usage, active) = {
+            let metrics = self.metrics.read().await;
+            (metrics.usage_percent, metrics.active)
+        };
 
-        if usage > self.config.scale_up_threshold {
-            tracing::warn!(
-                usage = format!("{:.2}%", usage * 100.0),
-                threshold = format!("{:.2}%", self.config.scale_up_threshold * 100.0),
-                "Pool utilization above scale-up threshold"
-            );
+        let capacity = self.capacity.load(Ordering::Relaxed);
+        let desired = ((active as f64 / self.config.target_utilization).ceil() as u32)
+            .clamp(self.config.min_connections, self.config.max_connections);
+
+        let (direction, target) =
+            if usage > self.config.scale_up_threshold && capacity < self.config.max_connections {
+                (ScaleDirection::Up, desired.max(capacity + 1))
+            } else if usage < self.config.scale_down_threshold && desired < capacity {
+                (ScaleDirection::Down, desired)
+            } else {
+                return;
+            };
+
+        tracing::info!(
+            usage = format!("{:.2}%", usage * 100.0),
+            from_size = capacity,
+            to_size = target,
+            direction = %direction,
+            "Resizing connection pool"
+        );
+
+        if let Err(e) = self.resize(target, direction, usage).await {
+            tracing::error!(error = %e, to_size = target, "Pool resize failed, keeping current pool");
+        }
+    }
+
+    async fn resize(
+        &self,
+        target: u32,
+        direction: ScaleDirection,
+        usage: f64,
+    ) -> Result<(), sqlx::Error> {
+        let new_pool = pool_options(&self.base_config, &self.config, target)
+            .connect_with(self.connect_options.clone())
+            .await?;
 
-            let mut last_scale = self.last_scale.write().await;
-            *last_scale = Instant::now();
+        let from_size = self.capacity.swap(target, Ordering::Relaxed);
+        let old_pool = self.pool.swap(new_pool);
+        *self.last_scale.write().await = Instant::now();
 
-            metrics::counter!("db_pool_scale_up_recommended").increment(1);
-        } else if usage < self.config.scale_down_threshold
-            && (self.pool.size() as u32) > self.config.min_connections
         {
+            let mut metrics = self.metrics.write().await;
+            metrics.capacity = target;
+            match direction {
+                ScaleDirection::Up => metrics.scale_ups += 1,
+                ScaleDirection::Down => metrics.scale_downs += 1,
+            }
+            if metrics.scale_events.len() == MAX_RECORDED_SCALE_EVENTS {
+                metrics.scale_events.remove(0);
+            }
+            metrics.scale_events.push(ScaleEvent {
+                timestamp: Utc::now(),
+                direction,
+                from_size,
+                to_size: target,
+                usage_percent: usage,
+            });
+        }
+
+        metrics::counter!("db_pool_scale_events_total", "direction" => direction.to_string())
+            .increment(1);
+
+        tokio::spawn(async move {
+            tokio::time::sleep(POOL_DRAIN_GRACE).await;
+            old_pool.close().await;
             tracing::info!(
-                usage = format!("{:.2}%", usage * 100.0),
-                current_size = self.pool.size(),
-                "Pool utilization low - could scale down"
+                size = from_size,
+                "Previous connection pool drained and closed"
             );
+        });
 
-            metrics::counter!("db_pool_scale_down_recommended").increment(1);
-        }
+        Ok(())
     }
 
     async fn health_check(&self) -> Result<(), sqlx::Error> {
-        let acquire_result =
-            tokio::time::timeout(Duration::from_secs(3), self.pool.acquire()).await;
+        let pool = self.pool.get();
+        let acquire_result = tokio::time::timeout(Duration::from_secs(3), pool.acquire()).await;
 
         let mut conn = match acquire_result {
             Ok(conn) => conn?,
@@ -256,8 +574,29 @@ impl PoolManager {
         Ok(())
     }
 
+    async fn check_replicas(&self) {
+        for replica in self.router.replicas.iter() {
+            replica.check_lag().await;
+        }
+    }
+
+    pub async fn wait_time_ms(&self) -> u128 {
+        self.metrics.read().await.wait_time_ms
+    }
+
     pub async fn metrics(&self) -> PoolMetrics {
-        self.metrics.read().await.clone()
+        let mut metrics = self.metrics.read().await.clone();
+        metrics.replicas = self
+            .router
+            .replicas
+            .iter()
+            .map(|replica| replica.metrics())
+            .collect();
+        metrics
+    }
+
+    pub fn max_replica_lag_ms(&self) -> u64 {
+        self.router.max_replica_lag_ms
     }
 
     pub async fn acquire_with_timeout(
@@ -274,26 +613,38 @@ impl PoolManager {
 
         drop(metrics);
 
-        tokio::time::timeout(adaptive_timeout, self.pool.acquire())
+        tokio::time::timeout(adaptive_timeout, self.pool.get().acquire())
             .await
             .map_err(|_| sqlx::Error::PoolTimedOut)?
     }
 
-    pub fn pool(&self) -> &PgPool {
-        &self.pool
+    pub fn pool(&self) -> PgPool {
+        self.pool.get()
+    }
+
+    pub fn handle(&self) -> PoolHandle {
+        self.pool.clone()
+    }
+
+    pub fn read_router(&self) -> ReadPoolRouter {
+        self.router.clone()
     }
 }
 
 pub struct PoolBuilder {
-    base_config: crate::config::DatabaseConfig,
+    base_config: DatabaseConfig,
     dynamic_config: DynamicPoolConfig,
+    replica_urls: Vec<SecretString>,
+    max_replica_lag: Duration,
 }
 
 impl PoolBuilder {
-    pub fn new(base_config: crate::config::DatabaseConfig) -> Self {
+    pub fn new(base_config: DatabaseConfig) -> Self {
         Self {
             base_config,
             dynamic_config: DynamicPoolConfig::default(),
+            replica_urls: Vec::new(),
+            max_replica_lag: Duration::from_secs(5),
         }
     }
 
@@ -302,25 +653,82 @@ impl PoolBuilder {
         self
     }
 
+    pub fn with_replicas(mut self, urls: Vec<SecretString>, max_lag: Duration) -> Self {
+        self.replica_urls = urls;
+        self.max_replica_lag = max_lag;
+        self
+    }
+
     pub async fn build(self) -> Result<Arc<PoolManager>, sqlx::Error> {
-        let pool = sqlx::postgres::PgPoolOptions::new()
-            .max_connections(self.base_config.max_connections)
-            .min_connections(
-                self.base_config
-                    .min_connections
-                    .max(self.dynamic_config.min_connections),
+        let connect_options = PgConnectOptions::from_str(self.base_config.url.expose_secret())?;
+        let capacity = if self.dynamic_config.scaling_enabled {
+            self.base_config.max_connections.clamp(
+                self.dynamic_config.min_connections,
+                self.dynamic_config.max_connections,
             )
-            .acquire_timeout(self.base_config.acquire_timeout())
-            .max_lifetime(self.base_config.max_lifetime())
-            .idle_timeout(self.base_config.idle_timeout())
-            .test_before_acquire(true)
-            .connect(self.base_config.url.expose_secret())
+        } else {
+            self.base_config.max_connections
+        };
+
+        let pool = pool_options(&self.base_config, &self.dynamic_config, capacity)
+            .connect_with(connect_options.clone())
             .await?;
 
-        let manager = Arc::new(PoolManager::new(pool, self.dynamic_config));
+        let mut replicas = Vec::with_capacity(self.replica_urls.len());
+        for (index, url) in self.replica_urls.iter().enumerate() {
+            let options = PgConnectOptions::from_str(url.expose_secret())?;
+            let name = format!(
+                "replica-{}-{}:{}",
+                index,
+                options.get_host(),
+                options.get_port()
+            );
+
+            let replica_pool = PgPoolOptions::new()
+                .max_connections(self.base_config.max_connections)
+                .acquire_timeout(self.base_config.acquire_timeout())
+                .max_lifetime(self.base_config.max_lifetime())
+                .idle_timeout(self.base_config.idle_timeout())
+                .test_before_acquire(true)
+                .connect_lazy_with(options);
+
+            tracing::info!(replica = %name, "Read replica pool configured");
+            replicas.push(ReplicaPool::new(name, replica_pool));
+        }
 
+        let manager = Arc::new(
+            PoolManager::new(
+                pool,
+                capacity,
+                self.dynamic_config,
+                self.base_config,
+                connect_options,
+            )
+            .with_replicas(replicas, self.max_replica_lag),
+        );
+
+        manager.check_replicas().await;
         Arc::clone(&manager).start_monitoring();
 
         Ok(manager)
     }
 }
+
+fn pool_options(
+    base_config: &DatabaseConfig,
+    dynamic_config: &DynamicPoolConfig,
+    max_connections: u32,
+) -> PgPoolOptions {
+    PgPoolOptions::new()
+        .max_connections(max_connections)
+        .min_connections(
+            base_config
+                .min_connections
+                .max(dynamic_config.min_connections)
+                .min(max_connections),
+        )
+        .acquire_timeout(base_config.acquire_timeout())
+        .max_lifetime(base_config.max_lifetime())
+        .idle_timeout(base_config.idle_timeout())
+        .test_before_acquire(true)
+}
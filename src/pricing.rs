@@ -0,0 +1,355 @@
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
+use chrono::{DateTime, Utc};
+
+use crate::models::{AppliedPricingRule, EngineType, PriceQuote, PricingRule};
+
+/// What pricing rule conditions are matched against.
+#[derive(Debug)]
+pub struct SaleContext<'a> {
+    pub brand: &'a str,
+    pub engine_type: &'a EngineType,
+    pub year: i32,
+    /// Aging bucket of the oldest unit; `None` when no rule asks for it.
+    pub aging_bucket: Option<&'a str>,
+    pub quantity: i32,
+    /// Upper-cased promo code given at checkout.
+    pub promo_code: Option<&'a str>,
+    pub now: DateTime<Utc>,
+}
+
+/// Whether every condition set on the rule holds for the sale.
+pub fn rule_applies(rule: &PricingRule, ctx: &SaleContext<'_>) -> bool {
+    rule.is_active
+        && rule.starts_at.is_none_or(|starts_at| starts_at <= ctx.now)
+        && rule.ends_at.is_none_or(|ends_at| ctx.now < ends_at)
+        && rule
+            .promo_code
+            .as_deref()
+            .is_none_or(|code| ctx.promo_code == Some(code))
+        && rule
+            .brand
+            .as_deref()
+            .is_none_or(|brand| brand.eq_ignore_ascii_case(ctx.brand))
+        && rule
+            .engine_type
+            .as_ref()
+            .is_none_or(|engine_type| engine_type == ctx.engine_type)
+        && rule.min_year.is_none_or(|min_year| ctx.year >= min_year)
+        && rule.max_year.is_none_or(|max_year| ctx.year <= max_year)
+        && rule
+            .aging_bucket
+            .as_deref()
+            .is_none_or(|bucket| ctx.aging_bucket == Some(bucket))
+        && rule
+            .min_quantity
+            .is_none_or(|min_quantity| ctx.quantity >= min_quantity)
+}
+
+/// Whether the promo code given at checkout unlocks a rule for this sale.
+pub fn promo_code_applies(rules: &[PricingRule], ctx: &SaleContext<'_>) -> bool {
+    ctx.promo_code.is_some_and(|code| {
+        rules
+            .iter()
+            .any(|rule| rule.promo_code.as_deref() == Some(code) && rule_applies(rule, ctx))
+    })
+}
+
+/// Unit price after the rules that apply to the sale.
+///
+/// Stackable rules apply one after another by descending priority, each
+/// percentage taken off the price left by the ones before. A non-stackable
+/// rule applies alone, and wins when it beats the whole stack. No rule may
+/// take the price below its own `floor_price` or `min_price_ratio` of the
+/// list price.
+pub fn quote(
+    list_price: &BigDecimal,
+    rules: &[PricingRule],
+    ctx: &SaleContext<'_>,
+    min_price_ratio: f64,
+) -> PriceQuote {
+    let global_floor =
+        (list_price * exact_decimal(min_price_ratio)).with_scale_round(2, RoundingMode::Ceiling);
+    let applicable: Vec<&PricingRule> = rules
+        .iter()
+        .filter(|rule| rule_applies(rule, ctx))
+        .collect();
+
+    let mut stack: Vec<&PricingRule> = applicable
+        .iter()
+        .copied()
+        .filter(|rule| rule.stackable)
+        .collect();
+    stack.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.created_at.cmp(&b.created_at))
+    });
+
+    let mut best = apply_rules(list_price, &stack, &global_floor);
+    for rule in applicable.into_iter().filter(|rule| !rule.stackable) {
+        let alone = apply_rules(list_price, &[rule], &global_floor);
+        if alone.unit_price < best.unit_price {
+            best = alone;
+        }
+    }
+    best
+}
+
+/// The decimal an operator typed, e.g. `12.3`, rather than the nearest
+/// binary fraction `BigDecimal::from_f64` would expand it to.
+fn exact_decimal(value: f64) -> BigDecimal {
+    value
+        .to_string()
+        .parse()
+        .unwrap_or_else(|_| BigDecimal::zero())
+}
+
+fn apply_rules(
+    list_price: &BigDecimal,
+    rules: &[&PricingRule],
+    global_floor: &BigDecimal,
+) -> PriceQuote {
+    let mut price = list_price.clone();
+    let mut applied_rules = Vec::new();
+    let mut floor_applied = false;
+
+    for rule in rules {
+        let discount = match (rule.discount_percent, &rule.discount_amount) {
+            (Some(percent), _) => &price * exact_decimal(percent) / BigDecimal::from(100),
+            (None, Some(amount)) => amount.clone(),
+            (None, None) => BigDecimal::zero(),
+        };
+        let floor = match &rule.floor_price {
+            Some(floor_price) if floor_price > global_floor => floor_price,
+            _ => global_floor,
+        };
+
+        let mut discounted = (&price - discount).round(2);
+        if &discounted < floor {
+            floor_applied = true;
+            discounted = floor.min(&price).clone();
+        }
+
+        let unit_discount = (&price - &discounted).with_scale(2);
+        if unit_discount > BigDecimal::zero() {
+            applied_rules.push(AppliedPricingRule {
+                rule_id: rule.rule_id,
+                name: rule.name.clone(),
+                promo_code: rule.promo_code.clone(),
+                unit_discount,
+            });
+            price = discounted;
+        }
+    }
+
+    PriceQuote {
+        list_price: list_price.clone(),
+        unit_price: price,
+        applied_rules,
+        floor_applied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn dec(value: &str) -> BigDecimal {
+        value.parse().unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    fn rule(name: &str, priority: i32) -> PricingRule {
+        PricingRule {
+            rule_id: Uuid::new_v4(),
+            name: name.to_string(),
+            promo_code: None,
+            brand: None,
+            engine_type: None,
+            min_year: None,
+            max_year: None,
+            aging_bucket: None,
+            min_quantity: None,
+            discount_percent: None,
+            discount_amount: None,
+            floor_price: None,
+            stackable: true,
+            priority,
+            starts_at: None,
+            ends_at: None,
+            is_active: true,
+            created_at: now() - Duration::days(30),
+        }
+    }
+
+    fn percent_off(name: &str, priority: i32, percent: f64) -> PricingRule {
+        PricingRule {
+            discount_percent: Some(percent),
+            ..rule(name, priority)
+        }
+    }
+
+    fn sale<'a>(engine_type: &'a EngineType, promo_code: Option<&'a str>) -> SaleContext<'a> {
+        SaleContext {
+            brand: "Toyota",
+            engine_type,
+            year: 2024,
+            aging_bucket: None,
+            quantity: 1,
+            promo_code,
+            now: now(),
+        }
+    }
+
+    fn applied_names(quote: &PriceQuote) -> Vec<&str> {
+        quote
+            .applied_rules
+            .iter()
+            .map(|applied| applied.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn stack_applies_by_descending_priority() {
+        let rules = vec![
+            percent_off("loyalty", 1, 10.0),
+            PricingRule {
+                discount_amount: Some(dec("20.00")),
+                ..rule("clearance", 5)
+            },
+        ];
+        let quote = quote(
+            &dec("100.00"),
+            &rules,
+            &sale(&EngineType::Hybrid, None),
+            0.0,
+        );
+
+        // 20 off first, then 10% of the 80 left; the other order would give 70.
+        assert_eq!(quote.unit_price, dec("72.00"));
+        assert_eq!(applied_names(&quote), ["clearance", "loyalty"]);
+        assert_eq!(quote.applied_rules[1].unit_discount, dec("8.00"));
+        assert!(!quote.floor_applied);
+    }
+
+    #[test]
+    fn non_stackable_rule_wins_when_it_beats_the_stack() {
+        let mut rules = vec![
+            percent_off("loyalty", 2, 10.0),
+            percent_off("season", 1, 5.0),
+            PricingRule {
+                stackable: false,
+                ..percent_off("fleet", 0, 20.0)
+            },
+            PricingRule {
+                stackable: false,
+                ..percent_off("trade-in", 9, 12.0)
+            },
+        ];
+        let engine_type = EngineType::Gasoline;
+        let ctx = sale(&engine_type, None);
+
+        let best = quote(&dec("100.00"), &rules, &ctx, 0.0);
+        assert_eq!(best.unit_price, dec("80.00"));
+        assert_eq!(applied_names(&best), ["fleet"]);
+
+        // Without it the stack (100 -> 90 -> 85.50) beats the 12% rule.
+        rules.retain(|rule| rule.name != "fleet");
+        let best = quote(&dec("100.00"), &rules, &ctx, 0.0);
+        assert_eq!(best.unit_price, dec("85.50"));
+        assert_eq!(applied_names(&best), ["loyalty", "season"]);
+    }
+
+    #[test]
+    fn higher_of_floor_price_and_ratio_floor_holds() {
+        let engine_type = EngineType::Electric;
+        let ctx = sale(&engine_type, None);
+        let half_off = |floor_price: &str| PricingRule {
+            floor_price: Some(dec(floor_price)),
+            ..percent_off("half off", 0, 50.0)
+        };
+
+        let above_ratio = quote(&dec("100.00"), &[half_off("75.00")], &ctx, 0.7);
+        assert_eq!(above_ratio.unit_price, dec("75.00"));
+        assert!(above_ratio.floor_applied);
+
+        let below_ratio = quote(&dec("100.00"), &[half_off("60.00")], &ctx, 0.7);
+        assert_eq!(below_ratio.unit_price, dec("70.00"));
+        assert!(below_ratio.floor_applied);
+        assert_eq!(below_ratio.applied_rules[0].unit_discount, dec("30.00"));
+    }
+
+    #[test]
+    fn rules_outside_their_window_do_not_apply() {
+        let expired = PricingRule {
+            ends_at: Some(now()),
+            ..percent_off("expired", 0, 10.0)
+        };
+        let upcoming = PricingRule {
+            starts_at: Some(now() + Duration::hours(1)),
+            ..percent_off("upcoming", 0, 10.0)
+        };
+        let engine_type = EngineType::Diesel;
+        let ctx = sale(&engine_type, None);
+
+        assert!(!rule_applies(&expired, &ctx));
+        assert!(!rule_applies(&upcoming, &ctx));
+        let quote = quote(&dec("100.00"), &[expired, upcoming], &ctx, 0.0);
+        assert_eq!(quote.unit_price, dec("100.00"));
+        assert!(quote.applied_rules.is_empty());
+    }
+
+    #[test]
+    fn promo_code_does_not_apply_when_the_rule_does_not() {
+        let rules = vec![PricingRule {
+            promo_code: Some("SPRING".to_string()),
+            brand: Some("Ford".to_string()),
+            ..percent_off("spring", 0, 15.0)
+        }];
+        let engine_type = EngineType::Petrol;
+
+        let wrong_car = sale(&engine_type, Some("SPRING"));
+        assert!(!promo_code_applies(&rules, &wrong_car));
+        assert_eq!(
+            quote(&dec("100.00"), &rules, &wrong_car, 0.0).unit_price,
+            dec("100.00")
+        );
+
+        let ford = SaleContext {
+            brand: "ford",
+            ..sale(&engine_type, Some("WINTER"))
+        };
+        assert!(!promo_code_applies(&rules, &ford));
+        assert!(!promo_code_applies(
+            &rules,
+            &SaleContext {
+                promo_code: None,
+                ..ford
+            }
+        ));
+        assert!(promo_code_applies(
+            &rules,
+            &SaleContext {
+                promo_code: Some("SPRING"),
+                ..ford
+            }
+        ));
+    }
+
+    #[test]
+    fn percentages_convert_exactly() {
+        assert_eq!(exact_decimal(12.3), dec("12.3"));
+        assert_eq!(exact_decimal(0.7), dec("0.7"));
+
+        let rules = [percent_off("odd", 0, 12.5)];
+        let quote = quote(&dec("19.99"), &rules, &sale(&EngineType::Hybrid, None), 0.0);
+        // 19.99 * 0.125 = 2.49875, leaving 17.49125.
+        assert_eq!(quote.unit_price, dec("17.49"));
+    }
+}
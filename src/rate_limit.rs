@@ -0,0 +1,302 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::bulkhead::RouteGroup;
+use crate::config::{PrincipalKind, RateLimitPolicy};
+use crate::error::AppError;
+use crate::middleware::{API_KEY_HEADER, TENANT_ID_HEADER, USER_ID_HEADER};
+
+const SHARDS: usize = 16;
+const MAX_KEYS_PER_SHARD: usize = 10_000;
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Picks the rate-limit bucket for a request. Principal headers are
+/// client-controlled, so they only count when they can be trusted: an API key
+/// must be one of the configured keys, while user and tenant headers are only
+/// honoured when set by a trusted (authenticating) proxy. Everything else is
+/// keyed by client IP.
+pub struct PrincipalResolver {
+    precedence: Vec<PrincipalKind>,
+    trusted_proxies: Vec<IpAddr>,
+    api_keys: HashSet<String>,
+}
+
+impl PrincipalResolver {
+    pub fn new(
+        precedence: Vec<PrincipalKind>,
+        trusted_proxies: Vec<IpAddr>,
+        api_keys: HashSet<String>,
+    ) -> Self {
+        Self {
+            precedence,
+            trusted_proxies,
+            api_keys,
+        }
+    }
+
+    /// Returns the principal label and the bucket key for a request.
+    pub fn resolve(&self, request: &Request) -> (&'static str, String) {
+        let headers = request.headers();
+        let peer = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|info| info.0.ip());
+        let via_trusted_proxy = peer.is_some_and(|ip| self.trusted_proxies.contains(&ip));
+
+        for kind in &self.precedence {
+            let (label, header) = match kind {
+                PrincipalKind::ApiKey => ("api_key", API_KEY_HEADER),
+                PrincipalKind::User => ("user", USER_ID_HEADER),
+                PrincipalKind::Tenant => ("tenant", TENANT_ID_HEADER),
+            };
+
+            if let Some(value) = headers
+                .get(header)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+            {
+                let trusted = match kind {
+                    PrincipalKind::ApiKey => via_trusted_proxy || self.api_keys.contains(value),
+                    PrincipalKind::User | PrincipalKind::Tenant => via_trusted_proxy,
+                };
+                if trusted {
+                    return (label, format!("{}:{}", label, value));
+                }
+            }
+        }
+
+        match peer {
+            Some(ip) => ("ip", format!("ip:{}", self.client_ip(ip, headers))),
+            None => ("ip", "ip:unknown".to_string()),
+        }
+    }
+
+    /// Walks `X-Forwarded-For` from the nearest hop and returns the first
+    /// address not belonging to a trusted proxy.
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.trusted_proxies.contains(&peer) {
+            return peer;
+        }
+
+        headers
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .filter_map(|hop| hop.trim().parse::<IpAddr>().ok())
+            .find(|ip| !self.trusted_proxies.contains(ip))
+            .unwrap_or(peer)
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+enum Decision {
+    Allowed { remaining: u32, reset_secs: u64 },
+    Limited { retry_after_secs: u64 },
+}
+
+pub struct RateLimiter {
+    group: RouteGroup,
+    policy: RateLimitPolicy,
+    resolver: Arc<PrincipalResolver>,
+    shards: Vec<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(
+        group: RouteGroup,
+        policy: RateLimitPolicy,
+        resolver: Arc<PrincipalResolver>,
+    ) -> Self {
+        Self {
+            group,
+            policy,
+            resolver,
+            shards: (0..SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+        }
+    }
+
+    fn check(&self, key: &str) -> Decision {
+        let rate = self.policy.requests_per_second;
+        let capacity = self.policy.burst as f64;
+        let now = Instant::now();
+
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let mut buckets = self.shards[hasher.finish() as usize % SHARDS]
+            .lock()
+            .expect("rate limiter shard poisoned");
+
+        if buckets.len() >= MAX_KEYS_PER_SHARD && !buckets.contains_key(key) {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(TokenBucket {
+            tokens: capacity,
+            updated: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Decision::Allowed {
+                remaining: bucket.tokens.floor() as u32,
+                reset_secs: ((capacity - bucket.tokens) / rate).ceil() as u64,
+            }
+        } else {
+            Decision::Limited {
+                retry_after_secs: ((1.0 - bucket.tokens) / rate).ceil().max(1.0) as u64,
+            }
+        }
+    }
+
+    fn insert_headers(&self, headers: &mut HeaderMap, remaining: u32, reset_secs: u64) {
+        let window_secs = (self.policy.burst as f64 / self.policy.requests_per_second).ceil();
+
+        headers.insert("RateLimit-Limit", HeaderValue::from(self.policy.burst));
+        headers.insert("RateLimit-Remaining", HeaderValue::from(remaining));
+        headers.insert("RateLimit-Reset", HeaderValue::from(reset_secs));
+        if let Ok(policy) =
+            HeaderValue::from_str(&format!("{};w={}", self.policy.burst, window_secs as u64))
+        {
+            headers.insert("RateLimit-Policy", policy);
+        }
+    }
+}
+
+pub async fn rate_limit_middleware(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (principal, key) = limiter.resolver.resolve(&request);
+
+    match limiter.check(&key) {
+        Decision::Allowed {
+            remaining,
+            reset_secs,
+        } => {
+            let mut response = next.run(request).await;
+            limiter.insert_headers(response.headers_mut(), remaining, reset_secs);
+            response
+        }
+        Decision::Limited { retry_after_secs } => {
+            tracing::warn!(
+                group = limiter.group.as_str(),
+                principal = principal,
+                retry_after_secs = retry_after_secs,
+                "Rate limit exceeded"
+            );
+            metrics::counter!(
+                "rate_limit_rejected_total",
+                "group" => limiter.group.as_str(),
+                "principal" => principal
+            )
+            .increment(1);
+
+            let mut response = AppError::RateLimited {
+                retry_after: retry_after_secs,
+            }
+            .into_response();
+            limiter.insert_headers(response.headers_mut(), 0, retry_after_secs);
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+
+    use super::*;
+
+    const PROXY: [u8; 4] = [10, 0, 0, 1];
+
+    fn resolver() -> PrincipalResolver {
+        PrincipalResolver::new(
+            vec![
+                PrincipalKind::ApiKey,
+                PrincipalKind::User,
+                PrincipalKind::Tenant,
+            ],
+            vec![IpAddr::from(PROXY)],
+            HashSet::from(["key-123".to_string()]),
+        )
+    }
+
+    fn request(peer: [u8; 4], headers: &[(&str, &str)]) -> Request {
+        let mut builder = Request::builder().uri("/api/v1/cars");
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 4000))));
+        request
+    }
+
+    #[test]
+    fn unknown_api_key_from_direct_client_is_keyed_by_ip() {
+        let resolved =
+            resolver().resolve(&request([203, 0, 113, 7], &[(API_KEY_HEADER, "made-up")]));
+        assert_eq!(resolved, ("ip", "ip:203.0.113.7".to_string()));
+    }
+
+    #[test]
+    fn configured_api_key_is_its_own_bucket() {
+        let resolved =
+            resolver().resolve(&request([203, 0, 113, 7], &[(API_KEY_HEADER, "key-123")]));
+        assert_eq!(resolved, ("api_key", "api_key:key-123".to_string()));
+    }
+
+    #[test]
+    fn user_and_tenant_headers_from_direct_client_are_ignored() {
+        let resolved = resolver().resolve(&request(
+            [203, 0, 113, 7],
+            &[(USER_ID_HEADER, "alice"), (TENANT_ID_HEADER, "acme")],
+        ));
+        assert_eq!(resolved, ("ip", "ip:203.0.113.7".to_string()));
+    }
+
+    #[test]
+    fn trusted_proxy_may_assert_user_principal() {
+        let resolved = resolver().resolve(&request(
+            PROXY,
+            &[(USER_ID_HEADER, "alice"), (TENANT_ID_HEADER, "acme")],
+        ));
+        assert_eq!(resolved, ("user", "user:alice".to_string()));
+    }
+
+    #[test]
+    fn trusted_proxy_without_principal_falls_back_to_forwarded_client_ip() {
+        let resolved = resolver().resolve(&request(
+            PROXY,
+            &[(FORWARDED_FOR_HEADER, "198.51.100.2, 10.0.0.1")],
+        ));
+        assert_eq!(resolved, ("ip", "ip:198.51.100.2".to_string()));
+    }
+}
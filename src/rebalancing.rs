@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use crate::config::RebalancingConfig;
+use crate::models::{StockLocationDemand, TransferProposal};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+struct Slot<'a> {
+    location: &'a StockLocationDemand,
+    available: i64,
+    target: i64,
+}
+
+impl Slot<'_> {
+    fn cover_days(&self) -> f64 {
+        self.available.max(0) as f64 / self.location.daily_demand
+    }
+
+    fn surplus(&self) -> i64 {
+        self.available - self.target
+    }
+}
+
+/// Proposes transfers that bring every location up to `target_cover_days`
+/// of its demand. The lowest-cover deficits are filled first, each from the
+/// nearest locations with surplus; sources keep their own target and
+/// destinations never exceed their free capacity.
+pub fn propose(
+    locations: &[StockLocationDemand],
+    config: &RebalancingConfig,
+) -> Vec<TransferProposal> {
+    let mut free_capacity: HashMap<&str, i64> = locations
+        .iter()
+        .map(|l| (l.warehouse_id.as_str(), l.free_capacity))
+        .collect();
+
+    let mut slots: Vec<Slot> = locations
+        .iter()
+        .map(|location| Slot {
+            location,
+            available: location.available as i64,
+            target: (location.daily_demand * config.target_cover_days as f64).ceil() as i64,
+        })
+        .collect();
+
+    let mut by_car: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, slot) in slots.iter().enumerate() {
+        by_car
+            .entry(slot.location.car_id.as_str())
+            .or_default()
+            .push(index);
+    }
+
+    let mut deficits: Vec<usize> = (0..slots.len())
+        .filter(|&i| slots[i].location.daily_demand > 0.0 && slots[i].available < slots[i].target)
+        .collect();
+    deficits.sort_by(|&a, &b| {
+        slots[a]
+            .cover_days()
+            .total_cmp(&slots[b].cover_days())
+            .then_with(|| {
+                slots[a]
+                    .location
+                    .car_id
+                    .as_str()
+                    .cmp(slots[b].location.car_id.as_str())
+            })
+    });
+
+    let mut proposals = Vec::new();
+    for destination in deficits {
+        let cover_before = slots[destination].cover_days();
+        let car_id = slots[destination].location.car_id.as_str();
+
+        let mut sources: Vec<(usize, Option<f64>)> = by_car[car_id]
+            .iter()
+            .filter(|&&i| i != destination && slots[i].surplus() > 0)
+            .map(|&i| {
+                (
+                    i,
+                    distance_km(slots[i].location, slots[destination].location),
+                )
+            })
+            .collect();
+        sources.sort_by(|a, b| {
+            a.1.unwrap_or(f64::INFINITY)
+                .total_cmp(&b.1.unwrap_or(f64::INFINITY))
+        });
+
+        let to = slots[destination].location.warehouse_id.as_str();
+        for (source, distance) in sources {
+            let need = slots[destination].target - slots[destination].available;
+            let room = free_capacity.get(to).copied().unwrap_or(0);
+            let quantity = need.min(slots[source].surplus()).min(room);
+            if quantity < config.min_transfer_quantity as i64 {
+                continue;
+            }
+
+            slots[source].available -= quantity;
+            slots[destination].available += quantity;
+            *free_capacity.entry(to).or_default() -= quantity;
+            *free_capacity
+                .entry(slots[source].location.warehouse_id.as_str())
+                .or_default() += quantity;
+
+            proposals.push(TransferProposal {
+                car_id: slots[destination].location.car_id.clone(),
+                from_warehouse_id: slots[source].location.warehouse_id.clone(),
+                to_warehouse_id: slots[destination].location.warehouse_id.clone(),
+                quantity: quantity as i32,
+                distance_km: distance,
+                destination_cover_days: cover_before,
+                destination_cover_days_after: slots[destination].cover_days(),
+            });
+
+            if slots[destination].available >= slots[destination].target {
+                break;
+            }
+        }
+    }
+
+    proposals
+}
+
+/// Haversine distance between two warehouses, if both have coordinates.
+fn distance_km(a: &StockLocationDemand, b: &StockLocationDemand) -> Option<f64> {
+    let (lat1, lon1) = (a.latitude?.to_radians(), a.longitude?.to_radians());
+    let (lat2, lon2) = (b.latitude?.to_radians(), b.longitude?.to_radians());
+
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    Some(2.0 * EARTH_RADIUS_KM * h.sqrt().asin())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{CarId, WarehouseId};
+
+    use super::*;
+
+    const MADRID: (f64, f64) = (40.4168, -3.7038);
+    const BARCELONA: (f64, f64) = (41.3874, 2.1686);
+    const BERLIN: (f64, f64) = (52.52, 13.405);
+
+    fn config() -> RebalancingConfig {
+        RebalancingConfig {
+            demand_days: 30,
+            target_cover_days: 10,
+            min_transfer_quantity: 2,
+        }
+    }
+
+    fn location(
+        car_id: &str,
+        warehouse_id: &str,
+        coordinates: Option<(f64, f64)>,
+        available: i32,
+        daily_demand: f64,
+    ) -> StockLocationDemand {
+        StockLocationDemand {
+            car_id: CarId::new(car_id.to_string()).unwrap(),
+            warehouse_id: WarehouseId::new(warehouse_id.to_string()).unwrap(),
+            latitude: coordinates.map(|(lat, _)| lat),
+            longitude: coordinates.map(|(_, lon)| lon),
+            available,
+            daily_demand,
+            free_capacity: 1_000,
+        }
+    }
+
+    fn moves(proposals: &[TransferProposal]) -> Vec<(&str, &str, &str, i32)> {
+        proposals
+            .iter()
+            .map(|p| {
+                (
+                    p.car_id.as_str(),
+                    p.from_warehouse_id.as_str(),
+                    p.to_warehouse_id.as_str(),
+                    p.quantity,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lowest_cover_destination_is_filled_first() {
+        let locations = [
+            // Target 10, so 20 units of surplus.
+            location("C0001", "W0001", Some(MADRID), 30, 1.0),
+            // 2 and 1.6 days of cover.
+            location("C0001", "W0002", Some(MADRID), 4, 2.0),
+            location("C0001", "W0003", Some(MADRID), 4, 2.5),
+        ];
+
+        let proposals = propose(&locations, &config());
+
+        // W0003 needs 21 and takes all 20 spare units; W0002 gets nothing.
+        assert_eq!(moves(&proposals), [("C0001", "W0001", "W0003", 20)]);
+        assert!((proposals[0].destination_cover_days - 1.6).abs() < 1e-9);
+        assert!((proposals[0].destination_cover_days_after - 9.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nearest_source_is_used_first() {
+        let locations = [
+            location("C0001", "W0001", Some(MADRID), 0, 1.0),
+            location("C0001", "W0002", None, 20, 0.0),
+            location("C0001", "W0003", Some(BERLIN), 3, 0.0),
+            location("C0001", "W0004", Some(BARCELONA), 4, 0.0),
+        ];
+
+        let proposals = propose(&locations, &config());
+
+        // Sources without coordinates come last.
+        assert_eq!(
+            moves(&proposals),
+            [
+                ("C0001", "W0004", "W0001", 4),
+                ("C0001", "W0003", "W0001", 3),
+                ("C0001", "W0002", "W0001", 3),
+            ]
+        );
+        let barcelona = proposals[0].distance_km.unwrap();
+        let berlin = proposals[1].distance_km.unwrap();
+        assert!((barcelona - 505.0).abs() < 10.0, "{barcelona}");
+        assert!((berlin - 1_870.0).abs() < 20.0, "{berlin}");
+    }
+
+    #[test]
+    fn destination_capacity_caps_transfers_across_cars() {
+        let locations = [
+            location("C0001", "W0001", Some(MADRID), 50, 0.0),
+            location("C0002", "W0001", Some(MADRID), 50, 0.0),
+            StockLocationDemand {
+                free_capacity: 12,
+                ..location("C0001", "W0002", Some(BARCELONA), 0, 1.0)
+            },
+            StockLocationDemand {
+                free_capacity: 12,
+                ..location("C0002", "W0002", Some(BARCELONA), 1, 1.0)
+            },
+        ];
+
+        let proposals = propose(&locations, &config());
+
+        // The warehouse has room for 12 units in total, whichever car they are.
+        assert_eq!(
+            moves(&proposals),
+            [
+                ("C0001", "W0001", "W0002", 10),
+                ("C0002", "W0001", "W0002", 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn transfers_below_the_minimum_are_dropped() {
+        let locations = [
+            location("C0001", "W0001", Some(MADRID), 11, 1.0),
+            location("C0001", "W0002", Some(BARCELONA), 9, 1.0),
+        ];
+
+        // One unit of surplus and one of need.
+        assert!(propose(&locations, &config()).is_empty());
+
+        let config = RebalancingConfig {
+            min_transfer_quantity: 1,
+            ..config()
+        };
+        assert_eq!(
+            moves(&propose(&locations, &config)),
+            [("C0001", "W0001", "W0002", 1)]
+        );
+    }
+}
@@ -1,17 +1,30 @@
 use async_trait::async_trait;
-use bigdecimal::{BigDecimal, FromPrimitive};
-use sqlx::{PgPool, QueryBuilder, Result as SqlxResult};
-use tracing::error;
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use chrono::{DateTime, Utc};
+use sqlx::{QueryBuilder, Result as SqlxResult};
+use tracing::{Instrument, error};
 use uuid::Uuid;
 
-use crate::error::{ReservationError, TransferError};
+use crate::error::{PurchaseOrderError, ReservationError, TransferError};
+use crate::fulfilment;
 use crate::models::{
-    CarEntity, CarFilter, CarId, CarSearchRequest, CarUpdateData, CreateCarDto,
-    CreateReservationDto, InventoryMetrics, InventoryStatusStat, PaginationParams, Reservation,
-    SalesVelocity, StockAlertRow, StockLocation, TransferOrder, TransferStatus, Warehouse,
-    WarehouseId,
+    AbcClass, AgingInput, AlertThreshold, CarClassification, CarEntity, CarFilter, CarId,
+    CarSearchRequest, CarUpdateData, ClassificationInput, CreateCarDto, CreatePricingRuleDto,
+    CreateReservationDto, DailySalesRow, DepreciationInput, InventoryMetrics, InventoryStatusStat,
+    JobExecution, JobExecutionFilter, JobStatus, MetricValues, MetricsAggregation,
+    MetricsHistoryPoint, MetricsHistoryRange, NewPurchaseOrderLine,
+    PURCHASE_ORDER_ORIGIN_REPLENISHMENT, PaginationParams, PriceChangeRow, PriceChangeStatus,
+    PriceQuote, PricingRule, PurchaseOrder, PurchaseOrderLine, PurchaseOrderResponse,
+    PurchaseOrderStatus, REORDER_SOURCE_OVERRIDE, RebalancingProposal, RebalancingStatus,
+    ReorderOverrideDto, ReorderParameterChange, ReorderParameterUpdate, ReorderPlanningInput,
+    ReorderSettings, ReplenishmentCandidate, Reservation, SalesVelocity, SchedulePriceChangeDto,
+    ScheduledPriceChange, SetSupplierPriceDto, StockAlertRow, StockLocation, StockLocationDemand,
+    Supplier, SupplierId, SupplierPrice, TransferOrder, TransferProposal, TransferStatus,
+    Warehouse, WarehouseId, WarehouseStockRow,
 };
 
+use crate::observability::db_span;
+use crate::pool_manager::{PoolHandle, ReadPoolRouter};
 use crate::uow::UnitOfWork;
 
 #[async_trait]
@@ -37,7 +50,8 @@ pub trait CarQueryRepository: Send + Sync {
     ) -> SqlxResult<(Vec<(CarEntity, f32)>, i64)>;
 
     async fn get_inventory_stats(&self) -> SqlxResult<Vec<InventoryStatusStat>>;
-    async fn get_depreciation_report(&self) -> SqlxResult<Vec<CarEntity>>;
+    async fn get_depreciation_report(&self) -> SqlxResult<Vec<DepreciationInput>>;
+    async fn get_warehouse_stock(&self) -> SqlxResult<Vec<WarehouseStockRow>>;
     async fn get_low_stock_report(&self, threshold: i32) -> SqlxResult<Vec<CarEntity>>;
 }
 
@@ -76,12 +90,16 @@ pub trait CarRepository: CarQueryRepository + CarCommandRepository {}
 impl<T> CarRepository for T where T: CarQueryRepository + CarCommandRepository {}
 
 pub struct PgCarQueryRepository {
-    pool: PgPool,
+    router: ReadPoolRouter,
 }
 
 impl PgCarQueryRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PoolHandle) -> Self {
+        Self::with_router(ReadPoolRouter::primary_only(pool))
+    }
+
+    pub fn with_router(router: ReadPoolRouter) -> Self {
+        Self { router }
     }
 }
 
@@ -103,14 +121,20 @@ impl CarQueryRepository for PgCarQueryRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             FROM cars
             WHERE car_id = $1
                 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        // Point reads follow writes and feed version checks, so they must not
+        // see a lagging replica.
+        .fetch_optional(&self.router.primary())
+        .instrument(db_span("SELECT", "cars"))
         .await
         .map_err(|e| {
             error!("Error fetching car by id: {:?}", e);
@@ -138,7 +162,10 @@ impl CarQueryRepository for PgCarQueryRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             FROM cars
             WHERE car_id = $1
                 AND deleted_at IS NULL
@@ -146,6 +173,7 @@ impl CarQueryRepository for PgCarQueryRepository {
         )
         .bind(&id)
         .fetch_optional(uow.connection())
+        .instrument(db_span("SELECT", "cars"))
         .await
     }
 
@@ -172,6 +200,9 @@ impl CarQueryRepository for PgCarQueryRepository {
                 created_at,
                 updated_at,
                 deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km,
                 COUNT(*) OVER() AS total_count
             FROM cars
             WHERE deleted_at IS NULL
@@ -188,6 +219,16 @@ impl CarQueryRepository for PgCarQueryRepository {
             builder.push_bind(status);
         }
 
+        if let Some(abc_class) = filter.abc_class {
+            builder.push(" AND abc_class = ");
+            builder.push_bind(abc_class);
+        }
+
+        if let Some(xyz_class) = filter.xyz_class {
+            builder.push(" AND xyz_class = ");
+            builder.push_bind(xyz_class);
+        }
+
         builder.push(" ORDER BY car_id ASC LIMIT ");
         builder.push_bind(limit);
         builder.push(" OFFSET ");
@@ -202,7 +243,8 @@ impl CarQueryRepository for PgCarQueryRepository {
 
         let rows = builder
             .build_query_as::<CarRow>()
-            .fetch_all(&self.pool)
+            .fetch_all(&self.router.reader())
+            .instrument(db_span("SELECT", "cars"))
             .await?;
 
         let total = rows.first().map(|r| r.total_count).unwrap_or(0);
@@ -234,6 +276,9 @@ impl CarQueryRepository for PgCarQueryRepository {
                 created_at,
                 updated_at,
                 deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km,
                 ts_rank_cd(search_vector, query, 32) AS rank,
                 COUNT(*) OVER() AS total_count
             FROM cars,
@@ -301,7 +346,8 @@ impl CarQueryRepository for PgCarQueryRepository {
 
         let rows = builder
             .build_query_as::<SearchRow>()
-            .fetch_all(&self.pool)
+            .fetch_all(&self.router.reader())
+            .instrument(db_span("SELECT", "cars"))
             .await?;
 
         let total = rows.first().map(|r| r.total_count).unwrap_or(0);
@@ -322,35 +368,49 @@ impl CarQueryRepository for PgCarQueryRepository {
             GROUP BY status
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "cars"))
         .await
     }
 
-    async fn get_depreciation_report(&self) -> SqlxResult<Vec<CarEntity>> {
-        sqlx::query_as::<_, CarEntity>(
+    async fn get_depreciation_report(&self) -> SqlxResult<Vec<DepreciationInput>> {
+        sqlx::query_as::<_, DepreciationInput>(
             r#"
             SELECT
                 car_id,
                 brand,
                 model,
                 year,
-                color,
                 engine_type,
-                transmission,
+                mileage_km,
                 price,
-                quantity_in_stock,
-                status,
-                created_at,
-                updated_at,
-                deleted_at
+                quantity_in_stock
             FROM cars
-            WHERE year < EXTRACT(YEAR FROM CURRENT_DATE) - 5
-                AND status = 'Available'
-                AND deleted_at IS NULL
-            ORDER BY year ASC
+            WHERE deleted_at IS NULL
+            ORDER BY year ASC, car_id ASC
+            "#,
+        )
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "cars"))
+        .await
+    }
+
+    async fn get_warehouse_stock(&self) -> SqlxResult<Vec<WarehouseStockRow>> {
+        sqlx::query_as::<_, WarehouseStockRow>(
+            r#"
+            SELECT
+                w.warehouse_id,
+                w.name,
+                sl.car_id,
+                sl.quantity::BIGINT AS units
+            FROM stock_locations sl
+            JOIN warehouses w ON w.warehouse_id = sl.warehouse_id
+            WHERE sl.quantity > 0
+            ORDER BY w.warehouse_id, sl.car_id
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "stock_locations"))
         .await
     }
 
@@ -370,7 +430,10 @@ impl CarQueryRepository for PgCarQueryRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             FROM cars
             WHERE quantity_in_stock < $1
                 AND deleted_at IS NULL
@@ -378,17 +441,18 @@ impl CarQueryRepository for PgCarQueryRepository {
             "#,
         )
         .bind(threshold)
-        .fetch_all(&self.pool)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "cars"))
         .await
     }
 }
 
 pub struct PgCarCommandRepository {
-    pool: PgPool,
+    pool: PoolHandle,
 }
 
 impl PgCarCommandRepository {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self { pool }
     }
 }
@@ -408,9 +472,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                 transmission,
                 price,
                 quantity_in_stock,
-                status
+                status,
+                mileage_km
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING
                 car_id,
                 brand,
@@ -424,7 +489,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             "#,
         )
         .bind(dto.car_id)
@@ -437,7 +505,9 @@ impl CarCommandRepository for PgCarCommandRepository {
         .bind(dto.price)
         .bind(dto.quantity_in_stock)
         .bind(dto.status)
-        .fetch_one(&self.pool)
+        .bind(dto.mileage_km)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "cars"))
         .await
         .map_err(|e| {
             error!("Error creating car: {:?}", e);
@@ -462,9 +532,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                 transmission,
                 price,
                 quantity_in_stock,
-                status
+                status,
+                mileage_km
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             RETURNING
                 car_id,
                 brand,
@@ -478,7 +549,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             "#,
         )
         .bind(&dto.car_id)
@@ -491,7 +565,9 @@ impl CarCommandRepository for PgCarCommandRepository {
         .bind(&dto.price)
         .bind(dto.quantity_in_stock)
         .bind(&dto.status)
+        .bind(dto.mileage_km)
         .fetch_one(uow.connection())
+        .instrument(db_span("INSERT", "cars"))
         .await
     }
 
@@ -508,7 +584,8 @@ impl CarCommandRepository for PgCarCommandRepository {
                     transmission = $6,
                     price = $7,
                     quantity_in_stock = $8,
-                    status = $9
+                    status = $9,
+                    mileage_km = $11
                 WHERE car_id = $10
                     AND deleted_at IS NULL
                 RETURNING
@@ -524,7 +601,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                     status,
                     created_at,
                     updated_at,
-                    deleted_at
+                    deleted_at,
+                    abc_class,
+                    xyz_class,
+                    mileage_km
                 "#,
         )
         .bind(data.brand)
@@ -537,7 +617,9 @@ impl CarCommandRepository for PgCarCommandRepository {
         .bind(data.quantity_in_stock)
         .bind(data.status)
         .bind(id)
-        .fetch_optional(&self.pool)
+        .bind(data.mileage_km)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("UPDATE", "cars"))
         .await?;
 
         result.ok_or(sqlx::Error::RowNotFound)
@@ -562,6 +644,7 @@ impl CarCommandRepository for PgCarCommandRepository {
                 price = $7,
                 quantity_in_stock = $8,
                 status = $9,
+                mileage_km = $11,
                 updated_at = NOW()
             WHERE car_id = $10
                 AND deleted_at IS NULL
@@ -578,7 +661,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                 status,
                 created_at,
                 updated_at,
-                deleted_at
+                deleted_at,
+                abc_class,
+                xyz_class,
+                mileage_km
             "#,
         )
         .bind(&data.brand)
@@ -591,7 +677,9 @@ impl CarCommandRepository for PgCarCommandRepository {
         .bind(data.quantity_in_stock)
         .bind(&data.status)
         .bind(id)
+        .bind(data.mileage_km)
         .fetch_optional(uow.connection())
+        .instrument(db_span("UPDATE", "cars"))
         .await?
         .ok_or(sqlx::Error::RowNotFound)
     }
@@ -614,7 +702,8 @@ impl CarCommandRepository for PgCarCommandRepository {
                     transmission = $6,
                     price = $7,
                     quantity_in_stock = $8,
-                    status = $9
+                    status = $9,
+                    mileage_km = $12
                 WHERE car_id = $10
                     AND deleted_at IS NULL
                     AND version = $11
@@ -631,7 +720,10 @@ impl CarCommandRepository for PgCarCommandRepository {
                     status,
                     created_at,
                     updated_at,
-                    deleted_at
+                    deleted_at,
+                    abc_class,
+                    xyz_class,
+                    mileage_km
                 "#,
         )
         .bind(data.brand)
@@ -645,7 +737,9 @@ impl CarCommandRepository for PgCarCommandRepository {
         .bind(data.status)
         .bind(id)
         .bind(expected_version)
-        .fetch_optional(&self.pool)
+        .bind(data.mileage_km)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("UPDATE", "cars"))
         .await?;
 
         result.ok_or(sqlx::Error::RowNotFound)
@@ -661,7 +755,8 @@ impl CarCommandRepository for PgCarCommandRepository {
             "#,
         )
         .bind(id)
-        .execute(&self.pool)
+        .execute(&self.pool.get())
+        .instrument(db_span("UPDATE", "cars"))
         .await?;
 
         if result.rows_affected() == 0 {
@@ -677,7 +772,7 @@ pub struct PgCarRepository {
 }
 
 impl PgCarRepository {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self {
             query: PgCarQueryRepository::new(pool.clone()),
             command: PgCarCommandRepository::new(pool),
@@ -723,10 +818,14 @@ impl CarQueryRepository for PgCarRepository {
         self.query.search(request, pagination).await
     }
 
-    async fn get_depreciation_report(&self) -> SqlxResult<Vec<CarEntity>> {
+    async fn get_depreciation_report(&self) -> SqlxResult<Vec<DepreciationInput>> {
         self.query.get_depreciation_report().await
     }
 
+    async fn get_warehouse_stock(&self) -> SqlxResult<Vec<WarehouseStockRow>> {
+        self.query.get_warehouse_stock().await
+    }
+
     async fn get_low_stock_report(&self, threshold: i32) -> SqlxResult<Vec<CarEntity>> {
         self.query.get_low_stock_report(threshold).await
     }
@@ -830,11 +929,11 @@ pub trait ReservationRepository: Send + Sync {
 }
 
 pub struct PgReservationRepository {
-    pool: PgPool,
+    pool: PoolHandle,
 }
 
 impl PgReservationRepository {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self { pool }
     }
 }
@@ -891,7 +990,8 @@ impl ReservationRepository for PgReservationRepository {
         .bind(reserved_by)
         .bind(ttl_minutes as f64)
         .bind(metadata)
-        .fetch_one(&self.pool)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "reservations"))
         .await
     }
 
@@ -906,7 +1006,8 @@ impl ReservationRepository for PgReservationRepository {
             "#,
         )
         .bind(car_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "reservations"))
         .await?;
 
         Ok(result.map(|r| r.0).unwrap_or(0))
@@ -930,7 +1031,8 @@ impl ReservationRepository for PgReservationRepository {
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "reservations"))
         .await
     }
 
@@ -956,7 +1058,8 @@ impl ReservationRepository for PgReservationRepository {
             "#,
         )
         .bind(id)
-        .fetch_one(&self.pool)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("UPDATE", "reservations"))
         .await
     }
 
@@ -971,7 +1074,8 @@ impl ReservationRepository for PgReservationRepository {
             "#,
         )
         .bind(id)
-        .execute(&self.pool)
+        .execute(&self.pool.get())
+        .instrument(db_span("UPDATE", "reservations"))
         .await?;
 
         if result.rows_affected() == 0 {
@@ -996,6 +1100,7 @@ impl ReservationRepository for PgReservationRepository {
         )
         .bind(car_id)
         .fetch_optional(&mut **tx)
+        .instrument(db_span("SELECT", "cars"))
         .await?;
 
         let total_stock = car_row.ok_or(sqlx::Error::RowNotFound)?.0;
@@ -1011,6 +1116,7 @@ impl ReservationRepository for PgReservationRepository {
         )
         .bind(car_id)
         .fetch_optional(&mut **tx)
+        .instrument(db_span("SELECT", "reservations"))
         .await?;
 
         let reserved_qty = reserved.map(|r| r.0).unwrap_or(0);
@@ -1070,6 +1176,7 @@ impl ReservationRepository for PgReservationRepository {
         .bind(ttl_minutes as f64)
         .bind(metadata)
         .fetch_one(&mut **tx)
+        .instrument(db_span("INSERT", "reservations"))
         .await
     }
 
@@ -1080,6 +1187,7 @@ impl ReservationRepository for PgReservationRepository {
     ) -> Result<Reservation, ReservationError> {
         let mut tx = self
             .pool
+            .get()
             .begin()
             .await
             .map_err(ReservationError::Database)?;
@@ -1145,6 +1253,7 @@ impl ReservationRepository for PgReservationRepository {
         )
         .bind(car_id)
         .fetch_one(uow.connection())
+        .instrument(db_span("SELECT", "reservations"))
         .await
         .map_err(|e| match e {
             sqlx::Error::RowNotFound => ReservationError::CarNotFound,
@@ -1203,6 +1312,7 @@ impl ReservationRepository for PgReservationRepository {
         .bind(dto.ttl_minutes as f64)
         .bind(&dto.metadata)
         .fetch_one(uow.connection())
+        .instrument(db_span("INSERT", "reservations"))
         .await
         .map_err(ReservationError::Database)?;
 
@@ -1237,6 +1347,7 @@ impl ReservationRepository for PgReservationRepository {
         )
         .bind(reservation_id)
         .fetch_optional(uow.connection())
+        .instrument(db_span("UPDATE", "reservations"))
         .await
         .map_err(ReservationError::Database)?
         .ok_or(ReservationError::ReservationNotFound)?;
@@ -1251,11 +1362,11 @@ pub trait HealthCheckRepository: Send + Sync {
 }
 
 pub struct PgHealthCheckRepository {
-    pool: PgPool,
+    pool: PoolHandle,
 }
 
 impl PgHealthCheckRepository {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self { pool }
     }
 }
@@ -1263,7 +1374,10 @@ impl PgHealthCheckRepository {
 #[async_trait]
 impl HealthCheckRepository for PgHealthCheckRepository {
     async fn health_check(&self) -> SqlxResult<()> {
-        sqlx::query("SELECT 1").execute(&self.pool).await?;
+        sqlx::query("SELECT 1")
+            .execute(&self.pool.get())
+            .instrument(db_span("SELECT", "health_check"))
+            .await?;
         Ok(())
     }
 }
@@ -1312,11 +1426,11 @@ pub trait WarehouseRepository: Send + Sync {
 }
 
 pub struct PgWarehouseRepository {
-    pool: PgPool,
+    pool: PoolHandle,
 }
 
 impl PgWarehouseRepository {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self { pool }
     }
 }
@@ -1363,7 +1477,8 @@ impl WarehouseRepository for PgWarehouseRepository {
         .bind(latitude.and_then(BigDecimal::from_f64))
         .bind(longitude.and_then(BigDecimal::from_f64))
         .bind(capacity_total)
-        .fetch_one(&self.pool)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "warehouses"))
         .await
     }
 
@@ -1385,7 +1500,8 @@ impl WarehouseRepository for PgWarehouseRepository {
             ORDER BY name
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "warehouses"))
         .await
     }
 
@@ -1410,7 +1526,8 @@ impl WarehouseRepository for PgWarehouseRepository {
             "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "warehouses"))
         .await
     }
 
@@ -1421,7 +1538,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         car_id: &CarId,
         quantity: i32,
     ) -> Result<TransferOrder, sqlx::Error> {
-        let mut tx = self.pool.begin().await?;
+        let mut tx = self.pool.get().begin().await?;
 
         let source: Option<StockLocation> = sqlx::query_as(
             r#"
@@ -1441,6 +1558,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         .bind(from)
         .bind(car_id)
         .fetch_optional(&mut *tx)
+        .instrument(db_span("SELECT", "stock_locations"))
         .await?;
 
         let available = source
@@ -1479,6 +1597,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         .bind(car_id)
         .bind(quantity)
         .fetch_one(&mut *tx)
+        .instrument(db_span("INSERT", "transfer_orders"))
         .await?;
 
         sqlx::query(
@@ -1493,6 +1612,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         .bind(from)
         .bind(car_id)
         .execute(&mut *tx)
+        .instrument(db_span("UPDATE", "stock_locations"))
         .await?;
 
         tx.commit().await?;
@@ -1506,7 +1626,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         car_id: &CarId,
         quantity: i32,
     ) -> Result<TransferOrder, TransferError> {
-        let mut tx = self.pool.begin().await?;
+        let mut tx = self.pool.get().begin().await?;
 
         let source_exists: bool = sqlx::query_scalar(
             r#"
@@ -1520,6 +1640,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         )
         .bind(from)
         .fetch_one(&mut *tx)
+        .instrument(db_span("SELECT", "warehouses"))
         .await?;
 
         if !source_exists {
@@ -1538,6 +1659,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         )
         .bind(to)
         .fetch_one(&mut *tx)
+        .instrument(db_span("SELECT", "warehouses"))
         .await?;
 
         if !dest_exists {
@@ -1623,6 +1745,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         .bind(Uuid::new_v4())
         .bind(to)
         .fetch_optional(&mut *tx)
+        .instrument(db_span("INSERT", "transfer_orders"))
         .await?;
 
         let transfer = match transfer_result {
@@ -1639,6 +1762,7 @@ impl WarehouseRepository for PgWarehouseRepository {
                 .bind(from)
                 .bind(car_id)
                 .fetch_optional(&mut *tx)
+                .instrument(db_span("SELECT", "stock_locations"))
                 .await?;
 
                 let available_qty = available.map(|a| a.0).unwrap_or(0);
@@ -1658,7 +1782,7 @@ impl WarehouseRepository for PgWarehouseRepository {
     }
 
     async fn complete_transfer(&self, transfer_id: Uuid) -> Result<TransferOrder, TransferError> {
-        let mut tx = self.pool.begin().await?;
+        let mut tx = self.pool.get().begin().await?;
 
         let transfer: Option<TransferOrder> = sqlx::query_as(
             r#"
@@ -1678,6 +1802,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         )
         .bind(transfer_id)
         .fetch_optional(&mut *tx)
+        .instrument(db_span("SELECT", "transfer_orders"))
         .await?;
 
         let transfer = transfer.ok_or(TransferError::TransferNotFound(transfer_id))?;
@@ -1709,6 +1834,7 @@ impl WarehouseRepository for PgWarehouseRepository {
         )
         .bind(transfer_id)
         .fetch_one(&mut *tx)
+        .instrument(db_span("UPDATE", "transfer_orders"))
         .await?;
 
         tx.commit().await?;
@@ -1736,7 +1862,8 @@ impl WarehouseRepository for PgWarehouseRepository {
             "#,
         )
         .bind(transfer_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "transfer_orders"))
         .await
     }
 }
@@ -1746,15 +1873,59 @@ pub trait InventoryAnalyticsRepository: Send + Sync {
     async fn get_stock_alerts(&self) -> Result<Vec<StockAlertRow>, sqlx::Error>;
     async fn get_sales_velocity(&self, days: i32) -> Result<Vec<SalesVelocity>, sqlx::Error>;
     async fn get_inventory_metrics(&self) -> Result<InventoryMetrics, sqlx::Error>;
+
+    /// One row per bucket in the range, empty buckets included with `samples = 0`.
+    async fn get_metrics_history(
+        &self,
+        range: &MetricsHistoryRange,
+    ) -> Result<Vec<MetricsHistoryPoint>, sqlx::Error>;
+
+    async fn get_metrics_period(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        aggregation: MetricsAggregation,
+    ) -> Result<MetricsHistoryPoint, sqlx::Error>;
+
+    /// Units sold per UTC day over the last `days` complete days, zero-filled,
+    /// ordered by car and day. Unknown or deleted cars yield no rows.
+    async fn get_daily_sales(
+        &self,
+        car_ids: &[CarId],
+        days: i32,
+    ) -> Result<Vec<DailySalesRow>, sqlx::Error>;
+}
+
+fn metric_aggregates(aggregation: MetricsAggregation) -> String {
+    MetricValues::NAMES
+        .iter()
+        .map(|column| {
+            let expr = match aggregation {
+                MetricsAggregation::Avg => format!("AVG({column})"),
+                MetricsAggregation::Min => format!("MIN({column})"),
+                MetricsAggregation::Max => format!("MAX({column})"),
+                MetricsAggregation::Sum => format!("SUM({column})"),
+                MetricsAggregation::Last => {
+                    format!("(ARRAY_AGG({column} ORDER BY metric_hour DESC))[1]")
+                }
+            };
+            format!("{expr}::float8 AS {column}")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 pub struct PgInventoryAnalyticsRepository {
-    pool: PgPool,
+    router: ReadPoolRouter,
 }
 
 impl PgInventoryAnalyticsRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PoolHandle) -> Self {
+        Self::with_router(ReadPoolRouter::primary_only(pool))
+    }
+
+    pub fn with_router(router: ReadPoolRouter) -> Self {
+        Self { router }
     }
 }
 
@@ -1766,13 +1937,26 @@ impl InventoryAnalyticsRepository for PgInventoryAnalyticsRepository {
             WITH sales_stats AS (
                 SELECT
                     car_id,
-                    COALESCE(AVG(quantity), 0) AS avg_daily_sales,
-                    COALESCE(STDDEV(quantity), 0) AS sales_volatility,
-                    COUNT(*) AS total_sales
+                    SUM(quantity)::float8 / 30 AS avg_daily_sales,
+                    COALESCE(SUM(quantity) FILTER (WHERE sold_at > NOW() - INTERVAL '7 days'), 0) AS this_week,
+                    COALESCE(SUM(quantity) FILTER (
+                        WHERE sold_at <= NOW() - INTERVAL '7 days' AND sold_at > NOW() - INTERVAL '14 days'
+                    ), 0) AS last_week
                 FROM sales_history
                 WHERE sold_at > NOW() - INTERVAL '30 days'
                 GROUP BY car_id
             ),
+            trends AS (
+                SELECT
+                    car_id,
+                    avg_daily_sales,
+                    CASE
+                        WHEN last_week > 0 THEN (this_week - last_week)::float8 / last_week * 100
+                        WHEN this_week > 0 THEN 100.0
+                        ELSE 0.0
+                    END AS trend_percentage
+                FROM sales_stats
+            ),
             reserved_stats AS (
                 SELECT
                     car_id,
@@ -1781,6 +1965,16 @@ impl InventoryAnalyticsRepository for PgInventoryAnalyticsRepository {
                 WHERE status = 'Pending'
                     AND expires_at > NOW()
                 GROUP BY car_id
+            ),
+            location_stats AS (
+                SELECT DISTINCT ON (car_id)
+                    car_id,
+                    warehouse_id AS surplus_warehouse_id,
+                    quantity - reserved_quantity AS surplus_available,
+                    COUNT(*) FILTER (WHERE quantity - reserved_quantity <= 0)
+                        OVER (PARTITION BY car_id) AS empty_locations
+                FROM stock_locations
+                ORDER BY car_id, quantity - reserved_quantity DESC
             )
             SELECT
                 c.car_id,
@@ -1792,96 +1986,262 @@ impl InventoryAnalyticsRepository for PgInventoryAnalyticsRepository {
                 c.reorder_point,
                 c.economic_order_qty,
                 CASE
-                    WHEN c.quantity_in_stock <= c.reorder_point THEN 'Critical'::alert_level
-                    WHEN c.quantity_in_stock <= c.reorder_point * 1.5 THEN 'Warning'::alert_level
+                    WHEN c.quantity_in_stock <= c.reorder_point * COALESCE(th.critical_ratio, 1.0)
+                        THEN 'Critical'::alert_level
+                    WHEN c.quantity_in_stock <= c.reorder_point * COALESCE(th.warning_ratio, 1.5)
+                        THEN 'Warning'::alert_level
                     ELSE 'Ok'::alert_level
                 END AS alert_level,
+                c.abc_class,
                 CASE
-                    WHEN s.avg_daily_sales > 0 THEN 'UP'
+                    WHEN t.trend_percentage >= 10 THEN 'UP'
+                    WHEN t.trend_percentage <= -10 THEN 'DOWN'
                     ELSE 'STABLE'
                 END AS trend_direction,
-                10.0 AS trend_percentage,
-                s.avg_daily_sales,
+                COALESCE(t.trend_percentage, 0.0) AS trend_percentage,
+                t.avg_daily_sales,
                 CASE
-                    WHEN s.avg_daily_sales > 0
-                    THEN ((c.quantity_in_stock - COALESCE(r.reserved_qty, 0)::int) / s.avg_daily_sales)::int
+                    WHEN t.avg_daily_sales > 0
+                    THEN FLOOR(GREATEST(c.quantity_in_stock - COALESCE(r.reserved_qty, 0), 0) / t.avg_daily_sales)::int
                     ELSE NULL
                 END AS days_until_stockout,
-                'Reorder' AS suggested_action_type,
-                'Stock below reorder point' AS suggested_description,
-                1 AS suggested_priority
+                l.surplus_warehouse_id,
+                l.surplus_available,
+                COALESCE(l.empty_locations, 0) AS empty_locations
             FROM cars c
-            LEFT JOIN sales_stats s ON c.car_id = s.car_id
+            LEFT JOIN trends t ON c.car_id = t.car_id
             LEFT JOIN reserved_stats r ON c.car_id = r.car_id
+            LEFT JOIN location_stats l ON c.car_id = l.car_id
+            LEFT JOIN alert_thresholds th ON c.abc_class = th.abc_class
             WHERE c.deleted_at IS NULL
-                AND c.quantity_in_stock <= c.reorder_point * 1.5
+                AND c.quantity_in_stock <= c.reorder_point * COALESCE(th.warning_ratio, 1.5)
             ORDER BY alert_level DESC, c.quantity_in_stock ASC
             "#,
         )
-        .fetch_all(&self.pool)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "sales_history"))
         .await
     }
 
     async fn get_sales_velocity(&self, days: i32) -> Result<Vec<SalesVelocity>, sqlx::Error> {
+        // Volatility is the standard deviation of daily units over the whole
+        // window, so days without sales count as zero.
         sqlx::query_as::<_, SalesVelocity>(
             r#"
+            WITH daily AS (
+                SELECT car_id, date_trunc('day', sold_at) AS day, SUM(quantity)::float8 AS units
+                FROM sales_history
+                WHERE sold_at > NOW() - make_interval(days => $1)
+                GROUP BY car_id, date_trunc('day', sold_at)
+            ),
+            window_stats AS (
+                SELECT car_id, SUM(units) AS units, SUM(units * units) AS units_sq
+                FROM daily
+                GROUP BY car_id
+            ),
+            recent AS (
+                SELECT
+                    car_id,
+                    COUNT(*) AS last_30_days_sales,
+                    COUNT(*) FILTER (WHERE sold_at > NOW() - INTERVAL '7 days') AS last_7_days_sales,
+                    COALESCE(SUM(quantity) FILTER (WHERE sold_at > NOW() - INTERVAL '7 days'), 0) AS this_week,
+                    COALESCE(SUM(quantity) FILTER (
+                        WHERE sold_at <= NOW() - INTERVAL '7 days' AND sold_at > NOW() - INTERVAL '14 days'
+                    ), 0) AS last_week
+                FROM sales_history
+                WHERE sold_at > NOW() - INTERVAL '30 days'
+                GROUP BY car_id
+            )
             SELECT
                 c.car_id,
                 c.brand,
                 c.model,
-                COALESCE(AVG(s.quantity), 0) / $1 AS avg_daily_sales,
-                COALESCE(STDDEV(s.quantity), 0) AS sales_volatility,
-                COUNT(*) FILTER (WHERE s.sold_at > NOW() - INTERVAL '30 days') AS last_30_days_sales,
-                COUNT(*) FILTER (WHERE s.sold_at > NOW() - INTERVAL '7 days') AS last_7_days_sales,
+                w.units / $1 AS avg_daily_sales,
+                SQRT(GREATEST(w.units_sq / $1 - POWER(w.units / $1, 2), 0)) AS sales_volatility,
+                COALESCE(r.last_30_days_sales, 0) AS last_30_days_sales,
+                COALESCE(r.last_7_days_sales, 0) AS last_7_days_sales,
                 CASE
-                    WHEN AVG(s.quantity) FILTER (WHERE s.sold_at > NOW() - INTERVAL '7 days') >
-                         AVG(s.quantity) FILTER (WHERE s.sold_at <= NOW() - INTERVAL '7 days' AND s.sold_at > NOW() - INTERVAL '14 days')
-                    THEN 'UP'
-                    ELSE 'DOWN'
+                    WHEN r.last_week > 0 AND (r.this_week - r.last_week)::float8 / r.last_week >= 0.1 THEN 'UP'
+                    WHEN r.last_week > 0 AND (r.this_week - r.last_week)::float8 / r.last_week <= -0.1 THEN 'DOWN'
+                    WHEN COALESCE(r.last_week, 0) = 0 AND r.this_week > 0 THEN 'UP'
+                    ELSE 'STABLE'
                 END AS trend_direction
-            FROM cars c
-            LEFT JOIN sales_history s ON c.car_id = s.car_id
-                AND s.sold_at > NOW() - INTERVAL '30 days'
+            FROM window_stats w
+            JOIN cars c ON c.car_id = w.car_id
+            LEFT JOIN recent r ON r.car_id = w.car_id
             WHERE c.deleted_at IS NULL
-            GROUP BY c.car_id, c.brand, c.model
-            HAVING COUNT(s.*) > 0
             ORDER BY avg_daily_sales DESC
             "#,
         )
-        .bind(days as f64)
-        .fetch_all(&self.pool)
+        .bind(days)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "sales_history"))
         .await
     }
 
     async fn get_inventory_metrics(&self) -> Result<InventoryMetrics, sqlx::Error> {
-        let row: (i64, Option<BigDecimal>, i64, i64, i64, i64) = sqlx::query_as(
+        // Turnover is annualised COGS (units sold at the price they were sold
+        // for) over the average hourly inventory value snapshot, across the
+        // last year or as much history as exists (at least 30 days).
+        let row: (i64, BigDecimal, i64, i64, i64, i64, f64, Option<f64>, f64) = sqlx::query_as(
             r#"
+            WITH turnover_window AS (
+                SELECT LEAST(
+                    NOW() - INTERVAL '30 days',
+                    GREATEST(NOW() - INTERVAL '365 days', COALESCE(MIN(metric_hour), NOW() - INTERVAL '365 days'))
+                ) AS since
+                FROM inventory_metrics_history
+            )
             SELECT
-                COUNT(DISTINCT c.car_id),
-                SUM(c.price * c.quantity_in_stock),
-                COUNT(DISTINCT w.warehouse_id),
-                COUNT(DISTINCT r.id) FILTER (WHERE r.status = 'Pending'),
-                COALESCE(SUM(r.quantity) FILTER (WHERE r.status = 'Pending'), 0),
-                COUNT(DISTINCT c.car_id) FILTER (WHERE c.quantity_in_stock <= c.reorder_point)
-            FROM cars c
-            CROSS JOIN warehouses w
-            LEFT JOIN reservations r ON c.car_id = r.car_id
-            WHERE c.deleted_at IS NULL
+                (SELECT COUNT(*) FROM cars WHERE deleted_at IS NULL),
+                (SELECT COALESCE(SUM(price * quantity_in_stock), 0) FROM cars WHERE deleted_at IS NULL),
+                (SELECT COUNT(*) FROM warehouses),
+                (SELECT COUNT(*) FROM reservations r JOIN cars c ON c.car_id = r.car_id
+                    WHERE r.status = 'Pending' AND c.deleted_at IS NULL),
+                (SELECT COALESCE(SUM(r.quantity), 0) FROM reservations r JOIN cars c ON c.car_id = r.car_id
+                    WHERE r.status = 'Pending' AND c.deleted_at IS NULL),
+                (SELECT COUNT(*) FROM cars WHERE deleted_at IS NULL AND quantity_in_stock <= reorder_point),
+                (SELECT COALESCE(SUM(s.quantity * s.sale_price), 0)::float8
+                    FROM sales_history s
+                    WHERE s.sold_at > w.since),
+                (SELECT AVG(total_value)::float8 FROM inventory_metrics_history WHERE metric_hour > w.since),
+                EXTRACT(EPOCH FROM NOW() - w.since)::float8 / 86400
+            FROM turnover_window w
             "#,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&self.router.reader())
+        .instrument(db_span("SELECT", "cars"))
         .await?;
 
+        let (cogs, avg_inventory_value, window_days) = (row.6, row.7, row.8);
+        let avg_inventory_value =
+            avg_inventory_value.unwrap_or_else(|| row.1.to_f64().unwrap_or(0.0));
+        let stock_turnover_rate = if avg_inventory_value > 0.0 && window_days > 0.0 {
+            cogs / avg_inventory_value * (365.0 / window_days)
+        } else {
+            0.0
+        };
+
         Ok(InventoryMetrics {
             total_cars: row.0,
-            total_value: row.1.unwrap_or_else(|| BigDecimal::from(0)),
+            total_value: row.1,
             total_warehouses: row.2,
             active_reservations: row.3,
             reserved_units: row.4,
             low_stock_items: row.5,
-            stock_turnover_rate: 0.0,
+            stock_turnover_rate,
         })
     }
+
+    async fn get_metrics_history(
+        &self,
+        range: &MetricsHistoryRange,
+    ) -> Result<Vec<MetricsHistoryPoint>, sqlx::Error> {
+        // Buckets are computed on UTC timestamps so day/week/month boundaries
+        // don't depend on the session time zone.
+        let sql = format!(
+            r#"
+            WITH buckets AS (
+                SELECT generate_series(
+                    date_trunc($1, $2 AT TIME ZONE 'UTC'),
+                    $3 AT TIME ZONE 'UTC',
+                    ('1 ' || $1)::interval
+                ) AS bucket
+            ),
+            aggregated AS (
+                SELECT
+                    date_trunc($1, metric_hour AT TIME ZONE 'UTC') AS bucket,
+                    COUNT(*) AS samples,
+                    {aggregates}
+                FROM inventory_metrics_history
+                WHERE metric_hour >= $2 AND metric_hour < $3
+                GROUP BY 1
+            )
+            SELECT
+                b.bucket AT TIME ZONE 'UTC' AS bucket_start,
+                COALESCE(a.samples, 0) AS samples,
+                {columns}
+            FROM buckets b
+            LEFT JOIN aggregated a ON a.bucket = b.bucket
+            WHERE b.bucket < $3 AT TIME ZONE 'UTC'
+            ORDER BY b.bucket
+            "#,
+            aggregates = metric_aggregates(range.aggregation),
+            columns = MetricValues::NAMES
+                .iter()
+                .map(|column| format!("a.{column}"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
+        sqlx::query_as::<_, MetricsHistoryPoint>(&sql)
+            .bind(range.bucket.as_str())
+            .bind(range.from)
+            .bind(range.to)
+            .fetch_all(&self.router.reader())
+            .instrument(db_span("SELECT", "inventory_metrics_history"))
+            .await
+    }
+
+    async fn get_metrics_period(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        aggregation: MetricsAggregation,
+    ) -> Result<MetricsHistoryPoint, sqlx::Error> {
+        let sql = format!(
+            r#"
+            SELECT $1::timestamptz AS bucket_start, COUNT(*) AS samples, {aggregates}
+            FROM inventory_metrics_history
+            WHERE metric_hour >= $1 AND metric_hour < $2
+            "#,
+            aggregates = metric_aggregates(aggregation),
+        );
+
+        sqlx::query_as::<_, MetricsHistoryPoint>(&sql)
+            .bind(from)
+            .bind(to)
+            .fetch_one(&self.router.reader())
+            .instrument(db_span("SELECT", "inventory_metrics_history"))
+            .await
+    }
+
+    async fn get_daily_sales(
+        &self,
+        car_ids: &[CarId],
+        days: i32,
+    ) -> Result<Vec<DailySalesRow>, sqlx::Error> {
+        let car_ids: Vec<&str> = car_ids.iter().map(CarId::as_str).collect();
+
+        sqlx::query_as::<_, DailySalesRow>(
+            r#"
+            WITH days AS (
+                SELECT generate_series(
+                    (NOW() AT TIME ZONE 'UTC')::date - $2,
+                    (NOW() AT TIME ZONE 'UTC')::date - 1,
+                    INTERVAL '1 day'
+                ) AS day
+            )
+            SELECT
+                c.car_id,
+                d.day::date AS day,
+                COALESCE(SUM(s.quantity), 0)::int8 AS units
+            FROM cars c
+            CROSS JOIN days d
+            LEFT JOIN sales_history s ON s.car_id = c.car_id
+                AND s.sold_at >= d.day AT TIME ZONE 'UTC'
+                AND s.sold_at < (d.day + INTERVAL '1 day') AT TIME ZONE 'UTC'
+            WHERE c.car_id = ANY($1) AND c.deleted_at IS NULL
+            GROUP BY c.car_id, d.day
+            ORDER BY c.car_id, d.day
+            "#,
+        )
+        .bind(car_ids)
+        .bind(days)
+        .fetch_all(&self.router.reader())
+        .instrument(db_span("SELECT", "sales_history"))
+        .await
+    }
 }
 
 #[async_trait]
@@ -1891,9 +2251,10 @@ pub trait SalesRepository: Send + Sync {
         uow: &mut UnitOfWork<'_>,
         car_id: &CarId,
         quantity: i32,
-        sale_price: &BigDecimal,
+        pricing: &PriceQuote,
         customer_id: Option<&str>,
-    ) -> SqlxResult<Uuid>;
+        promo_code: Option<&str>,
+    ) -> Result<Uuid, ReservationError>;
 }
 
 pub struct PgSalesRepository;
@@ -1905,11 +2266,59 @@ impl SalesRepository for PgSalesRepository {
         uow: &mut UnitOfWork<'_>,
         car_id: &CarId,
         quantity: i32,
-        sale_price: &BigDecimal,
+        pricing: &PriceQuote,
         customer_id: Option<&str>,
-    ) -> SqlxResult<Uuid> {
+        promo_code: Option<&str>,
+    ) -> Result<Uuid, ReservationError> {
         let sale_id = Uuid::new_v4();
 
+        // Units leave the car's locations, fullest first, so demand can be
+        // attributed per warehouse. Cars not held in any warehouse are only
+        // tracked through `cars.quantity_in_stock`.
+        let free_stock: Vec<(WarehouseId, i32)> = sqlx::query_as(
+            r#"
+            SELECT
+                sl.warehouse_id,
+                CASE WHEN w.is_active THEN sl.quantity - sl.reserved_quantity ELSE 0 END
+            FROM stock_locations sl
+            JOIN warehouses w ON w.warehouse_id = sl.warehouse_id
+            WHERE sl.car_id = $1
+            ORDER BY sl.warehouse_id
+            FOR UPDATE OF sl
+            "#,
+        )
+        .bind(car_id)
+        .fetch_all(uow.connection())
+        .instrument(db_span("SELECT", "stock_locations"))
+        .await?;
+
+        let parts = if free_stock.is_empty() {
+            Vec::new()
+        } else {
+            fulfilment::split_sale(&free_stock, quantity).map_err(|available| {
+                ReservationError::InsufficientStock {
+                    requested: quantity,
+                    available,
+                }
+            })?
+        };
+
+        for (warehouse_id, units) in &parts {
+            sqlx::query(
+                r#"
+                UPDATE stock_locations
+                SET quantity = quantity - $3, last_updated = NOW()
+                WHERE car_id = $1 AND warehouse_id = $2
+                "#,
+            )
+            .bind(car_id)
+            .bind(warehouse_id)
+            .bind(units)
+            .execute(uow.connection())
+            .instrument(db_span("UPDATE", "stock_locations"))
+            .await?;
+        }
+
         sqlx::query(
             r#"
             INSERT INTO sales_history (
@@ -1918,19 +2327,1979 @@ impl SalesRepository for PgSalesRepository {
                 quantity,
                 sale_price,
                 customer_id,
-                sold_at
+                sold_at,
+                list_price,
+                promo_code
             )
-            VALUES ($1, $2, $3, $4, $5, NOW())
+            VALUES ($1, $2, $3, $4, $5, NOW(), $6, $7)
             "#,
         )
         .bind(sale_id)
         .bind(car_id)
         .bind(quantity)
-        .bind(sale_price)
+        .bind(&pricing.unit_price)
         .bind(customer_id)
+        .bind(&pricing.list_price)
+        .bind(promo_code)
         .execute(uow.connection())
+        .instrument(db_span("INSERT", "sales_history"))
         .await?;
 
+        for (warehouse_id, units) in &parts {
+            sqlx::query(
+                r#"
+                INSERT INTO sale_fulfilments (sale_id, warehouse_id, quantity)
+                VALUES ($1, $2, $3)
+                "#,
+            )
+            .bind(sale_id)
+            .bind(warehouse_id)
+            .bind(units)
+            .execute(uow.connection())
+            .instrument(db_span("INSERT", "sale_fulfilments"))
+            .await?;
+        }
+
+        for (position, rule) in pricing.applied_rules.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO sale_price_adjustments (
+                    sale_id,
+                    position,
+                    rule_id,
+                    rule_name,
+                    unit_discount
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(sale_id)
+            .bind(position as i16)
+            .bind(rule.rule_id)
+            .bind(&rule.name)
+            .bind(&rule.unit_discount)
+            .execute(uow.connection())
+            .instrument(db_span("INSERT", "sale_price_adjustments"))
+            .await?;
+        }
+
         Ok(sale_id)
     }
 }
+
+#[async_trait]
+pub trait JobExecutionRepository: Send + Sync {
+    async fn start(&self, job_type: &str, triggered_by: &str) -> SqlxResult<JobExecution>;
+
+    async fn complete(
+        &self,
+        job_id: Uuid,
+        items_processed: i32,
+        attempts: i32,
+    ) -> SqlxResult<JobExecution>;
+
+    async fn fail(
+        &self,
+        job_id: Uuid,
+        items_processed: i32,
+        attempts: i32,
+        error_message: &str,
+    ) -> SqlxResult<JobExecution>;
+
+    async fn list(
+        &self,
+        filter: &JobExecutionFilter,
+        pagination: &PaginationParams,
+    ) -> SqlxResult<(Vec<JobExecution>, i64)>;
+}
+
+pub struct PgJobExecutionRepository {
+    pool: PoolHandle,
+}
+
+impl PgJobExecutionRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+
+    async fn finish(
+        &self,
+        job_id: Uuid,
+        status: JobStatus,
+        items_processed: i32,
+        attempts: i32,
+        error_message: Option<&str>,
+    ) -> SqlxResult<JobExecution> {
+        sqlx::query_as::<_, JobExecution>(
+            r#"
+            UPDATE job_executions
+            SET
+                status = $2,
+                completed_at = NOW(),
+                items_processed = $3,
+                attempts = $4,
+                error_message = $5
+            WHERE job_id = $1
+            RETURNING
+                job_id,
+                job_type,
+                started_at,
+                completed_at,
+                status,
+                items_processed,
+                error_message,
+                triggered_by,
+                attempts
+            "#,
+        )
+        .bind(job_id)
+        .bind(status)
+        .bind(items_processed)
+        .bind(attempts)
+        .bind(error_message)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("UPDATE", "job_executions"))
+        .await
+    }
+}
+
+#[async_trait]
+impl JobExecutionRepository for PgJobExecutionRepository {
+    async fn start(&self, job_type: &str, triggered_by: &str) -> SqlxResult<JobExecution> {
+        sqlx::query_as::<_, JobExecution>(
+            r#"
+            INSERT INTO job_executions (job_type, started_at, status, triggered_by)
+            VALUES ($1, NOW(), 'Running', $2)
+            RETURNING
+                job_id,
+                job_type,
+                started_at,
+                completed_at,
+                status,
+                items_processed,
+                error_message,
+                triggered_by,
+                attempts
+            "#,
+        )
+        .bind(job_type)
+        .bind(triggered_by)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "job_executions"))
+        .await
+    }
+
+    async fn complete(
+        &self,
+        job_id: Uuid,
+        items_processed: i32,
+        attempts: i32,
+    ) -> SqlxResult<JobExecution> {
+        self.finish(
+            job_id,
+            JobStatus::Completed,
+            items_processed,
+            attempts,
+            None,
+        )
+        .await
+    }
+
+    async fn fail(
+        &self,
+        job_id: Uuid,
+        items_processed: i32,
+        attempts: i32,
+        error_message: &str,
+    ) -> SqlxResult<JobExecution> {
+        self.finish(
+            job_id,
+            JobStatus::Failed,
+            items_processed,
+            attempts,
+            Some(error_message),
+        )
+        .await
+    }
+
+    async fn list(
+        &self,
+        filter: &JobExecutionFilter,
+        pagination: &PaginationParams,
+    ) -> SqlxResult<(Vec<JobExecution>, i64)> {
+        let (limit, offset, _, _) = pagination.normalize();
+
+        let mut builder = QueryBuilder::new(
+            r#"
+            SELECT
+                job_id,
+                job_type,
+                started_at,
+                completed_at,
+                status,
+                items_processed,
+                error_message,
+                triggered_by,
+                attempts,
+                COUNT(*) OVER() AS total_count
+            FROM job_executions
+            WHERE TRUE
+            "#,
+        );
+
+        if let Some(job_type) = &filter.job_type {
+            builder.push(" AND job_type = ");
+            builder.push_bind(job_type);
+        }
+
+        if let Some(status) = filter.status {
+            builder.push(" AND status = ");
+            builder.push_bind(status);
+        }
+
+        builder.push(" ORDER BY started_at DESC LIMIT ");
+        builder.push_bind(limit);
+        builder.push(" OFFSET ");
+        builder.push_bind(offset);
+
+        #[derive(sqlx::FromRow)]
+        struct JobExecutionRow {
+            #[sqlx(flatten)]
+            execution: JobExecution,
+            total_count: i64,
+        }
+
+        let rows = builder
+            .build_query_as::<JobExecutionRow>()
+            .fetch_all(&self.pool.get())
+            .instrument(db_span("SELECT", "job_executions"))
+            .await?;
+
+        let total = rows.first().map(|r| r.total_count).unwrap_or(0);
+        let executions = rows.into_iter().map(|r| r.execution).collect();
+
+        Ok((executions, total))
+    }
+}
+
+#[async_trait]
+pub trait ReorderRepository: Send + Sync {
+    async fn list_planning_inputs(&self) -> SqlxResult<Vec<ReorderPlanningInput>>;
+
+    /// Stores the new parameters and records the previous ones. Returns
+    /// `false` when nothing changed.
+    async fn apply_parameters(&self, update: &ReorderParameterUpdate) -> SqlxResult<bool>;
+
+    async fn get_settings(&self, car_id: &CarId) -> SqlxResult<Option<ReorderSettings>>;
+
+    async fn list_changes(
+        &self,
+        car_id: &CarId,
+        limit: i64,
+    ) -> SqlxResult<Vec<ReorderParameterChange>>;
+
+    /// Replaces the overrides and applies them right away.
+    async fn set_overrides(
+        &self,
+        car_id: &CarId,
+        overrides: &ReorderOverrideDto,
+    ) -> SqlxResult<Option<ReorderSettings>>;
+}
+
+pub struct PgReorderRepository {
+    pool: PoolHandle,
+}
+
+impl PgReorderRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+
+    async fn apply_parameters_with(
+        conn: &mut sqlx::PgConnection,
+        update: &ReorderParameterUpdate,
+    ) -> SqlxResult<bool> {
+        let result = sqlx::query(
+            r#"
+            WITH previous AS (
+                SELECT car_id, reorder_point, economic_order_qty, safety_stock
+                FROM cars
+                WHERE car_id = $1 AND deleted_at IS NULL
+                FOR UPDATE
+            ),
+            updated AS (
+                UPDATE cars c
+                SET
+                    reorder_point = $2,
+                    economic_order_qty = $3,
+                    safety_stock = $4,
+                    reorder_params_updated_at = NOW()
+                FROM previous p
+                WHERE c.car_id = p.car_id
+                    AND (p.reorder_point, p.economic_order_qty, p.safety_stock)
+                        IS DISTINCT FROM ($2, $3, $4)
+                RETURNING c.car_id
+            )
+            INSERT INTO reorder_parameter_history (
+                car_id, source,
+                previous_reorder_point, reorder_point,
+                previous_economic_order_qty, economic_order_qty,
+                previous_safety_stock, safety_stock,
+                avg_daily_demand, demand_std_dev, lead_time_days
+            )
+            SELECT
+                p.car_id, $5,
+                p.reorder_point, $2,
+                p.economic_order_qty, $3,
+                p.safety_stock, $4,
+                $6, $7, $8
+            FROM previous p
+            JOIN updated u ON u.car_id = p.car_id
+            "#,
+        )
+        .bind(update.car_id.as_str())
+        .bind(update.reorder_point)
+        .bind(update.economic_order_qty)
+        .bind(update.safety_stock)
+        .bind(update.source)
+        .bind(update.avg_daily_demand)
+        .bind(update.demand_std_dev)
+        .bind(update.lead_time_days)
+        .execute(conn)
+        .instrument(db_span("UPDATE", "cars"))
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+const REORDER_SETTINGS_COLUMNS: &str = "car_id, reorder_point, economic_order_qty, safety_stock, \
+     lead_time_days, reorder_point_override, economic_order_qty_override, reorder_params_updated_at";
+
+#[async_trait]
+impl ReorderRepository for PgReorderRepository {
+    async fn list_planning_inputs(&self) -> SqlxResult<Vec<ReorderPlanningInput>> {
+        sqlx::query_as::<_, ReorderPlanningInput>(
+            r#"
+            SELECT
+                car_id,
+                price::float8 AS unit_price,
+                reorder_point,
+                economic_order_qty,
+                safety_stock,
+                lead_time_days,
+                reorder_point_override,
+                economic_order_qty_override
+            FROM cars
+            WHERE deleted_at IS NULL
+            ORDER BY car_id
+            "#,
+        )
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "cars"))
+        .await
+    }
+
+    async fn apply_parameters(&self, update: &ReorderParameterUpdate) -> SqlxResult<bool> {
+        let mut conn = self.pool.get().acquire().await?;
+        Self::apply_parameters_with(&mut conn, update).await
+    }
+
+    async fn get_settings(&self, car_id: &CarId) -> SqlxResult<Option<ReorderSettings>> {
+        sqlx::query_as::<_, ReorderSettings>(&format!(
+            "SELECT {REORDER_SETTINGS_COLUMNS} FROM cars WHERE car_id = $1 AND deleted_at IS NULL"
+        ))
+        .bind(car_id.as_str())
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "cars"))
+        .await
+    }
+
+    async fn list_changes(
+        &self,
+        car_id: &CarId,
+        limit: i64,
+    ) -> SqlxResult<Vec<ReorderParameterChange>> {
+        sqlx::query_as::<_, ReorderParameterChange>(
+            r#"
+            SELECT
+                id, source,
+                previous_reorder_point, reorder_point,
+                previous_economic_order_qty, economic_order_qty,
+                previous_safety_stock, safety_stock,
+                avg_daily_demand, demand_std_dev, lead_time_days,
+                changed_at
+            FROM reorder_parameter_history
+            WHERE car_id = $1
+            ORDER BY changed_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(car_id.as_str())
+        .bind(limit)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "reorder_parameter_history"))
+        .await
+    }
+
+    async fn set_overrides(
+        &self,
+        car_id: &CarId,
+        overrides: &ReorderOverrideDto,
+    ) -> SqlxResult<Option<ReorderSettings>> {
+        let mut tx = self.pool.get().begin().await?;
+
+        let settings = sqlx::query_as::<_, ReorderSettings>(&format!(
+            r#"
+            UPDATE cars
+            SET
+                reorder_point_override = $2,
+                economic_order_qty_override = $3,
+                lead_time_days = $4
+            WHERE car_id = $1 AND deleted_at IS NULL
+            RETURNING {REORDER_SETTINGS_COLUMNS}
+            "#
+        ))
+        .bind(car_id.as_str())
+        .bind(overrides.reorder_point)
+        .bind(overrides.economic_order_qty)
+        .bind(overrides.lead_time_days)
+        .fetch_optional(&mut *tx)
+        .instrument(db_span("UPDATE", "cars"))
+        .await?;
+
+        let Some(settings) = settings else {
+            return Ok(None);
+        };
+
+        let update = ReorderParameterUpdate {
+            car_id: settings.car_id.clone(),
+            reorder_point: overrides.reorder_point.unwrap_or(settings.reorder_point),
+            economic_order_qty: overrides
+                .economic_order_qty
+                .unwrap_or(settings.economic_order_qty),
+            safety_stock: settings.safety_stock,
+            avg_daily_demand: None,
+            demand_std_dev: None,
+            lead_time_days: settings.lead_time_days,
+            source: REORDER_SOURCE_OVERRIDE,
+        };
+        Self::apply_parameters_with(&mut tx, &update).await?;
+
+        let settings = sqlx::query_as::<_, ReorderSettings>(&format!(
+            "SELECT {REORDER_SETTINGS_COLUMNS} FROM cars WHERE car_id = $1"
+        ))
+        .bind(car_id.as_str())
+        .fetch_optional(&mut *tx)
+        .instrument(db_span("SELECT", "cars"))
+        .await?;
+
+        tx.commit().await?;
+        Ok(settings)
+    }
+}
+
+#[async_trait]
+pub trait PurchasingRepository: Send + Sync {
+    async fn create_supplier(
+        &self,
+        id: &SupplierId,
+        name: &str,
+        contact_email: Option<&str>,
+        lead_time_days: i32,
+        min_order_qty: i32,
+    ) -> SqlxResult<Supplier>;
+
+    async fn list_suppliers(&self) -> SqlxResult<Vec<Supplier>>;
+
+    async fn find_supplier(&self, id: &SupplierId) -> SqlxResult<Option<Supplier>>;
+
+    async fn list_supplier_prices(&self, id: &SupplierId) -> SqlxResult<Vec<SupplierPrice>>;
+
+    async fn upsert_supplier_price(
+        &self,
+        id: &SupplierId,
+        car_id: &CarId,
+        price: &SetSupplierPriceDto,
+    ) -> SqlxResult<SupplierPrice>;
+
+    async fn create_purchase_order(
+        &self,
+        supplier_id: &SupplierId,
+        warehouse_id: &WarehouseId,
+        notes: Option<&str>,
+        lines: &[NewPurchaseOrderLine],
+    ) -> Result<PurchaseOrderResponse, PurchaseOrderError>;
+
+    async fn list_purchase_orders(
+        &self,
+        status: Option<PurchaseOrderStatus>,
+        supplier_id: Option<&SupplierId>,
+        origin: Option<&str>,
+        limit: i64,
+    ) -> SqlxResult<Vec<PurchaseOrder>>;
+
+    async fn find_purchase_order(&self, po_id: Uuid) -> SqlxResult<Option<PurchaseOrderResponse>>;
+
+    async fn submit_purchase_order(&self, po_id: Uuid)
+    -> Result<PurchaseOrder, PurchaseOrderError>;
+
+    async fn cancel_purchase_order(&self, po_id: Uuid)
+    -> Result<PurchaseOrder, PurchaseOrderError>;
+
+    /// Books the received units into the order's warehouse and car stock and
+    /// advances the order status, all in one transaction.
+    async fn receive_goods(
+        &self,
+        po_id: Uuid,
+        lines: &[(CarId, i32)],
+    ) -> Result<PurchaseOrderResponse, PurchaseOrderError>;
+
+    /// Cheapest active supplier and receiving warehouse per car. Cars
+    /// without a supplier price are left out.
+    async fn list_replenishment_candidates(
+        &self,
+        car_ids: &[CarId],
+    ) -> SqlxResult<Vec<ReplenishmentCandidate>>;
+
+    /// Adds lines to the open replenishment draft of the supplier and
+    /// warehouse, creating it if needed. Returns the number of lines added.
+    async fn draft_replenishment_order(
+        &self,
+        supplier_id: &SupplierId,
+        warehouse_id: &WarehouseId,
+        lines: &[NewPurchaseOrderLine],
+    ) -> SqlxResult<u64>;
+}
+
+pub struct PgPurchasingRepository {
+    pool: PoolHandle,
+}
+
+impl PgPurchasingRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_lines(
+        conn: &mut sqlx::PgConnection,
+        po_id: Uuid,
+    ) -> SqlxResult<Vec<PurchaseOrderLine>> {
+        sqlx::query_as::<_, PurchaseOrderLine>(
+            r#"
+            SELECT line_id, car_id, quantity, received_quantity, unit_cost, note
+            FROM purchase_order_lines
+            WHERE po_id = $1
+            ORDER BY car_id
+            "#,
+        )
+        .bind(po_id)
+        .fetch_all(conn)
+        .instrument(db_span("SELECT", "purchase_order_lines"))
+        .await
+    }
+
+    /// Explains why a guarded status update matched no row.
+    async fn transition_error(
+        conn: &mut sqlx::PgConnection,
+        po_id: Uuid,
+        expected: &str,
+    ) -> PurchaseOrderError {
+        let status = sqlx::query_scalar::<_, PurchaseOrderStatus>(
+            "SELECT status FROM purchase_orders WHERE po_id = $1",
+        )
+        .bind(po_id)
+        .fetch_optional(conn)
+        .instrument(db_span("SELECT", "purchase_orders"))
+        .await;
+
+        match status {
+            Ok(Some(found)) => PurchaseOrderError::InvalidState {
+                expected: expected.to_string(),
+                found: format!("{:?}", found),
+            },
+            Ok(None) => PurchaseOrderError::PurchaseOrderNotFound(po_id),
+            Err(e) => PurchaseOrderError::Database(e),
+        }
+    }
+}
+
+const SUPPLIER_COLUMNS: &str =
+    "supplier_id, name, contact_email, lead_time_days, min_order_qty, is_active, created_at";
+
+const SUPPLIER_PRICE_COLUMNS: &str =
+    "supplier_id, car_id, unit_cost, min_order_qty, lead_time_days, updated_at";
+
+const PURCHASE_ORDER_COLUMNS: &str = "po_id, supplier_id, warehouse_id, status, origin, notes, \
+     created_at, submitted_at, expected_at, received_at, cancelled_at";
+
+#[async_trait]
+impl PurchasingRepository for PgPurchasingRepository {
+    async fn create_supplier(
+        &self,
+        id: &SupplierId,
+        name: &str,
+        contact_email: Option<&str>,
+        lead_time_days: i32,
+        min_order_qty: i32,
+    ) -> SqlxResult<Supplier> {
+        sqlx::query_as::<_, Supplier>(&format!(
+            r#"
+            INSERT INTO suppliers (supplier_id, name, contact_email, lead_time_days, min_order_qty)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {SUPPLIER_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(name)
+        .bind(contact_email)
+        .bind(lead_time_days)
+        .bind(min_order_qty)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "suppliers"))
+        .await
+    }
+
+    async fn list_suppliers(&self) -> SqlxResult<Vec<Supplier>> {
+        sqlx::query_as::<_, Supplier>(&format!(
+            "SELECT {SUPPLIER_COLUMNS} FROM suppliers WHERE is_active = true ORDER BY name"
+        ))
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "suppliers"))
+        .await
+    }
+
+    async fn find_supplier(&self, id: &SupplierId) -> SqlxResult<Option<Supplier>> {
+        sqlx::query_as::<_, Supplier>(&format!(
+            "SELECT {SUPPLIER_COLUMNS} FROM suppliers WHERE supplier_id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "suppliers"))
+        .await
+    }
+
+    async fn list_supplier_prices(&self, id: &SupplierId) -> SqlxResult<Vec<SupplierPrice>> {
+        sqlx::query_as::<_, SupplierPrice>(&format!(
+            "SELECT {SUPPLIER_PRICE_COLUMNS} FROM supplier_prices WHERE supplier_id = $1 ORDER BY car_id"
+        ))
+        .bind(id)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "supplier_prices"))
+        .await
+    }
+
+    async fn upsert_supplier_price(
+        &self,
+        id: &SupplierId,
+        car_id: &CarId,
+        price: &SetSupplierPriceDto,
+    ) -> SqlxResult<SupplierPrice> {
+        sqlx::query_as::<_, SupplierPrice>(&format!(
+            r#"
+            INSERT INTO supplier_prices (supplier_id, car_id, unit_cost, min_order_qty, lead_time_days)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (supplier_id, car_id)
+            DO UPDATE SET
+                unit_cost = EXCLUDED.unit_cost,
+                min_order_qty = EXCLUDED.min_order_qty,
+                lead_time_days = EXCLUDED.lead_time_days,
+                updated_at = NOW()
+            RETURNING {SUPPLIER_PRICE_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(car_id.as_str())
+        .bind(BigDecimal::from_f64(price.unit_cost).map(|cost| cost.round(2)))
+        .bind(price.min_order_qty)
+        .bind(price.lead_time_days)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "supplier_prices"))
+        .await
+    }
+
+    async fn create_purchase_order(
+        &self,
+        supplier_id: &SupplierId,
+        warehouse_id: &WarehouseId,
+        notes: Option<&str>,
+        lines: &[NewPurchaseOrderLine],
+    ) -> Result<PurchaseOrderResponse, PurchaseOrderError> {
+        let mut tx = self.pool.get().begin().await?;
+
+        let supplier_minimum: Option<i32> = sqlx::query_scalar(
+            "SELECT min_order_qty FROM suppliers WHERE supplier_id = $1 AND is_active = true",
+        )
+        .bind(supplier_id)
+        .fetch_optional(&mut *tx)
+        .instrument(db_span("SELECT", "suppliers"))
+        .await?;
+
+        let supplier_minimum = supplier_minimum
+            .ok_or_else(|| PurchaseOrderError::SupplierNotFound(supplier_id.to_string()))?;
+
+        let warehouse_exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1
+                FROM warehouses
+                WHERE warehouse_id = $1
+                    AND is_active = true
+            )
+            "#,
+        )
+        .bind(warehouse_id)
+        .fetch_one(&mut *tx)
+        .instrument(db_span("SELECT", "warehouses"))
+        .await?;
+
+        if !warehouse_exists {
+            return Err(PurchaseOrderError::WarehouseNotFound(
+                warehouse_id.to_string(),
+            ));
+        }
+
+        let order = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            r#"
+            INSERT INTO purchase_orders (supplier_id, warehouse_id, status, notes)
+            VALUES ($1, $2, 'Draft', $3)
+            RETURNING {PURCHASE_ORDER_COLUMNS}
+            "#
+        ))
+        .bind(supplier_id)
+        .bind(warehouse_id)
+        .bind(notes)
+        .fetch_one(&mut *tx)
+        .instrument(db_span("INSERT", "purchase_orders"))
+        .await?;
+
+        for line in lines {
+            let price: Option<(Option<BigDecimal>, Option<i32>)> = sqlx::query_as(
+                r#"
+                SELECT sp.unit_cost, sp.min_order_qty
+                FROM cars c
+                LEFT JOIN supplier_prices sp
+                    ON sp.car_id = c.car_id
+                    AND sp.supplier_id = $1
+                WHERE c.car_id = $2
+                    AND c.deleted_at IS NULL
+                "#,
+            )
+            .bind(supplier_id)
+            .bind(line.car_id.as_str())
+            .fetch_optional(&mut *tx)
+            .instrument(db_span("SELECT", "supplier_prices"))
+            .await?;
+
+            let (listed_cost, line_minimum) =
+                price.ok_or_else(|| PurchaseOrderError::CarNotFound(line.car_id.to_string()))?;
+
+            let unit_cost = line.unit_cost.clone().or(listed_cost).ok_or_else(|| {
+                PurchaseOrderError::NoSupplierPrice {
+                    supplier_id: supplier_id.to_string(),
+                    car_id: line.car_id.to_string(),
+                }
+            })?;
+
+            let minimum = line_minimum.unwrap_or(supplier_minimum);
+            if line.quantity < minimum {
+                return Err(PurchaseOrderError::BelowMinimumOrder {
+                    car_id: line.car_id.to_string(),
+                    minimum,
+                    requested: line.quantity,
+                });
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO purchase_order_lines (po_id, car_id, quantity, unit_cost, note)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(order.po_id)
+            .bind(line.car_id.as_str())
+            .bind(line.quantity)
+            .bind(unit_cost)
+            .bind(&line.note)
+            .execute(&mut *tx)
+            .instrument(db_span("INSERT", "purchase_order_lines"))
+            .await?;
+        }
+
+        let lines = Self::fetch_lines(&mut tx, order.po_id).await?;
+        tx.commit().await?;
+
+        Ok(PurchaseOrderResponse::new(order, lines))
+    }
+
+    async fn list_purchase_orders(
+        &self,
+        status: Option<PurchaseOrderStatus>,
+        supplier_id: Option<&SupplierId>,
+        origin: Option<&str>,
+        limit: i64,
+    ) -> SqlxResult<Vec<PurchaseOrder>> {
+        sqlx::query_as::<_, PurchaseOrder>(&format!(
+            r#"
+            SELECT {PURCHASE_ORDER_COLUMNS}
+            FROM purchase_orders
+            WHERE ($1::purchase_order_status IS NULL OR status = $1)
+                AND ($2::varchar IS NULL OR supplier_id = $2)
+                AND ($3::varchar IS NULL OR origin = $3)
+            ORDER BY created_at DESC
+            LIMIT $4
+            "#
+        ))
+        .bind(status)
+        .bind(supplier_id)
+        .bind(origin)
+        .bind(limit)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "purchase_orders"))
+        .await
+    }
+
+    async fn find_purchase_order(&self, po_id: Uuid) -> SqlxResult<Option<PurchaseOrderResponse>> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        let order = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            "SELECT {PURCHASE_ORDER_COLUMNS} FROM purchase_orders WHERE po_id = $1"
+        ))
+        .bind(po_id)
+        .fetch_optional(&mut *conn)
+        .instrument(db_span("SELECT", "purchase_orders"))
+        .await?;
+
+        let Some(order) = order else {
+            return Ok(None);
+        };
+
+        let lines = Self::fetch_lines(&mut conn, po_id).await?;
+        Ok(Some(PurchaseOrderResponse::new(order, lines)))
+    }
+
+    async fn submit_purchase_order(
+        &self,
+        po_id: Uuid,
+    ) -> Result<PurchaseOrder, PurchaseOrderError> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        let submitted = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            r#"
+            UPDATE purchase_orders po
+            SET
+                status = 'Submitted',
+                submitted_at = NOW(),
+                expected_at = NOW() + make_interval(days => (
+                    SELECT MAX(COALESCE(sp.lead_time_days, s.lead_time_days))
+                    FROM purchase_order_lines l
+                    JOIN suppliers s ON s.supplier_id = po.supplier_id
+                    LEFT JOIN supplier_prices sp
+                        ON sp.supplier_id = po.supplier_id
+                        AND sp.car_id = l.car_id
+                    WHERE l.po_id = po.po_id
+                ))
+            WHERE po_id = $1
+                AND status = 'Draft'
+            RETURNING {PURCHASE_ORDER_COLUMNS}
+            "#
+        ))
+        .bind(po_id)
+        .fetch_optional(&mut *conn)
+        .instrument(db_span("UPDATE", "purchase_orders"))
+        .await?;
+
+        match submitted {
+            Some(order) => Ok(order),
+            None => Err(Self::transition_error(&mut conn, po_id, "Draft").await),
+        }
+    }
+
+    async fn cancel_purchase_order(
+        &self,
+        po_id: Uuid,
+    ) -> Result<PurchaseOrder, PurchaseOrderError> {
+        let mut conn = self.pool.get().acquire().await?;
+
+        let cancelled = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            r#"
+            UPDATE purchase_orders
+            SET
+                status = 'Cancelled',
+                cancelled_at = NOW()
+            WHERE po_id = $1
+                AND status IN ('Draft', 'Submitted')
+            RETURNING {PURCHASE_ORDER_COLUMNS}
+            "#
+        ))
+        .bind(po_id)
+        .fetch_optional(&mut *conn)
+        .instrument(db_span("UPDATE", "purchase_orders"))
+        .await?;
+
+        match cancelled {
+            Some(order) => Ok(order),
+            None => Err(Self::transition_error(&mut conn, po_id, "Draft or Submitted").await),
+        }
+    }
+
+    async fn receive_goods(
+        &self,
+        po_id: Uuid,
+        lines: &[(CarId, i32)],
+    ) -> Result<PurchaseOrderResponse, PurchaseOrderError> {
+        let mut tx = self.pool.get().begin().await?;
+
+        let order = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            "SELECT {PURCHASE_ORDER_COLUMNS} FROM purchase_orders WHERE po_id = $1 FOR UPDATE"
+        ))
+        .bind(po_id)
+        .fetch_optional(&mut *tx)
+        .instrument(db_span("SELECT", "purchase_orders"))
+        .await?
+        .ok_or(PurchaseOrderError::PurchaseOrderNotFound(po_id))?;
+
+        if !matches!(
+            order.status,
+            PurchaseOrderStatus::Submitted | PurchaseOrderStatus::PartiallyReceived
+        ) {
+            return Err(PurchaseOrderError::InvalidState {
+                expected: "Submitted or PartiallyReceived".to_string(),
+                found: format!("{:?}", order.status),
+            });
+        }
+
+        for (car_id, quantity) in lines {
+            let outstanding: Option<i32> = sqlx::query_scalar(
+                r#"
+                SELECT quantity - received_quantity
+                FROM purchase_order_lines
+                WHERE po_id = $1
+                    AND car_id = $2
+                FOR UPDATE
+                "#,
+            )
+            .bind(po_id)
+            .bind(car_id.as_str())
+            .fetch_optional(&mut *tx)
+            .instrument(db_span("SELECT", "purchase_order_lines"))
+            .await?;
+
+            let outstanding =
+                outstanding.ok_or_else(|| PurchaseOrderError::LineNotFound(car_id.to_string()))?;
+
+            if *quantity > outstanding {
+                return Err(PurchaseOrderError::OverReceipt {
+                    car_id: car_id.to_string(),
+                    outstanding,
+                    received: *quantity,
+                });
+            }
+
+            sqlx::query(
+                r#"
+                WITH line AS (
+                    UPDATE purchase_order_lines
+                    SET received_quantity = received_quantity + $3
+                    WHERE po_id = $1
+                        AND car_id = $2
+                    RETURNING car_id
+                ),
+                location AS (
+                    INSERT INTO stock_locations (
+                        warehouse_id,
+                        car_id,
+                        zone,
+                        quantity,
+                        reserved_quantity,
+                        last_updated
+                    )
+                    SELECT $4, car_id, 'RECEIVING', $3, 0, NOW()
+                    FROM line
+                    ON CONFLICT (warehouse_id, car_id)
+                    DO UPDATE SET
+                        quantity = stock_locations.quantity + EXCLUDED.quantity,
+                        last_updated = NOW()
+                    RETURNING car_id
+                ),
+                car AS (
+                    UPDATE cars
+                    SET quantity_in_stock = quantity_in_stock + $3
+                    WHERE car_id = (SELECT car_id FROM location)
+                    RETURNING car_id
+                )
+                INSERT INTO goods_receipts (po_id, warehouse_id, car_id, quantity)
+                SELECT $1, $4, car_id, $3
+                FROM car
+                "#,
+            )
+            .bind(po_id)
+            .bind(car_id.as_str())
+            .bind(quantity)
+            .bind(&order.warehouse_id)
+            .execute(&mut *tx)
+            .instrument(db_span("INSERT", "goods_receipts"))
+            .await?;
+        }
+
+        let order = sqlx::query_as::<_, PurchaseOrder>(&format!(
+            r#"
+            UPDATE purchase_orders po
+            SET
+                status = CASE WHEN fully_received THEN 'Received' ELSE 'PartiallyReceived' END::purchase_order_status,
+                received_at = CASE WHEN fully_received THEN NOW() END
+            FROM (
+                SELECT bool_and(received_quantity = quantity) AS fully_received
+                FROM purchase_order_lines
+                WHERE po_id = $1
+            ) progress
+            WHERE po.po_id = $1
+            RETURNING {PURCHASE_ORDER_COLUMNS}
+            "#
+        ))
+        .bind(po_id)
+        .fetch_one(&mut *tx)
+        .instrument(db_span("UPDATE", "purchase_orders"))
+        .await?;
+
+        let lines = Self::fetch_lines(&mut tx, po_id).await?;
+        tx.commit().await?;
+
+        Ok(PurchaseOrderResponse::new(order, lines))
+    }
+
+    async fn list_replenishment_candidates(
+        &self,
+        car_ids: &[CarId],
+    ) -> SqlxResult<Vec<ReplenishmentCandidate>> {
+        sqlx::query_as::<_, ReplenishmentCandidate>(
+            r#"
+            SELECT
+                c.car_id,
+                best.supplier_id,
+                best.unit_cost,
+                best.min_order_qty,
+                home.warehouse_id,
+                COALESCE(po.on_order, 0) AS on_order,
+                COALESCE(tr.in_transit, 0) AS in_transit
+            FROM cars c
+            CROSS JOIN LATERAL (
+                SELECT
+                    sp.supplier_id,
+                    sp.unit_cost,
+                    COALESCE(sp.min_order_qty, s.min_order_qty) AS min_order_qty
+                FROM supplier_prices sp
+                JOIN suppliers s ON s.supplier_id = sp.supplier_id
+                WHERE sp.car_id = c.car_id
+                    AND s.is_active = true
+                ORDER BY
+                    sp.unit_cost,
+                    COALESCE(sp.lead_time_days, s.lead_time_days),
+                    sp.supplier_id
+                LIMIT 1
+            ) best
+            CROSS JOIN LATERAL (
+                SELECT w.warehouse_id
+                FROM warehouses w
+                LEFT JOIN stock_locations sl
+                    ON sl.warehouse_id = w.warehouse_id
+                    AND sl.car_id = c.car_id
+                WHERE w.is_active = true
+                ORDER BY
+                    COALESCE(sl.quantity, -1) DESC,
+                    w.capacity_total - COALESCE(w.capacity_used, 0) DESC,
+                    w.warehouse_id
+                LIMIT 1
+            ) home
+            LEFT JOIN LATERAL (
+                SELECT SUM(l.quantity - l.received_quantity)::bigint AS on_order
+                FROM purchase_order_lines l
+                JOIN purchase_orders p ON p.po_id = l.po_id
+                WHERE l.car_id = c.car_id
+                    AND p.status IN ('Draft', 'Submitted', 'PartiallyReceived')
+            ) po ON true
+            LEFT JOIN LATERAL (
+                SELECT SUM(t.quantity)::bigint AS in_transit
+                FROM transfer_orders t
+                WHERE t.car_id = c.car_id
+                    AND t.to_warehouse_id = home.warehouse_id
+                    AND t.status IN ('Pending', 'InTransit')
+            ) tr ON true
+            WHERE c.car_id = ANY($1)
+                AND c.deleted_at IS NULL
+            ORDER BY c.car_id
+            "#,
+        )
+        .bind(car_ids)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "supplier_prices"))
+        .await
+    }
+
+    async fn draft_replenishment_order(
+        &self,
+        supplier_id: &SupplierId,
+        warehouse_id: &WarehouseId,
+        lines: &[NewPurchaseOrderLine],
+    ) -> SqlxResult<u64> {
+        let mut tx = self.pool.get().begin().await?;
+
+        // The no-op update makes RETURNING yield the existing draft.
+        let po_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO purchase_orders (supplier_id, warehouse_id, status, origin, notes)
+            VALUES ($1, $2, 'Draft', $3, 'Raised from critical stock alerts')
+            ON CONFLICT (supplier_id, warehouse_id)
+                WHERE status = 'Draft' AND origin = 'replenishment'
+            DO UPDATE SET origin = EXCLUDED.origin
+            RETURNING po_id
+            "#,
+        )
+        .bind(supplier_id)
+        .bind(warehouse_id)
+        .bind(PURCHASE_ORDER_ORIGIN_REPLENISHMENT)
+        .fetch_one(&mut *tx)
+        .instrument(db_span("INSERT", "purchase_orders"))
+        .await?;
+
+        let mut added = 0;
+        for line in lines {
+            added += sqlx::query(
+                r#"
+                INSERT INTO purchase_order_lines (po_id, car_id, quantity, unit_cost, note)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (po_id, car_id) DO NOTHING
+                "#,
+            )
+            .bind(po_id)
+            .bind(line.car_id.as_str())
+            .bind(line.quantity)
+            .bind(&line.unit_cost)
+            .bind(&line.note)
+            .execute(&mut *tx)
+            .instrument(db_span("INSERT", "purchase_order_lines"))
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(added)
+    }
+}
+
+#[async_trait]
+pub trait RebalancingRepository: Send + Sync {
+    async fn list_location_demand(&self, demand_days: i32) -> SqlxResult<Vec<StockLocationDemand>>;
+
+    /// Supersedes all open proposals and stores the new ones.
+    async fn replace_proposals(
+        &self,
+        proposals: &[TransferProposal],
+    ) -> SqlxResult<Vec<RebalancingProposal>>;
+
+    async fn list_proposals(
+        &self,
+        status: Option<RebalancingStatus>,
+    ) -> SqlxResult<Vec<RebalancingProposal>>;
+
+    async fn find_proposal(&self, proposal_id: Uuid) -> SqlxResult<Option<RebalancingProposal>>;
+
+    /// Moves a proposal from `from` to `to`; `None` when it was not in `from`.
+    async fn set_proposal_status(
+        &self,
+        proposal_id: Uuid,
+        from: RebalancingStatus,
+        to: RebalancingStatus,
+        transfer_id: Option<Uuid>,
+    ) -> SqlxResult<Option<RebalancingProposal>>;
+}
+
+pub struct PgRebalancingRepository {
+    pool: PoolHandle,
+}
+
+impl PgRebalancingRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+const REBALANCING_PROPOSAL_COLUMNS: &str = "proposal_id, car_id, from_warehouse_id, \
+     to_warehouse_id, quantity, distance_km, destination_cover_days, destination_cover_days_after, \
+     status, transfer_id, created_at, decided_at";
+
+#[async_trait]
+impl RebalancingRepository for PgRebalancingRepository {
+    async fn list_location_demand(&self, demand_days: i32) -> SqlxResult<Vec<StockLocationDemand>> {
+        // Demand is what each warehouse fulfilled. Sales without fulfilment
+        // rows (from before it was tracked) are spread over the car's
+        // locations. Warehouses that sold the car but no longer hold a stock
+        // row are included with nothing available, so they can receive
+        // transfers.
+        sqlx::query_as::<_, StockLocationDemand>(
+            r#"
+            WITH recent AS (
+                SELECT id, car_id, quantity
+                FROM sales_history
+                WHERE sold_at > NOW() - make_interval(days => $1)
+            ),
+            fulfilled AS (
+                SELECT r.car_id, f.warehouse_id, SUM(f.quantity)::float8 / $1 AS daily_demand
+                FROM recent r
+                JOIN sale_fulfilments f ON f.sale_id = r.id
+                GROUP BY r.car_id, f.warehouse_id
+            ),
+            unattributed AS (
+                SELECT r.car_id, SUM(r.quantity)::float8 / $1 AS daily_demand
+                FROM recent r
+                WHERE NOT EXISTS (SELECT 1 FROM sale_fulfilments f WHERE f.sale_id = r.id)
+                GROUP BY r.car_id
+            ),
+            locations AS (
+                SELECT car_id, warehouse_id FROM stock_locations
+                UNION
+                SELECT car_id, warehouse_id FROM fulfilled
+            ),
+            occupancy AS (
+                SELECT warehouse_id, SUM(quantity)::bigint AS units
+                FROM stock_locations
+                GROUP BY warehouse_id
+            )
+            SELECT
+                l.car_id,
+                l.warehouse_id,
+                w.latitude::float8 AS latitude,
+                w.longitude::float8 AS longitude,
+                COALESCE(sl.quantity - sl.reserved_quantity, 0) AS available,
+                COALESCE(s.daily_demand, 0.0)
+                    + COALESCE(u.daily_demand, 0.0) / COUNT(*) OVER (PARTITION BY l.car_id)
+                    AS daily_demand,
+                w.capacity_total - COALESCE(o.units, 0) AS free_capacity
+            FROM locations l
+            JOIN warehouses w ON w.warehouse_id = l.warehouse_id AND w.is_active = true
+            JOIN cars c ON c.car_id = l.car_id AND c.deleted_at IS NULL
+            LEFT JOIN stock_locations sl
+                ON sl.car_id = l.car_id AND sl.warehouse_id = l.warehouse_id
+            LEFT JOIN fulfilled s ON s.car_id = l.car_id AND s.warehouse_id = l.warehouse_id
+            LEFT JOIN unattributed u ON u.car_id = l.car_id
+            LEFT JOIN occupancy o ON o.warehouse_id = l.warehouse_id
+            ORDER BY l.car_id, l.warehouse_id
+            "#,
+        )
+        .bind(demand_days)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "stock_locations"))
+        .await
+    }
+
+    async fn replace_proposals(
+        &self,
+        proposals: &[TransferProposal],
+    ) -> SqlxResult<Vec<RebalancingProposal>> {
+        let mut tx = self.pool.get().begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE rebalancing_proposals
+            SET status = 'Superseded', decided_at = NOW()
+            WHERE status = 'Proposed'
+            "#,
+        )
+        .execute(&mut *tx)
+        .instrument(db_span("UPDATE", "rebalancing_proposals"))
+        .await?;
+
+        let mut stored = Vec::with_capacity(proposals.len());
+        for proposal in proposals {
+            let row = sqlx::query_as::<_, RebalancingProposal>(&format!(
+                r#"
+                INSERT INTO rebalancing_proposals (
+                    car_id, from_warehouse_id, to_warehouse_id, quantity, distance_km,
+                    destination_cover_days, destination_cover_days_after
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING {REBALANCING_PROPOSAL_COLUMNS}
+                "#
+            ))
+            .bind(proposal.car_id.as_str())
+            .bind(&proposal.from_warehouse_id)
+            .bind(&proposal.to_warehouse_id)
+            .bind(proposal.quantity)
+            .bind(proposal.distance_km)
+            .bind(proposal.destination_cover_days)
+            .bind(proposal.destination_cover_days_after)
+            .fetch_one(&mut *tx)
+            .instrument(db_span("INSERT", "rebalancing_proposals"))
+            .await?;
+            stored.push(row);
+        }
+
+        tx.commit().await?;
+        Ok(stored)
+    }
+
+    async fn list_proposals(
+        &self,
+        status: Option<RebalancingStatus>,
+    ) -> SqlxResult<Vec<RebalancingProposal>> {
+        sqlx::query_as::<_, RebalancingProposal>(&format!(
+            r#"
+            SELECT {REBALANCING_PROPOSAL_COLUMNS}
+            FROM rebalancing_proposals
+            WHERE ($1::rebalancing_status IS NULL OR status = $1)
+            ORDER BY created_at DESC, destination_cover_days
+            LIMIT 500
+            "#
+        ))
+        .bind(status)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "rebalancing_proposals"))
+        .await
+    }
+
+    async fn find_proposal(&self, proposal_id: Uuid) -> SqlxResult<Option<RebalancingProposal>> {
+        sqlx::query_as::<_, RebalancingProposal>(&format!(
+            "SELECT {REBALANCING_PROPOSAL_COLUMNS} FROM rebalancing_proposals WHERE proposal_id = $1"
+        ))
+        .bind(proposal_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "rebalancing_proposals"))
+        .await
+    }
+
+    async fn set_proposal_status(
+        &self,
+        proposal_id: Uuid,
+        from: RebalancingStatus,
+        to: RebalancingStatus,
+        transfer_id: Option<Uuid>,
+    ) -> SqlxResult<Option<RebalancingProposal>> {
+        sqlx::query_as::<_, RebalancingProposal>(&format!(
+            r#"
+            UPDATE rebalancing_proposals
+            SET
+                status = $3,
+                transfer_id = COALESCE($4, transfer_id),
+                decided_at = CASE WHEN $3 = 'Proposed'::rebalancing_status THEN NULL ELSE NOW() END
+            WHERE proposal_id = $1
+                AND status = $2
+            RETURNING {REBALANCING_PROPOSAL_COLUMNS}
+            "#
+        ))
+        .bind(proposal_id)
+        .bind(from)
+        .bind(to)
+        .bind(transfer_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("UPDATE", "rebalancing_proposals"))
+        .await
+    }
+}
+
+#[async_trait]
+pub trait ClassificationRepository: Send + Sync {
+    /// Revenue and weekly demand of every active car over the last `weeks` weeks.
+    async fn list_classification_inputs(&self, weeks: i32) -> SqlxResult<Vec<ClassificationInput>>;
+
+    /// Stores the classes on the cars; returns how many cars changed class.
+    async fn apply_classifications(&self, classifications: &[CarClassification])
+    -> SqlxResult<u64>;
+
+    async fn list_alert_thresholds(&self) -> SqlxResult<Vec<AlertThreshold>>;
+
+    async fn set_alert_threshold(
+        &self,
+        abc_class: AbcClass,
+        critical_ratio: f64,
+        warning_ratio: f64,
+    ) -> SqlxResult<AlertThreshold>;
+}
+
+pub struct PgClassificationRepository {
+    pool: PoolHandle,
+}
+
+impl PgClassificationRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ClassificationRepository for PgClassificationRepository {
+    async fn list_classification_inputs(&self, weeks: i32) -> SqlxResult<Vec<ClassificationInput>> {
+        sqlx::query_as::<_, ClassificationInput>(
+            r#"
+            WITH weekly AS (
+                SELECT
+                    car_id,
+                    FLOOR(EXTRACT(EPOCH FROM NOW() - sold_at) / 604800)::int AS weeks_ago,
+                    SUM(quantity)::bigint AS units
+                FROM sales_history
+                WHERE sold_at > NOW() - make_interval(weeks => $1)
+                GROUP BY 1, 2
+            )
+            SELECT
+                c.car_id,
+                c.brand,
+                c.model,
+                (COALESCE(SUM(w.units), 0) * c.price)::numeric(18, 2) AS revenue,
+                COALESCE(SUM(w.units), 0)::bigint AS units_sold,
+                ARRAY_AGG(COALESCE(w.units, 0) ORDER BY weeks.weeks_ago DESC) AS weekly_units
+            FROM cars c
+            CROSS JOIN generate_series(0, $1 - 1) AS weeks(weeks_ago)
+            LEFT JOIN weekly w ON w.car_id = c.car_id AND w.weeks_ago = weeks.weeks_ago
+            WHERE c.deleted_at IS NULL
+            GROUP BY c.car_id
+            ORDER BY c.car_id
+            "#,
+        )
+        .bind(weeks)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "sales_history"))
+        .await
+    }
+
+    async fn apply_classifications(
+        &self,
+        classifications: &[CarClassification],
+    ) -> SqlxResult<u64> {
+        let mut tx = self.pool.get().begin().await?;
+        let mut changed = 0;
+
+        for classification in classifications {
+            changed += sqlx::query(
+                r#"
+                UPDATE cars
+                SET
+                    abc_class = $2,
+                    xyz_class = $3,
+                    classification_updated_at = NOW()
+                WHERE car_id = $1
+                    AND deleted_at IS NULL
+                    AND (abc_class, xyz_class) IS DISTINCT FROM ($2, $3)
+                "#,
+            )
+            .bind(classification.car_id.as_str())
+            .bind(classification.abc_class)
+            .bind(classification.xyz_class)
+            .execute(&mut *tx)
+            .instrument(db_span("UPDATE", "cars"))
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        Ok(changed)
+    }
+
+    async fn list_alert_thresholds(&self) -> SqlxResult<Vec<AlertThreshold>> {
+        sqlx::query_as::<_, AlertThreshold>(
+            r#"
+            SELECT abc_class, critical_ratio, warning_ratio, updated_at
+            FROM alert_thresholds
+            ORDER BY abc_class
+            "#,
+        )
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "alert_thresholds"))
+        .await
+    }
+
+    async fn set_alert_threshold(
+        &self,
+        abc_class: AbcClass,
+        critical_ratio: f64,
+        warning_ratio: f64,
+    ) -> SqlxResult<AlertThreshold> {
+        sqlx::query_as::<_, AlertThreshold>(
+            r#"
+            INSERT INTO alert_thresholds (abc_class, critical_ratio, warning_ratio)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (abc_class) DO UPDATE
+            SET
+                critical_ratio = EXCLUDED.critical_ratio,
+                warning_ratio = EXCLUDED.warning_ratio,
+                updated_at = NOW()
+            RETURNING abc_class, critical_ratio, warning_ratio, updated_at
+            "#,
+        )
+        .bind(abc_class)
+        .bind(critical_ratio)
+        .bind(warning_ratio)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "alert_thresholds"))
+        .await
+    }
+}
+
+#[async_trait]
+pub trait AgingRepository: Send + Sync {
+    /// Active cars with stock, their goods receipts and units sold over the
+    /// last `sales_days` days.
+    async fn list_aging_inputs(&self, sales_days: i32) -> SqlxResult<Vec<AgingInput>>;
+
+    /// The same for one car; `None` when it is deleted or out of stock.
+    async fn find_aging_input(
+        &self,
+        car_id: &CarId,
+        sales_days: i32,
+    ) -> SqlxResult<Option<AgingInput>>;
+}
+
+pub struct PgAgingRepository {
+    pool: PoolHandle,
+}
+
+impl PgAgingRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_aging_inputs(
+        &self,
+        sales_days: i32,
+        car_id: Option<&CarId>,
+    ) -> SqlxResult<Vec<AgingInput>> {
+        sqlx::query_as::<_, AgingInput>(
+            r#"
+            WITH sales AS (
+                SELECT car_id, SUM(quantity)::bigint AS units_sold
+                FROM sales_history
+                WHERE sold_at > NOW() - make_interval(days => $1)
+                GROUP BY car_id
+            )
+            SELECT
+                c.car_id,
+                c.brand,
+                c.model,
+                c.price,
+                c.quantity_in_stock AS units_on_hand,
+                c.created_at AS stocked_since,
+                COALESCE(r.receipt_dates, '{}') AS receipt_dates,
+                COALESCE(r.receipt_quantities, '{}') AS receipt_quantities,
+                COALESCE(s.units_sold, 0) AS units_sold
+            FROM cars c
+            LEFT JOIN LATERAL (
+                SELECT
+                    ARRAY_AGG(g.received_at ORDER BY g.received_at DESC) AS receipt_dates,
+                    ARRAY_AGG(g.quantity ORDER BY g.received_at DESC) AS receipt_quantities
+                FROM goods_receipts g
+                WHERE g.car_id = c.car_id
+            ) r ON true
+            LEFT JOIN sales s ON s.car_id = c.car_id
+            WHERE c.deleted_at IS NULL
+                AND c.quantity_in_stock > 0
+                AND ($2::VARCHAR IS NULL OR c.car_id = $2)
+            ORDER BY c.car_id
+            "#,
+        )
+        .bind(sales_days)
+        .bind(car_id)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "goods_receipts"))
+        .await
+    }
+}
+
+#[async_trait]
+impl AgingRepository for PgAgingRepository {
+    async fn list_aging_inputs(&self, sales_days: i32) -> SqlxResult<Vec<AgingInput>> {
+        self.fetch_aging_inputs(sales_days, None).await
+    }
+
+    async fn find_aging_input(
+        &self,
+        car_id: &CarId,
+        sales_days: i32,
+    ) -> SqlxResult<Option<AgingInput>> {
+        Ok(self
+            .fetch_aging_inputs(sales_days, Some(car_id))
+            .await?
+            .into_iter()
+            .next())
+    }
+}
+
+#[async_trait]
+pub trait PriceHistoryRepository: Send + Sync {
+    async fn find_current_price(&self, car_id: &CarId) -> SqlxResult<Option<BigDecimal>>;
+
+    /// Price changes of a car, newest first, with units sold up to
+    /// `window_days` before and after each one.
+    async fn list_price_changes(
+        &self,
+        car_id: &CarId,
+        window_days: i32,
+    ) -> SqlxResult<Vec<PriceChangeRow>>;
+
+    async fn list_pending_changes(&self, car_id: &CarId) -> SqlxResult<Vec<ScheduledPriceChange>>;
+
+    async fn find_scheduled_change(
+        &self,
+        car_id: &CarId,
+        change_id: Uuid,
+    ) -> SqlxResult<Option<ScheduledPriceChange>>;
+
+    async fn schedule_change(
+        &self,
+        car_id: &CarId,
+        dto: &SchedulePriceChangeDto,
+    ) -> SqlxResult<ScheduledPriceChange>;
+
+    /// Cancels a change that is still pending.
+    async fn cancel_scheduled_change(
+        &self,
+        car_id: &CarId,
+        change_id: Uuid,
+    ) -> SqlxResult<Option<ScheduledPriceChange>>;
+
+    /// Sets the price of every car with a due change, oldest first, in one
+    /// transaction. Changes for deleted cars are cancelled.
+    async fn apply_due_changes(&self, limit: i64) -> SqlxResult<Vec<ScheduledPriceChange>>;
+}
+
+pub struct PgPriceHistoryRepository {
+    pool: PoolHandle,
+}
+
+impl PgPriceHistoryRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+const SCHEDULED_PRICE_CHANGE_COLUMNS: &str =
+    "change_id, car_id, new_price, effective_at, reason, status, created_at, applied_at";
+
+#[async_trait]
+impl PriceHistoryRepository for PgPriceHistoryRepository {
+    async fn find_current_price(&self, car_id: &CarId) -> SqlxResult<Option<BigDecimal>> {
+        sqlx::query_scalar::<_, BigDecimal>(
+            "SELECT price FROM cars WHERE car_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(car_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "cars"))
+        .await
+    }
+
+    async fn list_price_changes(
+        &self,
+        car_id: &CarId,
+        window_days: i32,
+    ) -> SqlxResult<Vec<PriceChangeRow>> {
+        sqlx::query_as::<_, PriceChangeRow>(
+            r#"
+            WITH changes AS (
+                SELECT
+                    id,
+                    old_price,
+                    new_price,
+                    source,
+                    scheduled_change_id,
+                    changed_at,
+                    GREATEST(
+                        changed_at - make_interval(days => $2),
+                        COALESCE(LAG(changed_at) OVER w, '-infinity')
+                    ) AS before_from,
+                    LEAST(
+                        changed_at + make_interval(days => $2),
+                        COALESCE(LEAD(changed_at) OVER w, 'infinity'),
+                        NOW()
+                    ) AS after_to
+                FROM price_history
+                WHERE car_id = $1
+                WINDOW w AS (ORDER BY changed_at)
+            )
+            SELECT
+                c.id,
+                c.old_price,
+                c.new_price,
+                c.source,
+                c.scheduled_change_id,
+                c.changed_at,
+                EXTRACT(EPOCH FROM c.changed_at - c.before_from)::float8 / 86400 AS days_before,
+                COALESCE(before.units, 0) AS units_before,
+                GREATEST(EXTRACT(EPOCH FROM c.after_to - c.changed_at)::float8, 0) / 86400
+                    AS days_after,
+                COALESCE(after.units, 0) AS units_after
+            FROM changes c
+            LEFT JOIN LATERAL (
+                SELECT SUM(s.quantity)::bigint AS units
+                FROM sales_history s
+                WHERE s.car_id = $1
+                    AND s.sold_at >= c.before_from
+                    AND s.sold_at < c.changed_at
+            ) before ON true
+            LEFT JOIN LATERAL (
+                SELECT SUM(s.quantity)::bigint AS units
+                FROM sales_history s
+                WHERE s.car_id = $1
+                    AND s.sold_at >= c.changed_at
+                    AND s.sold_at < c.after_to
+            ) after ON true
+            ORDER BY c.changed_at DESC
+            LIMIT 100
+            "#,
+        )
+        .bind(car_id)
+        .bind(window_days)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "price_history"))
+        .await
+    }
+
+    async fn list_pending_changes(&self, car_id: &CarId) -> SqlxResult<Vec<ScheduledPriceChange>> {
+        sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+            r#"
+            SELECT {SCHEDULED_PRICE_CHANGE_COLUMNS}
+            FROM scheduled_price_changes
+            WHERE car_id = $1
+                AND status = 'Pending'
+            ORDER BY effective_at, created_at
+            "#
+        ))
+        .bind(car_id)
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "scheduled_price_changes"))
+        .await
+    }
+
+    async fn find_scheduled_change(
+        &self,
+        car_id: &CarId,
+        change_id: Uuid,
+    ) -> SqlxResult<Option<ScheduledPriceChange>> {
+        sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+            r#"
+            SELECT {SCHEDULED_PRICE_CHANGE_COLUMNS}
+            FROM scheduled_price_changes
+            WHERE change_id = $1
+                AND car_id = $2
+            "#
+        ))
+        .bind(change_id)
+        .bind(car_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("SELECT", "scheduled_price_changes"))
+        .await
+    }
+
+    async fn schedule_change(
+        &self,
+        car_id: &CarId,
+        dto: &SchedulePriceChangeDto,
+    ) -> SqlxResult<ScheduledPriceChange> {
+        sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+            r#"
+            INSERT INTO scheduled_price_changes (car_id, new_price, effective_at, reason)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {SCHEDULED_PRICE_CHANGE_COLUMNS}
+            "#
+        ))
+        .bind(car_id)
+        .bind(&dto.new_price)
+        .bind(dto.effective_at)
+        .bind(&dto.reason)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "scheduled_price_changes"))
+        .await
+    }
+
+    async fn cancel_scheduled_change(
+        &self,
+        car_id: &CarId,
+        change_id: Uuid,
+    ) -> SqlxResult<Option<ScheduledPriceChange>> {
+        sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+            r#"
+            UPDATE scheduled_price_changes
+            SET status = 'Cancelled'
+            WHERE change_id = $1
+                AND car_id = $2
+                AND status = 'Pending'
+            RETURNING {SCHEDULED_PRICE_CHANGE_COLUMNS}
+            "#
+        ))
+        .bind(change_id)
+        .bind(car_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("UPDATE", "scheduled_price_changes"))
+        .await
+    }
+
+    async fn apply_due_changes(&self, limit: i64) -> SqlxResult<Vec<ScheduledPriceChange>> {
+        let mut tx = self.pool.get().begin().await?;
+
+        let due = sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+            r#"
+            SELECT {SCHEDULED_PRICE_CHANGE_COLUMNS}
+            FROM scheduled_price_changes
+            WHERE status = 'Pending'
+                AND effective_at <= NOW()
+            ORDER BY effective_at, created_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#
+        ))
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .instrument(db_span("SELECT", "scheduled_price_changes"))
+        .await?;
+
+        let mut processed = Vec::with_capacity(due.len());
+        for change in due {
+            // Read by the price history trigger to link the row to this change.
+            sqlx::query("SELECT set_config('app.scheduled_price_change_id', $1, true)")
+                .bind(change.change_id.to_string())
+                .execute(&mut *tx)
+                .await?;
+
+            let updated = sqlx::query(
+                r#"
+                UPDATE cars
+                SET price = $2
+                WHERE car_id = $1
+                    AND deleted_at IS NULL
+                "#,
+            )
+            .bind(&change.car_id)
+            .bind(&change.new_price)
+            .execute(&mut *tx)
+            .instrument(db_span("UPDATE", "cars"))
+            .await?;
+
+            let status = if updated.rows_affected() > 0 {
+                PriceChangeStatus::Applied
+            } else {
+                PriceChangeStatus::Cancelled
+            };
+            let row = sqlx::query_as::<_, ScheduledPriceChange>(&format!(
+                r#"
+                UPDATE scheduled_price_changes
+                SET
+                    status = $2,
+                    applied_at = CASE WHEN $2 = 'Applied'::price_change_status THEN NOW() END
+                WHERE change_id = $1
+                RETURNING {SCHEDULED_PRICE_CHANGE_COLUMNS}
+                "#
+            ))
+            .bind(change.change_id)
+            .bind(status)
+            .fetch_one(&mut *tx)
+            .instrument(db_span("UPDATE", "scheduled_price_changes"))
+            .await?;
+            processed.push(row);
+        }
+
+        tx.commit().await?;
+        Ok(processed)
+    }
+}
+
+#[async_trait]
+pub trait PricingRepository: Send + Sync {
+    async fn create_rule(&self, dto: &CreatePricingRuleDto) -> SqlxResult<PricingRule>;
+
+    /// Active rules first, then by priority.
+    async fn list_rules(&self) -> SqlxResult<Vec<PricingRule>>;
+
+    /// Active rules whose time window includes now.
+    async fn list_current_rules(&self) -> SqlxResult<Vec<PricingRule>>;
+
+    async fn deactivate_rule(&self, rule_id: Uuid) -> SqlxResult<Option<PricingRule>>;
+}
+
+pub struct PgPricingRepository {
+    pool: PoolHandle,
+}
+
+impl PgPricingRepository {
+    pub fn new(pool: PoolHandle) -> Self {
+        Self { pool }
+    }
+}
+
+const PRICING_RULE_COLUMNS: &str = "rule_id, name, promo_code, brand, engine_type, min_year, \
+     max_year, aging_bucket, min_quantity, discount_percent, discount_amount, floor_price, \
+     stackable, priority, starts_at, ends_at, is_active, created_at";
+
+#[async_trait]
+impl PricingRepository for PgPricingRepository {
+    async fn create_rule(&self, dto: &CreatePricingRuleDto) -> SqlxResult<PricingRule> {
+        sqlx::query_as::<_, PricingRule>(&format!(
+            r#"
+            INSERT INTO pricing_rules (
+                name, promo_code, brand, engine_type, min_year, max_year, aging_bucket,
+                min_quantity, discount_percent, discount_amount, floor_price, stackable,
+                priority, starts_at, ends_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            RETURNING {PRICING_RULE_COLUMNS}
+            "#
+        ))
+        .bind(&dto.name)
+        .bind(&dto.promo_code)
+        .bind(&dto.brand)
+        .bind(&dto.engine_type)
+        .bind(dto.min_year)
+        .bind(dto.max_year)
+        .bind(&dto.aging_bucket)
+        .bind(dto.min_quantity)
+        .bind(dto.discount_percent)
+        .bind(&dto.discount_amount)
+        .bind(&dto.floor_price)
+        .bind(dto.stackable)
+        .bind(dto.priority)
+        .bind(dto.starts_at)
+        .bind(dto.ends_at)
+        .fetch_one(&self.pool.get())
+        .instrument(db_span("INSERT", "pricing_rules"))
+        .await
+    }
+
+    async fn list_rules(&self) -> SqlxResult<Vec<PricingRule>> {
+        sqlx::query_as::<_, PricingRule>(&format!(
+            r#"
+            SELECT {PRICING_RULE_COLUMNS}
+            FROM pricing_rules
+            ORDER BY is_active DESC, priority DESC, created_at DESC
+            LIMIT 500
+            "#
+        ))
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "pricing_rules"))
+        .await
+    }
+
+    async fn list_current_rules(&self) -> SqlxResult<Vec<PricingRule>> {
+        sqlx::query_as::<_, PricingRule>(&format!(
+            r#"
+            SELECT {PRICING_RULE_COLUMNS}
+            FROM pricing_rules
+            WHERE is_active
+                AND (starts_at IS NULL OR starts_at <= NOW())
+                AND (ends_at IS NULL OR ends_at > NOW())
+            "#
+        ))
+        .fetch_all(&self.pool.get())
+        .instrument(db_span("SELECT", "pricing_rules"))
+        .await
+    }
+
+    async fn deactivate_rule(&self, rule_id: Uuid) -> SqlxResult<Option<PricingRule>> {
+        sqlx::query_as::<_, PricingRule>(&format!(
+            r#"
+            UPDATE pricing_rules
+            SET is_active = false
+            WHERE rule_id = $1
+            RETURNING {PRICING_RULE_COLUMNS}
+            "#
+        ))
+        .bind(rule_id)
+        .fetch_optional(&self.pool.get())
+        .instrument(db_span("UPDATE", "pricing_rules"))
+        .await
+    }
+}
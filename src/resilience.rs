@@ -0,0 +1,496 @@
+use std::future::Future;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sqlx::Result as SqlxResult;
+use uuid::Uuid;
+
+use crate::cache::QueryCache;
+use crate::circuit_breaker::{CircuitBreaker, CircuitError};
+use crate::error::{ReservationError, TransferError};
+use crate::models::{
+    CarEntity, CarFilter, CarId, CarSearchRequest, CarUpdateData, CreateCarDto,
+    CreateReservationDto, DailySalesRow, DepreciationInput, InventoryMetrics, InventoryStatusStat,
+    MetricsAggregation, MetricsHistoryPoint, MetricsHistoryRange, PaginationParams, Reservation,
+    SalesVelocity, StockAlertRow, TransferOrder, Warehouse, WarehouseId, WarehouseStockRow,
+};
+use crate::repositories::{
+    CarCommandRepository, CarQueryRepository, InventoryAnalyticsRepository, ReservationRepository,
+    WarehouseRepository,
+};
+use crate::uow::UnitOfWork;
+
+/// Errors that can be routed through a circuit breaker.
+///
+/// Only infrastructure failures should trip the breaker; domain outcomes such as
+/// a missing row or a constraint violation mean the database is healthy.
+pub trait BreakerFailure {
+    fn trips_breaker(&self) -> bool;
+    fn circuit_open() -> Self;
+}
+
+pub fn is_infrastructure_failure(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_)
+        | sqlx::Error::Tls(_)
+        | sqlx::Error::Protocol(_)
+        | sqlx::Error::PoolTimedOut
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        // 08: connection exception, 53: insufficient resources,
+        // 57: operator intervention (shutdown, statement timeout), 58: system error
+        sqlx::Error::Database(db_err) => db_err.code().is_some_and(|code| {
+            ["08", "53", "57", "58"]
+                .iter()
+                .any(|class| code.starts_with(class))
+        }),
+        _ => false,
+    }
+}
+
+impl BreakerFailure for sqlx::Error {
+    fn trips_breaker(&self) -> bool {
+        is_infrastructure_failure(self)
+    }
+
+    fn circuit_open() -> Self {
+        sqlx::Error::PoolTimedOut
+    }
+}
+
+impl BreakerFailure for ReservationError {
+    fn trips_breaker(&self) -> bool {
+        matches!(self, ReservationError::Database(e) if is_infrastructure_failure(e))
+    }
+
+    fn circuit_open() -> Self {
+        ReservationError::Database(sqlx::Error::PoolTimedOut)
+    }
+}
+
+impl BreakerFailure for TransferError {
+    fn trips_breaker(&self) -> bool {
+        matches!(self, TransferError::Database(e) if is_infrastructure_failure(e))
+    }
+
+    fn circuit_open() -> Self {
+        TransferError::Database(sqlx::Error::PoolTimedOut)
+    }
+}
+
+/// Repository decorator that routes every database call through a named
+/// circuit breaker. Read paths keep a snapshot of their last successful result
+/// and serve it while the breaker is rejecting calls.
+pub struct ResilientRepository<R: ?Sized> {
+    inner: Arc<R>,
+    breaker: Arc<CircuitBreaker>,
+    stale_cache: Option<QueryCache>,
+}
+
+impl<R: ?Sized> ResilientRepository<R> {
+    pub fn new(inner: Arc<R>, breaker: Arc<CircuitBreaker>) -> Self {
+        Self {
+            inner,
+            breaker,
+            stale_cache: None,
+        }
+    }
+
+    pub fn with_stale_cache(mut self, cache: QueryCache) -> Self {
+        self.stale_cache = Some(cache);
+        self
+    }
+
+    pub fn breaker(&self) -> &Arc<CircuitBreaker> {
+        &self.breaker
+    }
+
+    /// Fixed-length key per operation and argument set, so search and
+    /// pagination inputs don't end up verbatim in cache keys.
+    fn stale_key(&self, operation: &str, args: impl std::fmt::Debug) -> String {
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", args).hash(&mut hasher);
+        format!(
+            "{}:{}:{:016x}",
+            self.breaker.name(),
+            operation,
+            hasher.finish()
+        )
+    }
+
+    async fn guarded<T, E, F, Fut>(&self, operation: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: BreakerFailure,
+    {
+        match self.breaker.call_with(operation, E::trips_breaker).await {
+            Ok(value) => Ok(value),
+            Err(CircuitError::Underlying(e)) => Err(e),
+            Err(_) => {
+                tracing::warn!(
+                    circuit_breaker = %self.breaker.name(),
+                    "Database call rejected by circuit breaker"
+                );
+                Err(E::circuit_open())
+            }
+        }
+    }
+
+    async fn guarded_read<T, E, F, Fut>(&self, key: String, operation: F) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: BreakerFailure,
+    {
+        match self.breaker.call_with(operation, E::trips_breaker).await {
+            Ok(value) => {
+                if let Some(cache) = &self.stale_cache {
+                    cache.store_stale(&key, &value);
+                }
+                Ok(value)
+            }
+            Err(CircuitError::Underlying(e)) => Err(e),
+            Err(_) => {
+                if let Some(cache) = &self.stale_cache
+                    && let Some(value) = cache.get_stale(&key).await
+                {
+                    tracing::warn!(
+                        circuit_breaker = %self.breaker.name(),
+                        key = %key,
+                        "Circuit open, serving stale read"
+                    );
+                    metrics::counter!(
+                        "circuit_breaker_stale_served_total",
+                        "name" => self.breaker.name().to_string()
+                    )
+                    .increment(1);
+                    return Ok(value);
+                }
+
+                tracing::warn!(
+                    circuit_breaker = %self.breaker.name(),
+                    key = %key,
+                    "Database read rejected by circuit breaker, no stale snapshot available"
+                );
+                Err(E::circuit_open())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: CarQueryRepository + ?Sized> CarQueryRepository for ResilientRepository<R> {
+    async fn find_by_id(&self, id: CarId) -> SqlxResult<Option<CarEntity>> {
+        let key = self.stale_key("find_by_id", &id);
+        self.guarded_read(key, || self.inner.find_by_id(id)).await
+    }
+
+    async fn find_by_id_in_uow(
+        &self,
+        uow: &mut UnitOfWork<'_>,
+        id: CarId,
+    ) -> SqlxResult<Option<CarEntity>> {
+        self.inner.find_by_id_in_uow(uow, id).await
+    }
+
+    async fn find_all(
+        &self,
+        filter: &CarFilter,
+        pagination: &PaginationParams,
+    ) -> SqlxResult<(Vec<CarEntity>, i64)> {
+        let key = self.stale_key("find_all", (filter, pagination));
+        self.guarded_read(key, || self.inner.find_all(filter, pagination))
+            .await
+    }
+
+    async fn search(
+        &self,
+        request: &CarSearchRequest,
+        pagination: &PaginationParams,
+    ) -> SqlxResult<(Vec<(CarEntity, f32)>, i64)> {
+        let key = self.stale_key("search", (request, pagination));
+        self.guarded_read(key, || self.inner.search(request, pagination))
+            .await
+    }
+
+    async fn get_inventory_stats(&self) -> SqlxResult<Vec<InventoryStatusStat>> {
+        let key = self.stale_key("get_inventory_stats", ());
+        self.guarded_read(key, || self.inner.get_inventory_stats())
+            .await
+    }
+
+    async fn get_depreciation_report(&self) -> SqlxResult<Vec<DepreciationInput>> {
+        let key = self.stale_key("get_depreciation_report", ());
+        self.guarded_read(key, || self.inner.get_depreciation_report())
+            .await
+    }
+
+    async fn get_warehouse_stock(&self) -> SqlxResult<Vec<WarehouseStockRow>> {
+        let key = self.stale_key("get_warehouse_stock", ());
+        self.guarded_read(key, || self.inner.get_warehouse_stock())
+            .await
+    }
+
+    async fn get_low_stock_report(&self, threshold: i32) -> SqlxResult<Vec<CarEntity>> {
+        let key = self.stale_key("get_low_stock_report", threshold);
+        self.guarded_read(key, || self.inner.get_low_stock_report(threshold))
+            .await
+    }
+}
+
+#[async_trait]
+impl<R: CarCommandRepository + ?Sized> CarCommandRepository for ResilientRepository<R> {
+    async fn create(&self, dto: CreateCarDto) -> SqlxResult<CarEntity> {
+        self.guarded(|| self.inner.create(dto)).await
+    }
+
+    async fn create_in_uow(
+        &self,
+        uow: &mut UnitOfWork<'_>,
+        dto: CreateCarDto,
+    ) -> SqlxResult<CarEntity> {
+        self.inner.create_in_uow(uow, dto).await
+    }
+
+    async fn update_partial(&self, id: &CarId, data: CarUpdateData) -> SqlxResult<CarEntity> {
+        self.guarded(|| self.inner.update_partial(id, data)).await
+    }
+
+    async fn update_in_uow(
+        &self,
+        uow: &mut UnitOfWork<'_>,
+        id: &CarId,
+        data: CarUpdateData,
+    ) -> SqlxResult<CarEntity> {
+        self.inner.update_in_uow(uow, id, data).await
+    }
+
+    async fn update_with_version(
+        &self,
+        id: &CarId,
+        data: CarUpdateData,
+        expected_version: i64,
+    ) -> SqlxResult<CarEntity> {
+        self.guarded(|| self.inner.update_with_version(id, data, expected_version))
+            .await
+    }
+
+    async fn soft_delete(&self, id: &CarId) -> SqlxResult<()> {
+        self.guarded(|| self.inner.soft_delete(id)).await
+    }
+}
+
+#[async_trait]
+impl<R: ReservationRepository + ?Sized> ReservationRepository for ResilientRepository<R> {
+    async fn create_reservation(
+        &self,
+        car_id: &CarId,
+        quantity: i32,
+        reserved_by: &str,
+        ttl_minutes: i32,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<Reservation, sqlx::Error> {
+        self.guarded(|| {
+            self.inner
+                .create_reservation(car_id, quantity, reserved_by, ttl_minutes, metadata)
+        })
+        .await
+    }
+
+    async fn get_reserved_quantity_for_car(&self, car_id: &CarId) -> Result<i64, sqlx::Error> {
+        self.guarded(|| self.inner.get_reserved_quantity_for_car(car_id))
+            .await
+    }
+
+    async fn find_reservation_by_id(&self, id: Uuid) -> Result<Option<Reservation>, sqlx::Error> {
+        let key = self.stale_key("find_reservation_by_id", id);
+        self.guarded_read(key, || self.inner.find_reservation_by_id(id))
+            .await
+    }
+
+    async fn confirm_reservation(&self, id: Uuid) -> Result<Reservation, sqlx::Error> {
+        self.guarded(|| self.inner.confirm_reservation(id)).await
+    }
+
+    async fn cancel_reservation(&self, id: Uuid, reason: Option<&str>) -> Result<(), sqlx::Error> {
+        self.guarded(|| self.inner.cancel_reservation(id, reason))
+            .await
+    }
+
+    async fn get_available_stock_with_lock(
+        &self,
+        car_id: &CarId,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<(i32, i64), sqlx::Error> {
+        self.inner.get_available_stock_with_lock(car_id, tx).await
+    }
+
+    async fn create_reservation_in_tx(
+        &self,
+        car_id: &CarId,
+        quantity: i32,
+        reserved_by: &str,
+        ttl_minutes: i32,
+        metadata: Option<serde_json::Value>,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    ) -> Result<Reservation, sqlx::Error> {
+        self.inner
+            .create_reservation_in_tx(car_id, quantity, reserved_by, ttl_minutes, metadata, tx)
+            .await
+    }
+
+    async fn execute_reservation_atomic(
+        &self,
+        car_id: CarId,
+        dto: CreateReservationDto,
+    ) -> Result<Reservation, ReservationError> {
+        self.guarded(|| self.inner.execute_reservation_atomic(car_id, dto))
+            .await
+    }
+
+    async fn create_in_uow(
+        &self,
+        uow: &mut UnitOfWork<'_>,
+        car_id: &CarId,
+        dto: CreateReservationDto,
+    ) -> Result<Reservation, ReservationError> {
+        self.inner.create_in_uow(uow, car_id, dto).await
+    }
+
+    async fn confirm_in_uow(
+        &self,
+        uow: &mut UnitOfWork<'_>,
+        reservation_id: Uuid,
+    ) -> Result<Reservation, ReservationError> {
+        self.inner.confirm_in_uow(uow, reservation_id).await
+    }
+}
+
+#[async_trait]
+impl<R: WarehouseRepository + ?Sized> WarehouseRepository for ResilientRepository<R> {
+    async fn create_warehouse(
+        &self,
+        id: WarehouseId,
+        name: String,
+        location: String,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        capacity_total: i32,
+    ) -> Result<Warehouse, sqlx::Error> {
+        self.guarded(|| {
+            self.inner
+                .create_warehouse(id, name, location, latitude, longitude, capacity_total)
+        })
+        .await
+    }
+
+    async fn list_warehouses(&self) -> Result<Vec<Warehouse>, sqlx::Error> {
+        let key = self.stale_key("list_warehouses", ());
+        self.guarded_read(key, || self.inner.list_warehouses())
+            .await
+    }
+
+    async fn find_warehouse_by_id(
+        &self,
+        id: &WarehouseId,
+    ) -> Result<Option<Warehouse>, sqlx::Error> {
+        let key = self.stale_key("find_warehouse_by_id", id);
+        self.guarded_read(key, || self.inner.find_warehouse_by_id(id))
+            .await
+    }
+
+    async fn transfer_stock(
+        &self,
+        from: &WarehouseId,
+        to: &WarehouseId,
+        car_id: &CarId,
+        quantity: i32,
+    ) -> Result<TransferOrder, sqlx::Error> {
+        self.guarded(|| self.inner.transfer_stock(from, to, car_id, quantity))
+            .await
+    }
+
+    async fn execute_transfer(
+        &self,
+        from: &WarehouseId,
+        to: &WarehouseId,
+        car_id: &CarId,
+        quantity: i32,
+    ) -> Result<TransferOrder, TransferError> {
+        self.guarded(|| self.inner.execute_transfer(from, to, car_id, quantity))
+            .await
+    }
+
+    async fn complete_transfer(&self, transfer_id: Uuid) -> Result<TransferOrder, TransferError> {
+        self.guarded(|| self.inner.complete_transfer(transfer_id))
+            .await
+    }
+
+    async fn find_transfer_by_id(
+        &self,
+        transfer_id: Uuid,
+    ) -> Result<Option<TransferOrder>, sqlx::Error> {
+        let key = self.stale_key("find_transfer_by_id", transfer_id);
+        self.guarded_read(key, || self.inner.find_transfer_by_id(transfer_id))
+            .await
+    }
+}
+
+#[async_trait]
+impl<R: InventoryAnalyticsRepository + ?Sized> InventoryAnalyticsRepository
+    for ResilientRepository<R>
+{
+    async fn get_stock_alerts(&self) -> Result<Vec<StockAlertRow>, sqlx::Error> {
+        let key = self.stale_key("get_stock_alerts", ());
+        self.guarded_read(key, || self.inner.get_stock_alerts())
+            .await
+    }
+
+    async fn get_sales_velocity(&self, days: i32) -> Result<Vec<SalesVelocity>, sqlx::Error> {
+        let key = self.stale_key("get_sales_velocity", days);
+        self.guarded_read(key, || self.inner.get_sales_velocity(days))
+            .await
+    }
+
+    async fn get_inventory_metrics(&self) -> Result<InventoryMetrics, sqlx::Error> {
+        let key = self.stale_key("get_inventory_metrics", ());
+        self.guarded_read(key, || self.inner.get_inventory_metrics())
+            .await
+    }
+
+    async fn get_metrics_history(
+        &self,
+        range: &MetricsHistoryRange,
+    ) -> Result<Vec<MetricsHistoryPoint>, sqlx::Error> {
+        let key = self.stale_key("get_metrics_history", range);
+        self.guarded_read(key, || self.inner.get_metrics_history(range))
+            .await
+    }
+
+    async fn get_metrics_period(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        aggregation: MetricsAggregation,
+    ) -> Result<MetricsHistoryPoint, sqlx::Error> {
+        let key = self.stale_key("get_metrics_period", (from, to, aggregation));
+        self.guarded_read(key, || self.inner.get_metrics_period(from, to, aggregation))
+            .await
+    }
+
+    async fn get_daily_sales(
+        &self,
+        car_ids: &[CarId],
+        days: i32,
+    ) -> Result<Vec<DailySalesRow>, sqlx::Error> {
+        let key = self.stale_key("get_daily_sales", (car_ids, days));
+        self.guarded_read(key, || self.inner.get_daily_sales(car_ids, days))
+            .await
+    }
+}
@@ -6,12 +6,10 @@ use axum::{
     body::Body,
     extract::Request,
     http::{HeaderName, StatusCode},
+    middleware,
     routing::{delete, get, post, put},
 };
 use tower::ServiceBuilder;
-use tower_governor::{
-    GovernorLayer, governor::GovernorConfigBuilder, key_extractor::PeerIpKeyExtractor,
-};
 use tower_http::{
     compression::CompressionLayer,
     limit::RequestBodyLimitLayer,
@@ -23,9 +21,13 @@ use tracing::Level;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use crate::config::create_cors_layer;
+use crate::bulkhead::{Bulkhead, LoadShedder, RouteGroup, bulkhead_middleware};
+use crate::config::{RateLimitPolicy, create_cors_layer};
 use crate::handlers;
+use crate::middleware::http_metrics_middleware;
 use crate::models::*;
+use crate::observability::set_parent_from_headers;
+use crate::rate_limit::{PrincipalResolver, RateLimiter, rate_limit_middleware};
 use crate::state::AppState;
 
 #[derive(OpenApi)]
@@ -49,16 +51,56 @@ use crate::state::AppState;
         crate::handlers::create_transfer_handler,
         crate::handlers::complete_transfer_handler,
         crate::handlers::get_transfer_handler,
+        crate::handlers::generate_rebalancing_proposals_handler,
+        crate::handlers::list_rebalancing_proposals_handler,
+        crate::handlers::accept_rebalancing_proposal_handler,
+        crate::handlers::reject_rebalancing_proposal_handler,
+        crate::handlers::checkout_reservation_handler,
+        crate::handlers::create_pricing_rule_handler,
+        crate::handlers::list_pricing_rules_handler,
+        crate::handlers::deactivate_pricing_rule_handler,
+        crate::handlers::create_supplier_handler,
+        crate::handlers::list_suppliers_handler,
+        crate::handlers::get_supplier_handler,
+        crate::handlers::set_supplier_price_handler,
+        crate::handlers::create_purchase_order_handler,
+        crate::handlers::list_purchase_orders_handler,
+        crate::handlers::get_purchase_order_handler,
+        crate::handlers::submit_purchase_order_handler,
+        crate::handlers::cancel_purchase_order_handler,
+        crate::handlers::receive_goods_handler,
+        crate::handlers::get_price_history_handler,
+        crate::handlers::schedule_price_change_handler,
+        crate::handlers::cancel_scheduled_price_change_handler,
         crate::handlers::get_dashboard_stats_handler,
         crate::handlers::get_depreciation_handler,
+        crate::handlers::get_inventory_valuation_handler,
         crate::handlers::get_low_stock_handler,
         crate::handlers::get_stock_alerts_handler,
         crate::handlers::get_sales_velocity_handler,
         crate::handlers::get_inventory_metrics_handler,
+        crate::handlers::get_metrics_history_handler,
+        crate::handlers::get_demand_forecast_handler,
+        crate::handlers::get_reorder_parameters_handler,
+        crate::handlers::set_reorder_overrides_handler,
+        crate::handlers::get_classification_report_handler,
+        crate::handlers::list_alert_thresholds_handler,
+        crate::handlers::set_alert_threshold_handler,
+        crate::handlers::get_inventory_aging_handler,
+        crate::handlers::get_slow_movers_handler,
+        crate::handlers::list_job_executions_handler,
+        crate::handlers::list_job_schedules_handler,
+        crate::handlers::trigger_job_handler,
+        crate::handlers::pause_job_handler,
+        crate::handlers::resume_job_handler,
         crate::handlers::health_check_handler,
+        crate::handlers::liveness_handler,
+        crate::handlers::readiness_handler,
+        crate::handlers::startup_handler,
         crate::handlers::circuit_breaker_health_handler,
         crate::handlers::cache_metrics_handler,
         crate::handlers::pool_metrics_handler,
+        crate::handlers::metrics_handler,
     ),
     components(
         schemas(
@@ -68,6 +110,8 @@ use crate::state::AppState;
             UpdateCarDto,
             CarStatus,
             EngineType,
+            AbcClass,
+            XyzClass,
             PaginationMeta,
             PaginatedResponse<CarResponse>,
             Reservation,
@@ -79,8 +123,25 @@ use crate::state::AppState;
             WarehouseId,
             StockLocation,
             TransferOrder,
+            RebalancingStatus,
+            RebalancingProposal,
+            RebalancingAcceptance,
             TransferStatus,
             StockTransferDto,
+            SupplierId,
+            Supplier,
+            CreateSupplierDto,
+            SupplierPrice,
+            SetSupplierPriceDto,
+            SupplierDetail,
+            PurchaseOrderStatus,
+            PurchaseOrder,
+            PurchaseOrderLine,
+            PurchaseOrderResponse,
+            CreatePurchaseOrderDto,
+            PurchaseOrderLineDto,
+            GoodsReceiptDto,
+            GoodsReceiptLineDto,
             StockAlert,
             AlertLevel,
             StockTrend,
@@ -89,13 +150,61 @@ use crate::state::AppState;
             InventoryAlertSummary,
             SalesVelocity,
             InventoryMetrics,
+            MetricsBucket,
+            MetricsAggregation,
+            MetricsGapFill,
+            MetricValues,
+            MetricsHistoryPoint,
+            MetricsPeriod,
+            MetricDelta,
+            MetricsHistoryResponse,
+            ForecastModel,
+            ForecastPoint,
+            ForecastModelScore,
+            DemandForecast,
+            ReorderSettings,
+            ReorderParameterChange,
+            ReorderParametersResponse,
+            ReorderOverrideDto,
+            CarClassification,
+            ClassificationReport,
+            AlertThreshold,
+            SetAlertThresholdDto,
+            AgingBucketStock,
+            CarAging,
+            InventoryAgingReport,
+            SlowMover,
+            CarDepreciation,
+            ValuationTotals,
+            WarehouseValuation,
+            InventoryValuation,
+            PriceChangeStatus,
+            ScheduledPriceChange,
+            SchedulePriceChangeDto,
+            SalesWindow,
+            PriceChange,
+            CarPriceHistory,
+            PricingRule,
+            CreatePricingRuleDto,
+            AppliedPricingRule,
+            PriceQuote,
+            CheckoutDto,
+            SaleReceipt,
+            JobExecution,
+            JobStatus,
+            JobScheduleStatus,
+            LeaderStatus,
+            PaginatedResponse<JobExecution>,
             UpdateCarDto,
         )
     ),
     tags(
         (name = "Reservations", description = "Stock reservation management with TTL"),
         (name = "Warehouses", description = "Multi-warehouse inventory management"),
+        (name = "Purchasing", description = "Suppliers, purchase orders and goods receipts"),
+        (name = "Pricing", description = "Pricing rules and promotions applied at checkout"),
         (name = "Inventory Analytics", description = "Smart inventory insights and alerts"),
+        (name = "Admin", description = "Job schedules, run history and manual runs"),
     ),
     info(
         title = "Automobile Inventory API",
@@ -118,15 +227,6 @@ pub fn create_router(state: AppState) -> Router {
             Duration::from_secs(30),
         ));
 
-    let governor_conf = Arc::new(
-        GovernorConfigBuilder::default()
-            .per_second(5)
-            .burst_size(10)
-            .key_extractor(PeerIpKeyExtractor)
-            .finish()
-            .unwrap(),
-    );
-
     let outer_layers = ServiceBuilder::new()
         .layer(SetRequestIdLayer::new(
             x_request_id.clone(),
@@ -142,12 +242,15 @@ pub fn create_router(state: AppState) -> Router {
                         .and_then(|v| v.to_str().ok())
                         .unwrap_or("unknown");
 
-                    tracing::info_span!(
+                    let span = tracing::info_span!(
                         "http_request",
                         method = %request.method(),
                         uri = %request.uri(),
                         request_id = %request_id,
-                    )
+                        otel.kind = "server",
+                    );
+                    set_parent_from_headers(&span, request.headers());
+                    span
                 })
                 .on_response(
                     DefaultOnResponse::new()
@@ -157,23 +260,105 @@ pub fn create_router(state: AppState) -> Router {
         )
         .layer(CompressionLayer::new());
 
+    let resilience = &state.config.resilience;
+    let shedder = state
+        .pool_manager
+        .clone()
+        .filter(|_| resilience.load_shedding.enabled)
+        .map(|manager| LoadShedder::new(manager, resilience.load_shedding.clone()));
+    let bulkhead = |group: RouteGroup, config| {
+        let bulkhead = Bulkhead::new(group, config);
+        let bulkhead = match &shedder {
+            Some(shedder) => bulkhead.with_load_shedding(shedder.clone()),
+            None => bulkhead,
+        };
+        middleware::from_fn_with_state(Arc::new(bulkhead), bulkhead_middleware)
+    };
+
+    let rate_limiting = &state.config.rate_limiting;
+    let resolver = Arc::new(PrincipalResolver::new(
+        rate_limiting
+            .principal_precedence()
+            .expect("Invalid rate_limiting.principal_precedence"),
+        rate_limiting
+            .trusted_proxies()
+            .expect("Invalid rate_limiting.trusted_proxies"),
+        rate_limiting.api_keys(),
+    ));
+    let rate_limit = |router: Router<AppState>, group: RouteGroup, policy: &RateLimitPolicy| {
+        if !state.config.features.enable_rate_limiting {
+            return router;
+        }
+        let limiter = RateLimiter::new(group, policy.clone(), Arc::clone(&resolver));
+        router.layer(middleware::from_fn_with_state(
+            Arc::new(limiter),
+            rate_limit_middleware,
+        ))
+    };
+
+    let catalogue_routes = rate_limit(
+        Router::new()
+            .nest("/cars", car_routes())
+            .nest("/warehouses", warehouse_routes())
+            .nest("/suppliers", supplier_routes())
+            .nest("/purchase-orders", purchase_order_routes())
+            .nest("/pricing", pricing_routes())
+            .layer(bulkhead(
+                RouteGroup::Catalogue,
+                &resilience.bulkheads.catalogue,
+            )),
+        RouteGroup::Catalogue,
+        &rate_limiting.catalogue,
+    );
+
+    let checkout_routes = rate_limit(
+        Router::new()
+            .route(
+                "/cars/{id}/reservations",
+                post(handlers::create_reservation_handler),
+            )
+            .nest("/reservations", reservation_routes())
+            .layer(bulkhead(
+                RouteGroup::Reservations,
+                &resilience.bulkheads.reservations,
+            )),
+        RouteGroup::Reservations,
+        &rate_limiting.reservations,
+    );
+
+    let analytics_routes = rate_limit(
+        Router::new()
+            .nest("/cars/analytics", car_analytics_routes())
+            .nest("/inventory", inventory_routes())
+            .nest("/admin", admin_routes())
+            .layer(bulkhead(
+                RouteGroup::Analytics,
+                &resilience.bulkheads.analytics,
+            )),
+        RouteGroup::Analytics,
+        &rate_limiting.analytics,
+    );
+
     let v1_routes = Router::new()
-        .nest("/cars", car_routes())
-        .nest("/reservations", reservation_routes())
-        .nest("/warehouses", warehouse_routes())
-        .nest("/inventory", inventory_routes())
-        .layer(inner_layers)
-        .layer(GovernorLayer::new(governor_conf));
+        .merge(catalogue_routes)
+        .merge(checkout_routes)
+        .merge(analytics_routes)
+        .layer(inner_layers);
 
     Router::new()
         .route("/health", get(handlers::health_check_handler))
+        .route("/health/live", get(handlers::liveness_handler))
+        .route("/health/ready", get(handlers::readiness_handler))
+        .route("/health/startup", get(handlers::startup_handler))
         .route(
             "/health/circuit-breakers",
             get(handlers::circuit_breaker_health_handler),
         )
         .route("/health/cache", get(handlers::cache_metrics_handler))
         .route("/health/pool", get(handlers::pool_metrics_handler))
+        .route("/metrics", get(handlers::metrics_handler))
         .nest("/api/v1", v1_routes)
+        .route_layer(middleware::from_fn(http_metrics_middleware))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .layer(outer_layers)
         .layer(cors_layer)
@@ -192,29 +377,37 @@ fn car_routes() -> Router<AppState> {
             put(handlers::update_car_versioned_handler),
         )
         .route("/{id}", delete(handlers::delete_car_handler))
-        .route(
-            "/{id}/reservations",
-            post(handlers::create_reservation_handler),
-        )
         .route(
             "/{id}/resilient",
             get(handlers::get_car_by_id_resilient_handler),
         )
+        .route("/{id}/prices", get(handlers::get_price_history_handler))
         .route(
-            "/analytics/dashboard",
-            get(handlers::get_dashboard_stats_handler),
+            "/{id}/prices/scheduled",
+            post(handlers::schedule_price_change_handler),
         )
         .route(
-            "/analytics/depreciation",
-            get(handlers::get_depreciation_handler),
+            "/{id}/prices/scheduled/{change_id}",
+            delete(handlers::cancel_scheduled_price_change_handler),
         )
-        .route("/analytics/low-stock", get(handlers::get_low_stock_handler))
+}
+
+fn car_analytics_routes() -> Router<AppState> {
+    Router::new()
+        .route("/dashboard", get(handlers::get_dashboard_stats_handler))
+        .route("/depreciation", get(handlers::get_depreciation_handler))
+        .route("/valuation", get(handlers::get_inventory_valuation_handler))
+        .route("/low-stock", get(handlers::get_low_stock_handler))
 }
 
 fn reservation_routes() -> Router<AppState> {
     Router::new()
         .route("/{id}", get(handlers::get_reservation_handler))
         .route("/{id}/confirm", post(handlers::confirm_reservation_handler))
+        .route(
+            "/{id}/checkout",
+            post(handlers::checkout_reservation_handler),
+        )
         .route("/{id}", delete(handlers::cancel_reservation_handler))
 }
 
@@ -229,6 +422,59 @@ fn warehouse_routes() -> Router<AppState> {
             "/transfers/{id}/complete",
             post(handlers::complete_transfer_handler),
         )
+        .route(
+            "/rebalancing/proposals",
+            post(handlers::generate_rebalancing_proposals_handler),
+        )
+        .route(
+            "/rebalancing/proposals",
+            get(handlers::list_rebalancing_proposals_handler),
+        )
+        .route(
+            "/rebalancing/proposals/{id}/accept",
+            post(handlers::accept_rebalancing_proposal_handler),
+        )
+        .route(
+            "/rebalancing/proposals/{id}/reject",
+            post(handlers::reject_rebalancing_proposal_handler),
+        )
+}
+
+fn supplier_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(handlers::create_supplier_handler))
+        .route("/", get(handlers::list_suppliers_handler))
+        .route("/{id}", get(handlers::get_supplier_handler))
+        .route(
+            "/{id}/prices/{car_id}",
+            put(handlers::set_supplier_price_handler),
+        )
+}
+
+fn purchase_order_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", post(handlers::create_purchase_order_handler))
+        .route("/", get(handlers::list_purchase_orders_handler))
+        .route("/{id}", get(handlers::get_purchase_order_handler))
+        .route(
+            "/{id}/submit",
+            post(handlers::submit_purchase_order_handler),
+        )
+        .route(
+            "/{id}/cancel",
+            post(handlers::cancel_purchase_order_handler),
+        )
+        .route("/{id}/receipts", post(handlers::receive_goods_handler))
+}
+
+fn pricing_routes() -> Router<AppState> {
+    Router::new()
+        .route("/rules", post(handlers::create_pricing_rule_handler))
+        .route("/rules", get(handlers::list_pricing_rules_handler))
+        .route(
+            "/rules/{id}",
+            delete(handlers::deactivate_pricing_rule_handler),
+        )
 }
 
 fn inventory_routes() -> Router<AppState> {
@@ -236,4 +482,40 @@ fn inventory_routes() -> Router<AppState> {
         .route("/alerts", get(handlers::get_stock_alerts_handler))
         .route("/velocity", get(handlers::get_sales_velocity_handler))
         .route("/metrics", get(handlers::get_inventory_metrics_handler))
+        .route(
+            "/metrics/history",
+            get(handlers::get_metrics_history_handler),
+        )
+        .route(
+            "/forecast/{car_id}",
+            get(handlers::get_demand_forecast_handler),
+        )
+        .route(
+            "/reorder/{car_id}",
+            get(handlers::get_reorder_parameters_handler)
+                .put(handlers::set_reorder_overrides_handler),
+        )
+        .route(
+            "/classification",
+            get(handlers::get_classification_report_handler),
+        )
+        .route(
+            "/classification/thresholds",
+            get(handlers::list_alert_thresholds_handler),
+        )
+        .route(
+            "/classification/thresholds/{class}",
+            put(handlers::set_alert_threshold_handler),
+        )
+        .route("/aging", get(handlers::get_inventory_aging_handler))
+        .route("/aging/slow-movers", get(handlers::get_slow_movers_handler))
+}
+
+fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route("/jobs", get(handlers::list_job_executions_handler))
+        .route("/jobs/schedules", get(handlers::list_job_schedules_handler))
+        .route("/jobs/{name}/run", post(handlers::trigger_job_handler))
+        .route("/jobs/{name}/pause", post(handlers::pause_job_handler))
+        .route("/jobs/{name}/resume", post(handlers::resume_job_handler))
 }
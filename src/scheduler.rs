@@ -0,0 +1,565 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, watch};
+use tokio::task::JoinSet;
+
+use crate::config::JobScheduleConfig;
+use crate::error::{AppError, AppResult};
+use crate::leader::{JobLock, LeaderElection};
+use crate::lifecycle::Lifecycle;
+use crate::models::{JobExecution, JobScheduleStatus};
+use crate::repositories::JobExecutionRepository;
+
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+#[async_trait]
+pub trait Job: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn run(&self, ctx: &JobContext) -> AppResult<()>;
+}
+
+/// Per-run state handed to a job. Progress recorded here survives timeouts
+/// and failed attempts, so partial work still shows up in the run history.
+pub struct JobContext {
+    shutdown_rx: watch::Receiver<bool>,
+    items_processed: AtomicU64,
+}
+
+impl JobContext {
+    fn new(shutdown_rx: watch::Receiver<bool>) -> Self {
+        Self {
+            shutdown_rx,
+            items_processed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        *self.shutdown_rx.borrow()
+    }
+
+    pub fn record_processed(&self, items: u64) {
+        self.items_processed.fetch_add(items, Ordering::Relaxed);
+    }
+
+    pub fn items_processed(&self) -> u64 {
+        self.items_processed.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum JobSchedule {
+    Interval(Duration),
+    Cron(Box<cron::Schedule>),
+}
+
+impl TryFrom<&JobScheduleConfig> for JobSchedule {
+    type Error = String;
+
+    fn try_from(config: &JobScheduleConfig) -> Result<Self, Self::Error> {
+        match config.cron.as_deref().map(str::trim) {
+            Some(expression) if !expression.is_empty() => cron::Schedule::from_str(expression)
+                .map(|schedule| Self::Cron(Box::new(schedule)))
+                .map_err(|e| format!("invalid cron expression '{}': {}", expression, e)),
+            _ if config.interval_seconds == 0 => {
+                Err("interval_seconds must be greater than 0".to_string())
+            }
+            _ => Ok(Self::Interval(Duration::from_secs(config.interval_seconds))),
+        }
+    }
+}
+
+impl JobSchedule {
+    /// Interval jobs fire immediately on startup, cron jobs at their next match.
+    fn first_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Interval(_) => Some(now),
+            Self::Cron(schedule) => schedule.after(&now).next(),
+        }
+    }
+
+    fn next_run(&self, fired_at: DateTime<Utc>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Interval(period) => {
+                let period = chrono::Duration::from_std(*period).ok()?;
+                Some((fired_at + period).max(now))
+            }
+            Self::Cron(schedule) => schedule.after(&now).next(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Interval(period) => format!("every {}s", period.as_secs()),
+            Self::Cron(schedule) => schedule.to_string(),
+        }
+    }
+}
+
+struct ScheduledJob {
+    job: Arc<dyn Job>,
+    schedule: JobSchedule,
+    timeout: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+    max_concurrent: usize,
+    permits: Arc<Semaphore>,
+    paused: AtomicBool,
+    next_run_at: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl ScheduledJob {
+    fn name(&self) -> &'static str {
+        self.job.name()
+    }
+
+    fn set_next_run(&self, at: Option<DateTime<Utc>>) {
+        *self.next_run_at.lock().expect("scheduler state poisoned") = at;
+    }
+
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(1 << (attempt - 1).min(16))
+            .min(MAX_RETRY_BACKOFF)
+    }
+
+    fn status(&self) -> JobScheduleStatus {
+        JobScheduleStatus {
+            name: self.name().to_string(),
+            schedule: self.schedule.describe(),
+            paused: self.paused.load(Ordering::Acquire),
+            running: self.max_concurrent - self.permits.available_permits(),
+            max_concurrent: self.max_concurrent,
+            timeout_seconds: self.timeout.as_secs(),
+            max_retries: self.max_retries,
+            next_run_at: *self.next_run_at.lock().expect("scheduler state poisoned"),
+        }
+    }
+}
+
+pub struct SchedulerBuilder {
+    jobs: BTreeMap<&'static str, Arc<ScheduledJob>>,
+    executions: Arc<dyn JobExecutionRepository>,
+    shutdown_rx: watch::Receiver<bool>,
+    schedules_enabled: bool,
+    leader_election: Option<Arc<LeaderElection>>,
+}
+
+impl SchedulerBuilder {
+    /// With schedules disabled jobs only run when triggered manually.
+    pub fn schedules_enabled(mut self, enabled: bool) -> Self {
+        self.schedules_enabled = enabled;
+        self
+    }
+
+    /// Only the elected leader fires schedules, and every run takes a
+    /// cluster-wide per-job lock.
+    pub fn with_leader_election(mut self, leader_election: Arc<LeaderElection>) -> Self {
+        self.leader_election = Some(leader_election);
+        self
+    }
+
+    pub fn register(
+        mut self,
+        job: Arc<dyn Job>,
+        config: &JobScheduleConfig,
+    ) -> Result<Self, AppError> {
+        let name = job.name();
+        let schedule = JobSchedule::try_from(config)
+            .map_err(|e| AppError::ConfigError(format!("scheduler.jobs.{}: {}", name, e)))?;
+        let max_concurrent = config.max_concurrent.max(1);
+
+        tracing::info!(
+            job = name,
+            schedule = %schedule.describe(),
+            timeout_secs = config.timeout_seconds,
+            max_retries = config.max_retries,
+            max_concurrent = max_concurrent,
+            paused = config.paused,
+            "Job registered"
+        );
+
+        self.jobs.insert(
+            name,
+            Arc::new(ScheduledJob {
+                job,
+                schedule,
+                timeout: config.timeout(),
+                max_retries: config.max_retries,
+                retry_backoff: config.retry_backoff(),
+                max_concurrent,
+                permits: Arc::new(Semaphore::new(max_concurrent)),
+                paused: AtomicBool::new(config.paused),
+                next_run_at: Mutex::new(None),
+            }),
+        );
+
+        Ok(self)
+    }
+
+    pub fn build(self) -> Scheduler {
+        Scheduler {
+            inner: Arc::new(SchedulerInner {
+                jobs: self.jobs,
+                executions: self.executions,
+                shutdown_rx: self.shutdown_rx,
+                schedules_enabled: self.schedules_enabled,
+                leader_election: self.leader_election,
+            }),
+        }
+    }
+}
+
+struct SchedulerInner {
+    jobs: BTreeMap<&'static str, Arc<ScheduledJob>>,
+    executions: Arc<dyn JobExecutionRepository>,
+    shutdown_rx: watch::Receiver<bool>,
+    schedules_enabled: bool,
+    leader_election: Option<Arc<LeaderElection>>,
+}
+
+/// Runs registered jobs on their schedules and on demand, recording every run
+/// in `job_executions`. Each job gets its own timeout, retry policy and
+/// concurrency limit.
+#[derive(Clone)]
+pub struct Scheduler {
+    inner: Arc<SchedulerInner>,
+}
+
+impl Scheduler {
+    pub fn builder(
+        executions: Arc<dyn JobExecutionRepository>,
+        shutdown_rx: watch::Receiver<bool>,
+    ) -> SchedulerBuilder {
+        SchedulerBuilder {
+            jobs: BTreeMap::new(),
+            executions,
+            shutdown_rx,
+            schedules_enabled: true,
+            leader_election: None,
+        }
+    }
+
+    pub fn executions(&self) -> &Arc<dyn JobExecutionRepository> {
+        &self.inner.executions
+    }
+
+    pub fn statuses(&self) -> Vec<JobScheduleStatus> {
+        self.inner.jobs.values().map(|job| job.status()).collect()
+    }
+
+    pub fn pause(&self, name: &str) -> AppResult<JobScheduleStatus> {
+        self.set_paused(name, true)
+    }
+
+    pub fn resume(&self, name: &str) -> AppResult<JobScheduleStatus> {
+        self.set_paused(name, false)
+    }
+
+    fn set_paused(&self, name: &str, paused: bool) -> AppResult<JobScheduleStatus> {
+        let job = self.job(name)?;
+        if job.paused.swap(paused, Ordering::AcqRel) != paused {
+            tracing::info!(job = name, paused = paused, "Job schedule updated");
+        }
+        Ok(job.status())
+    }
+
+    fn job(&self, name: &str) -> AppResult<Arc<ScheduledJob>> {
+        self.inner.jobs.get(name).cloned().ok_or(AppError::NotFound)
+    }
+
+    /// Starts a run in the background and returns the `Running` execution.
+    /// Paused jobs can still be triggered manually.
+    pub async fn trigger(&self, name: &str) -> AppResult<JobExecution> {
+        let job = self.job(name)?;
+        let permit = Arc::clone(&job.permits)
+            .try_acquire_owned()
+            .map_err(|_| AppError::JobAlreadyRunning(name.to_string()))?;
+        let lock = self.cluster_lock(job.name()).await?;
+
+        let execution = match self.inner.executions.start(job.name(), "manual").await {
+            Ok(execution) => execution,
+            Err(e) => {
+                if let Some(lock) = lock {
+                    lock.release().await;
+                }
+                return Err(e.into());
+            }
+        };
+
+        let scheduler = self.clone();
+        let started = execution.clone();
+        tokio::spawn(async move {
+            let _ = scheduler.execute(job, permit, lock, started).await;
+        });
+
+        Ok(execution)
+    }
+
+    /// Takes the cluster-wide run lock for a job; `None` when leader
+    /// election is disabled.
+    async fn cluster_lock(&self, name: &'static str) -> AppResult<Option<JobLock>> {
+        let Some(leader_election) = &self.inner.leader_election else {
+            return Ok(None);
+        };
+
+        leader_election
+            .try_job_lock(name)
+            .await?
+            .map(Some)
+            .ok_or_else(|| AppError::JobAlreadyRunning(name.to_string()))
+    }
+
+    /// Drives every job's schedule until shutdown, then waits for in-flight
+    /// runs to finish.
+    pub async fn start(self, lifecycle: Option<Arc<Lifecycle>>) {
+        tracing::info!(
+            jobs = self.inner.jobs.len(),
+            schedules_enabled = self.inner.schedules_enabled,
+            "Job scheduler started"
+        );
+
+        let mut loops = JoinSet::new();
+        if self.inner.schedules_enabled {
+            for job in self.inner.jobs.values() {
+                loops.spawn(self.clone().schedule_loop(Arc::clone(job)));
+            }
+        }
+
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        let mut shutdown_rx = self.inner.shutdown_rx.clone();
+
+        loop {
+            tokio::select! {
+                _ = heartbeat.tick() => {
+                    if let Some(lifecycle) = &lifecycle {
+                        lifecycle.record_heartbeat();
+                    }
+                }
+                changed = shutdown_rx.changed() => {
+                    if changed.is_err() || *shutdown_rx.borrow() {
+                        tracing::info!("Job scheduler received shutdown signal");
+                        break;
+                    }
+                }
+            }
+        }
+
+        while loops.join_next().await.is_some() {}
+
+        for job in self.inner.jobs.values() {
+            let _ = job.permits.acquire_many(job.max_concurrent as u32).await;
+        }
+
+        tracing::info!("Job scheduler stopped gracefully");
+    }
+
+    async fn schedule_loop(self, job: Arc<ScheduledJob>) {
+        let mut shutdown_rx = self.inner.shutdown_rx.clone();
+        let mut next = job.schedule.first_run(Utc::now());
+
+        while let Some(fire_at) = next {
+            job.set_next_run(Some(fire_at));
+
+            let wait = (fire_at - Utc::now()).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                changed = shutdown_rx.changed() => {
+                    if changed.is_err() || *shutdown_rx.borrow() {
+                        break;
+                    }
+                    continue;
+                }
+            }
+
+            next = job.schedule.next_run(fire_at, Utc::now());
+
+            if job.paused.load(Ordering::Acquire) {
+                tracing::debug!(job = job.name(), "Skipping scheduled run, job paused");
+                record_skip(job.name(), "paused");
+                continue;
+            }
+
+            if let Some(leader_election) = &self.inner.leader_election
+                && !leader_election.is_leader()
+            {
+                tracing::debug!(job = job.name(), "Skipping scheduled run, not the leader");
+                record_skip(job.name(), "not_leader");
+                continue;
+            }
+
+            let Ok(permit) = Arc::clone(&job.permits).try_acquire_owned() else {
+                tracing::info!(
+                    job = job.name(),
+                    max_concurrent = job.max_concurrent,
+                    "Skipping scheduled run, concurrency limit reached"
+                );
+                record_skip(job.name(), "concurrency_limit");
+                continue;
+            };
+
+            tokio::spawn(self.clone().run_scheduled(Arc::clone(&job), permit));
+        }
+
+        job.set_next_run(None);
+    }
+
+    async fn run_scheduled(self, job: Arc<ScheduledJob>, permit: OwnedSemaphorePermit) {
+        let lock = match self.cluster_lock(job.name()).await {
+            Ok(lock) => lock,
+            Err(AppError::JobAlreadyRunning(_)) => {
+                tracing::info!(
+                    job = job.name(),
+                    "Skipping scheduled run, job running on another instance"
+                );
+                record_skip(job.name(), "locked");
+                return;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, task = job.name(), "Failed to take job lock, skipping run");
+                record_skip(job.name(), "lock_failed");
+                return;
+            }
+        };
+
+        match self.inner.executions.start(job.name(), "schedule").await {
+            Ok(execution) => {
+                let _ = self.execute(job, permit, lock, execution).await;
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    task = job.name(),
+                    "Failed to record job start, skipping run"
+                );
+                record_skip(job.name(), "record_failed");
+                if let Some(lock) = lock {
+                    lock.release().await;
+                }
+            }
+        }
+    }
+
+    async fn execute(
+        &self,
+        job: Arc<ScheduledJob>,
+        permit: OwnedSemaphorePermit,
+        lock: Option<JobLock>,
+        execution: JobExecution,
+    ) -> AppResult<JobExecution> {
+        let name = job.name();
+        let ctx = JobContext::new(self.inner.shutdown_rx.clone());
+        let start = Instant::now();
+        let mut attempts: u32 = 0;
+
+        let result = loop {
+            attempts += 1;
+
+            let outcome = match tokio::time::timeout(job.timeout, job.job.run(&ctx)).await {
+                Ok(outcome) => outcome,
+                Err(_) => Err(AppError::BackgroundJobError(format!(
+                    "{} timed out after {}s",
+                    name,
+                    job.timeout.as_secs()
+                ))),
+            };
+
+            match outcome {
+                Ok(()) => break Ok(()),
+                Err(e) if attempts <= job.max_retries && !ctx.is_shutting_down() => {
+                    let delay = job.retry_delay(attempts);
+                    tracing::warn!(
+                        error = %e,
+                        task = name,
+                        attempt = attempts,
+                        retry_in_ms = delay.as_millis() as u64,
+                        "Job attempt failed, retrying"
+                    );
+                    metrics::counter!("background_job_retries_total", "job" => name).increment(1);
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => break Err(e),
+            }
+        };
+
+        let items = ctx.items_processed().min(i32::MAX as u64) as i32;
+        let recorded = match &result {
+            Ok(()) => {
+                self.inner
+                    .executions
+                    .complete(execution.job_id, items, attempts as i32)
+                    .await
+            }
+            Err(e) => {
+                tracing::error!(
+                    error = %e,
+                    task = name,
+                    attempts = attempts,
+                    "Background task failed"
+                );
+                self.inner
+                    .executions
+                    .fail(execution.job_id, items, attempts as i32, &e.to_string())
+                    .await
+            }
+        };
+        if let Some(lock) = lock {
+            lock.release().await;
+        }
+        drop(permit);
+
+        let elapsed = start.elapsed();
+        let status = if result.is_ok() {
+            "completed"
+        } else {
+            "failed"
+        };
+        metrics::counter!(
+            "background_job_runs_total",
+            "job" => name,
+            "status" => status
+        )
+        .increment(1);
+        metrics::histogram!("background_job_duration_ms", "job" => name)
+            .record(elapsed.as_millis() as f64);
+
+        tracing::info!(
+            task = name,
+            job_id = %execution.job_id,
+            status = status,
+            items_processed = items,
+            attempts = attempts,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Job run finished"
+        );
+
+        result?;
+
+        recorded.map_err(|e| {
+            tracing::warn!(
+                error = %e,
+                job_id = %execution.job_id,
+                task = name,
+                "Failed to record job completion"
+            );
+            AppError::DatabaseError(e)
+        })
+    }
+}
+
+fn record_skip(job: &'static str, reason: &'static str) {
+    metrics::counter!(
+        "background_job_skipped_total",
+        "job" => job,
+        "reason" => reason
+    )
+    .increment(1);
+}
@@ -1,34 +1,90 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, FromPrimitive};
 use chrono::Utc;
-use sqlx::PgPool;
 use tokio::time::timeout;
 use tracing::{info, instrument};
 use uuid::Uuid;
 
+use crate::aging;
 use crate::cache::QueryCache;
-use crate::config::DatabaseConfig;
-use crate::error::{AppError, AppResult, ReservationError};
+use crate::classification;
+use crate::config::{
+    AgingConfig, ClassificationConfig, DatabaseConfig, DepreciationConfig, PriceHistoryConfig,
+    PricingConfig, RebalancingConfig,
+};
+use crate::depreciation;
+use crate::error::{AppError, AppResult, PurchaseOrderError, ReservationError};
+use crate::forecasting;
+use crate::lifecycle::MIGRATOR;
 use crate::models::{
-    AlertLevel, CarFilter, CarId, CarResponse, CarSearchQuery, CarSearchRequest, CarSearchResult,
-    CarStatus, CarUpdateData, CreateCarDto, CreateReservationDto, DashboardStats, HealthStatus,
-    InventoryAlertSummary, InventoryMetrics, InventoryStatusStat, PaginatedResponse,
-    ReservationResponse, ReservationStatus, SalesVelocity, StockAlert, StockTransferDto,
-    SystemHealth, TransferOrder, UpdateCarDto, Warehouse, WarehouseId,
+    AbcClass, AlertLevel, AlertThreshold, CarAging, CarClassification, CarDepreciation, CarEntity,
+    CarFilter, CarId, CarPriceHistory, CarResponse, CarSearchQuery, CarSearchRequest,
+    CarSearchResult, CarStatus, CarUpdateData, ClassificationReport, CreateCarDto,
+    CreatePricingRuleDto, CreatePurchaseOrderDto, CreateReservationDto, CreateSupplierDto,
+    DashboardStats, DemandForecast, ForecastModel, ForecastModelScore, ForecastPoint,
+    ForecastQuery, GoodsReceiptDto, HealthStatus, InventoryAgingReport, InventoryAlertSummary,
+    InventoryMetrics, InventoryStatusStat, InventoryValuation, JobExecution, JobExecutionQuery,
+    JobScheduleStatus, MetricDelta, MetricValues, MetricsBucket, MetricsGapFill,
+    MetricsHistoryPoint, MetricsHistoryQuery, MetricsHistoryRange, MetricsHistoryResponse,
+    MetricsPeriod, NewPurchaseOrderLine, PURCHASE_ORDER_ORIGIN_MANUAL,
+    PURCHASE_ORDER_ORIGIN_REPLENISHMENT, PaginatedResponse, PriceChange, PriceChangeStatus,
+    PriceQuote, PricingRule, PurchaseOrder, PurchaseOrderListQuery, PurchaseOrderResponse,
+    RebalancingAcceptance, RebalancingProposal, RebalancingStatus, ReorderOverrideDto,
+    ReorderParametersResponse, ReorderSettings, ReservationResponse, ReservationStatus,
+    SaleReceipt, SalesVelocity, SchedulePriceChangeDto, ScheduledPriceChange, SetAlertThresholdDto,
+    SetSupplierPriceDto, SlowMover, StockAlert, StockAlertRow, StockTransferDto, Supplier,
+    SupplierDetail, SupplierId, SupplierPrice, SystemHealth, TransferOrder, UpdateCarDto,
+    Warehouse, WarehouseId,
 };
+use crate::pool_manager::PoolHandle;
+use crate::pricing;
+use crate::rebalancing;
 use crate::repositories::{
-    CarCommandRepository, CarQueryRepository, CarRepository, InventoryAnalyticsRepository,
+    AgingRepository, CarCommandRepository, CarQueryRepository, CarRepository,
+    ClassificationRepository, InventoryAnalyticsRepository, PriceHistoryRepository,
+    PricingRepository, PurchasingRepository, RebalancingRepository, ReorderRepository,
     ReservationRepository, SalesRepository, WarehouseRepository,
 };
+use crate::scheduler::Scheduler;
 use crate::uow::UnitOfWorkFactory;
 
+const MAX_SALES_VELOCITY_DAYS: i32 = 365;
+const DEFAULT_FORECAST_HORIZON_DAYS: u32 = 30;
+const MAX_FORECAST_HORIZON_DAYS: u32 = 180;
+const DEFAULT_FORECAST_HISTORY_DAYS: u32 = 180;
+const MIN_FORECAST_HISTORY_DAYS: u32 = 14;
+const MAX_FORECAST_HISTORY_DAYS: u32 = 730;
+const STOCKOUT_HISTORY_DAYS: i32 = 90;
+const STOCKOUT_HORIZON_DAYS: usize = 90;
+/// Stockout forecasts only move with daily sales, so alerts reuse them for a while.
+const STOCKOUT_FORECAST_TTL: Duration = Duration::from_secs(15 * 60);
+const STOCKOUT_FORECAST_CAPACITY: u64 = 10_000;
+const REORDER_HISTORY_LIMIT: i64 = 20;
+const PURCHASE_ORDER_LIST_LIMIT: i64 = 100;
+const DEFAULT_METRICS_HISTORY_DAYS: i64 = 7;
+const MAX_METRICS_HISTORY_POINTS: i64 = 1000;
+const SCHEDULED_PRICE_CHANGE_BATCH: i64 = 500;
+
+/// 400 for a query parameter or body field that fails a check the DTO's
+/// validation can't express, reported against `field` like derived ones.
+fn invalid_field(field: &'static str, code: &'static str, message: String) -> AppError {
+    let mut error = validator::ValidationError::new(code);
+    error.message = Some(message.into());
+    let mut errors = validator::ValidationErrors::new();
+    errors.add(field, error);
+    AppError::ValidationError(errors)
+}
+
 #[derive(Clone)]
 pub struct CarService {
     query_repo: Arc<dyn CarQueryRepository + Send + Sync>,
     command_repo: Arc<dyn CarCommandRepository + Send + Sync>,
     cache: QueryCache,
+    depreciation: DepreciationConfig,
 }
 
 impl CarService {
@@ -40,6 +96,20 @@ impl CarService {
             query_repo,
             command_repo,
             cache: QueryCache::new(),
+            depreciation: DepreciationConfig::default(),
+        }
+    }
+
+    pub fn with_cache(
+        query_repo: Arc<dyn CarQueryRepository + Send + Sync>,
+        command_repo: Arc<dyn CarCommandRepository + Send + Sync>,
+        cache: QueryCache,
+    ) -> Self {
+        Self {
+            query_repo,
+            command_repo,
+            cache,
+            depreciation: DepreciationConfig::default(),
         }
     }
 
@@ -48,9 +118,15 @@ impl CarService {
             query_repo: Arc::clone(&repo) as Arc<dyn CarQueryRepository + Send + Sync>,
             command_repo: Arc::clone(&repo) as Arc<dyn CarCommandRepository + Send + Sync>,
             cache: QueryCache::new(),
+            depreciation: DepreciationConfig::default(),
         }
     }
 
+    pub fn with_depreciation(mut self, depreciation: DepreciationConfig) -> Self {
+        self.depreciation = depreciation;
+        self
+    }
+
     #[instrument(skip(self))]
     pub async fn create_car(&self, dto: CreateCarDto) -> AppResult<CarResponse> {
         let entity = self
@@ -181,6 +257,7 @@ impl CarService {
             let filter = CarFilter {
                 brand: None,
                 status: request.status.as_ref().map(|s| format!("{:?}", s)),
+                ..CarFilter::default()
             };
             let (entities, total) = self.query_repo.find_all(&filter, &pagination).await?;
 
@@ -231,17 +308,34 @@ impl CarService {
             .await
     }
 
-    pub async fn get_depreciation_report(&self) -> AppResult<Vec<CarResponse>> {
+    pub async fn get_depreciation_report(&self) -> AppResult<Vec<CarDepreciation>> {
         let query_repo = Arc::clone(&self.query_repo);
+        let config = &self.depreciation;
 
         self.cache
             .get_depreciation(|| async {
+                let today = depreciation::today();
                 let cars = query_repo.get_depreciation_report().await?;
-                Ok(cars.into_iter().map(CarResponse::from).collect())
+                Ok(cars
+                    .into_iter()
+                    .map(|car| depreciation::appraise(car, config, today))
+                    .collect())
             })
             .await
     }
 
+    pub async fn get_inventory_valuation(&self) -> AppResult<InventoryValuation> {
+        let cars = self.get_depreciation_report().await?;
+        let stock = self.query_repo.get_warehouse_stock().await?;
+        let (totals, warehouses) = depreciation::valuation(&cars, stock);
+
+        Ok(InventoryValuation {
+            generated_at: Utc::now(),
+            totals,
+            warehouses,
+        })
+    }
+
     pub async fn get_low_stock_report(
         &self,
         threshold: Option<i32>,
@@ -257,8 +351,8 @@ impl CarService {
             .await
     }
 
-    pub fn cache_metrics(&self) -> crate::cache::CacheMetrics {
-        self.cache.metrics()
+    pub async fn cache_metrics(&self) -> crate::cache::CacheMetrics {
+        self.cache.metrics().await
     }
 }
 
@@ -371,16 +465,17 @@ impl ReservationService {
 #[async_trait]
 pub trait HealthCheckService: Send + Sync {
     async fn check_db(&self) -> HealthStatus;
+    async fn check_migrations(&self) -> HealthStatus;
     async fn check_full(&self) -> SystemHealth;
 }
 
 pub struct HealthCheckServiceImpl {
-    pub pool: PgPool,
+    pub pool: PoolHandle,
     pub config: DatabaseConfig,
 }
 
 impl HealthCheckServiceImpl {
-    pub fn new(pool: PgPool, config: DatabaseConfig) -> Self {
+    pub fn new(pool: PoolHandle, config: DatabaseConfig) -> Self {
         Self { pool, config }
     }
 
@@ -389,7 +484,7 @@ impl HealthCheckServiceImpl {
     ) -> Result<sqlx::pool::PoolConnection<sqlx::Postgres>, HealthStatus> {
         match timeout(
             self.config.health_check_acquire_timeout(),
-            self.pool.acquire(),
+            self.pool.get().acquire(),
         )
         .await
         {
@@ -459,6 +554,35 @@ impl HealthCheckService for HealthCheckServiceImpl {
         status
     }
 
+    async fn check_migrations(&self) -> HealthStatus {
+        let applied: Vec<i64> = match timeout(
+            self.config.health_check_timeout(),
+            sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+                .fetch_all(&self.pool.get()),
+        )
+        .await
+        {
+            Ok(Ok(versions)) => versions,
+            Ok(Err(e)) => {
+                return HealthStatus::Unhealthy(format!("Failed to read migrations: {}", e));
+            }
+            Err(_) => return HealthStatus::Degraded("Migration check timed out".to_string()),
+        };
+
+        let pending: Vec<i64> = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .filter(|version| !applied.contains(version))
+            .collect();
+
+        if pending.is_empty() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Unhealthy(format!("Pending migrations: {:?}", pending))
+        }
+    }
+
     async fn check_full(&self) -> SystemHealth {
         let start = std::time::Instant::now();
 
@@ -601,159 +725,1470 @@ impl WarehouseService {
     }
 }
 
-pub struct InventoryAnalyticsService {
-    analytics_repo: Arc<dyn InventoryAnalyticsRepository>,
+pub struct ReorderService {
+    reorder_repo: Arc<dyn ReorderRepository>,
 }
 
-impl InventoryAnalyticsService {
-    pub fn new(analytics_repo: Arc<dyn InventoryAnalyticsRepository>) -> Self {
-        Self { analytics_repo }
+impl ReorderService {
+    pub fn new(reorder_repo: Arc<dyn ReorderRepository>) -> Self {
+        Self { reorder_repo }
     }
 
-    pub async fn get_stock_alerts(&self) -> AppResult<InventoryAlertSummary> {
-        let rows = self
-            .analytics_repo
-            .get_stock_alerts()
+    pub async fn get_parameters(&self, car_id: &CarId) -> AppResult<ReorderParametersResponse> {
+        let settings = self
+            .reorder_repo
+            .get_settings(car_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        self.with_history(settings).await
+    }
+
+    pub async fn set_overrides(
+        &self,
+        car_id: &CarId,
+        overrides: ReorderOverrideDto,
+    ) -> AppResult<ReorderParametersResponse> {
+        let settings = self
+            .reorder_repo
+            .set_overrides(car_id, &overrides)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        info!(
+            car_id = %car_id,
+            reorder_point_override = ?overrides.reorder_point,
+            economic_order_qty_override = ?overrides.economic_order_qty,
+            lead_time_days = ?overrides.lead_time_days,
+            "Reorder overrides updated"
+        );
+
+        self.with_history(settings).await
+    }
+
+    async fn with_history(
+        &self,
+        settings: ReorderSettings,
+    ) -> AppResult<ReorderParametersResponse> {
+        let history = self
+            .reorder_repo
+            .list_changes(&settings.car_id, REORDER_HISTORY_LIMIT)
             .await
             .map_err(AppError::DatabaseError)?;
 
-        let alerts: Vec<StockAlert> = rows.into_iter().map(StockAlert::from).collect();
+        Ok(ReorderParametersResponse { settings, history })
+    }
+}
 
-        let critical_count = alerts
-            .iter()
-            .filter(|a| matches!(a.alert_level, AlertLevel::Critical))
-            .count() as i64;
-        let warning_count = alerts
-            .iter()
-            .filter(|a| matches!(a.alert_level, AlertLevel::Warning))
-            .count() as i64;
+pub struct PurchasingService {
+    purchasing_repo: Arc<dyn PurchasingRepository>,
+}
 
-        Ok(InventoryAlertSummary {
-            critical_count,
-            warning_count,
-            total_alerts: alerts.len() as i64,
-            alerts,
-        })
+impl PurchasingService {
+    pub fn new(purchasing_repo: Arc<dyn PurchasingRepository>) -> Self {
+        Self { purchasing_repo }
     }
 
-    pub async fn get_sales_velocity(&self, days: i32) -> AppResult<Vec<SalesVelocity>> {
-        self.analytics_repo
-            .get_sales_velocity(days)
+    pub async fn create_supplier(&self, dto: CreateSupplierDto) -> AppResult<Supplier> {
+        let supplier_id = SupplierId::new(dto.supplier_id)?;
+
+        let supplier = self
+            .purchasing_repo
+            .create_supplier(
+                &supplier_id,
+                &dto.name,
+                dto.contact_email.as_deref(),
+                dto.lead_time_days,
+                dto.min_order_qty.unwrap_or(1),
+            )
+            .await
+            .map_err(|e| AppError::from_db(e, "Supplier"))?;
+
+        info!(
+            supplier_id = %supplier.supplier_id,
+            name = %supplier.name,
+            "Supplier created successfully"
+        );
+
+        Ok(supplier)
+    }
+
+    pub async fn list_suppliers(&self) -> AppResult<Vec<Supplier>> {
+        self.purchasing_repo
+            .list_suppliers()
             .await
             .map_err(AppError::DatabaseError)
     }
 
-    pub async fn get_inventory_metrics(&self) -> AppResult<InventoryMetrics> {
-        self.analytics_repo
-            .get_inventory_metrics()
+    pub async fn get_supplier(&self, supplier_id: SupplierId) -> AppResult<SupplierDetail> {
+        let supplier = self.find_supplier(&supplier_id).await?;
+        let prices = self
+            .purchasing_repo
+            .list_supplier_prices(&supplier_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(SupplierDetail { supplier, prices })
+    }
+
+    pub async fn set_supplier_price(
+        &self,
+        supplier_id: SupplierId,
+        car_id: CarId,
+        dto: SetSupplierPriceDto,
+    ) -> AppResult<SupplierPrice> {
+        self.find_supplier(&supplier_id).await?;
+
+        self.purchasing_repo
+            .upsert_supplier_price(&supplier_id, &car_id, &dto)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("23503") => {
+                    PurchaseOrderError::CarNotFound(car_id.to_string()).into()
+                }
+                e => AppError::DatabaseError(e),
+            })
+    }
+
+    pub async fn create_purchase_order(
+        &self,
+        dto: CreatePurchaseOrderDto,
+    ) -> AppResult<PurchaseOrderResponse> {
+        let supplier_id = SupplierId::new(dto.supplier_id)?;
+        let warehouse_id =
+            WarehouseId::new(dto.warehouse_id).map_err(|e| AppError::InvalidJson(e.to_string()))?;
+
+        let mut lines = Vec::with_capacity(dto.lines.len());
+        for line in dto.lines {
+            let car_id = CarId::new(line.car_id)?;
+            if lines
+                .iter()
+                .any(|l: &NewPurchaseOrderLine| l.car_id == car_id)
+            {
+                return Err(AppError::InvalidJson(format!(
+                    "Car {} appears on more than one line",
+                    car_id
+                )));
+            }
+            lines.push(NewPurchaseOrderLine {
+                car_id,
+                quantity: line.quantity,
+                unit_cost: line
+                    .unit_cost
+                    .and_then(BigDecimal::from_f64)
+                    .map(|cost| cost.round(2)),
+                note: None,
+            });
+        }
+
+        let order = self
+            .purchasing_repo
+            .create_purchase_order(&supplier_id, &warehouse_id, dto.notes.as_deref(), &lines)
+            .await?;
+
+        info!(
+            po_id = %order.order.po_id,
+            supplier_id = %supplier_id,
+            warehouse_id = %warehouse_id,
+            lines = order.lines.len(),
+            "Purchase order drafted"
+        );
+
+        Ok(order)
+    }
+
+    pub async fn list_purchase_orders(
+        &self,
+        query: PurchaseOrderListQuery,
+    ) -> AppResult<Vec<PurchaseOrder>> {
+        let supplier_id = query.supplier_id.map(SupplierId::new).transpose()?;
+        if let Some(origin) = query.origin.as_deref()
+            && ![
+                PURCHASE_ORDER_ORIGIN_MANUAL,
+                PURCHASE_ORDER_ORIGIN_REPLENISHMENT,
+            ]
+            .contains(&origin)
+        {
+            return Err(invalid_field(
+                "origin",
+                "range",
+                format!(
+                    "must be `{PURCHASE_ORDER_ORIGIN_MANUAL}` or `{PURCHASE_ORDER_ORIGIN_REPLENISHMENT}`"
+                ),
+            ));
+        }
+
+        self.purchasing_repo
+            .list_purchase_orders(
+                query.status,
+                supplier_id.as_ref(),
+                query.origin.as_deref(),
+                PURCHASE_ORDER_LIST_LIMIT,
+            )
             .await
             .map_err(AppError::DatabaseError)
     }
+
+    pub async fn get_purchase_order(&self, po_id: Uuid) -> AppResult<PurchaseOrderResponse> {
+        self.purchasing_repo
+            .find_purchase_order(po_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| PurchaseOrderError::PurchaseOrderNotFound(po_id).into())
+    }
+
+    pub async fn submit_purchase_order(&self, po_id: Uuid) -> AppResult<PurchaseOrderResponse> {
+        let order = self.purchasing_repo.submit_purchase_order(po_id).await?;
+
+        info!(
+            po_id = %po_id,
+            expected_at = ?order.expected_at,
+            "Purchase order submitted"
+        );
+
+        self.get_purchase_order(po_id).await
+    }
+
+    pub async fn cancel_purchase_order(&self, po_id: Uuid) -> AppResult<PurchaseOrderResponse> {
+        self.purchasing_repo.cancel_purchase_order(po_id).await?;
+
+        info!(po_id = %po_id, "Purchase order cancelled");
+
+        self.get_purchase_order(po_id).await
+    }
+
+    pub async fn receive_goods(
+        &self,
+        po_id: Uuid,
+        dto: GoodsReceiptDto,
+    ) -> AppResult<PurchaseOrderResponse> {
+        let mut lines: Vec<(CarId, i32)> = Vec::with_capacity(dto.lines.len());
+        for line in dto.lines {
+            let car_id = CarId::new(line.car_id)?;
+            if lines.iter().any(|(id, _)| *id == car_id) {
+                return Err(AppError::InvalidJson(format!(
+                    "Car {} appears on more than one line",
+                    car_id
+                )));
+            }
+            lines.push((car_id, line.quantity));
+        }
+
+        let order = self.purchasing_repo.receive_goods(po_id, &lines).await?;
+
+        info!(
+            po_id = %po_id,
+            warehouse_id = %order.order.warehouse_id,
+            units = lines.iter().map(|(_, quantity)| quantity).sum::<i32>(),
+            status = ?order.order.status,
+            "Goods received"
+        );
+
+        Ok(order)
+    }
+
+    async fn find_supplier(&self, supplier_id: &SupplierId) -> AppResult<Supplier> {
+        self.purchasing_repo
+            .find_supplier(supplier_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or_else(|| PurchaseOrderError::SupplierNotFound(supplier_id.to_string()).into())
+    }
 }
 
-pub struct SaleService {
-    uow_factory: Arc<dyn UnitOfWorkFactory>,
-    car_repo: Arc<dyn CarRepository>,
-    reservation_repo: Arc<dyn ReservationRepository>,
-    sales_repo: Arc<dyn SalesRepository>,
+pub struct RebalancingService {
+    rebalancing_repo: Arc<dyn RebalancingRepository>,
+    warehouse_service: Arc<WarehouseService>,
+    config: RebalancingConfig,
 }
 
-impl SaleService {
+impl RebalancingService {
     pub fn new(
-        uow_factory: Arc<dyn UnitOfWorkFactory>,
-        car_repo: Arc<dyn CarRepository>,
-        reservation_repo: Arc<dyn ReservationRepository>,
-        sales_repo: Arc<dyn SalesRepository>,
+        rebalancing_repo: Arc<dyn RebalancingRepository>,
+        warehouse_service: Arc<WarehouseService>,
+        config: RebalancingConfig,
     ) -> Self {
         Self {
-            uow_factory,
-            car_repo,
-            reservation_repo,
-            sales_repo,
+            rebalancing_repo,
+            warehouse_service,
+            config,
         }
     }
 
-    pub async fn process_sale(
-        &self,
-        reservation_id: Uuid,
-        customer_id: String,
-    ) -> AppResult<SaleReceipt> {
-        let mut uow = self.uow_factory.create_uow().await?;
+    /// Recomputes proposals from current availability and demand. Proposals
+    /// still open from the previous run are superseded.
+    pub async fn generate_proposals(&self) -> AppResult<Vec<RebalancingProposal>> {
+        let locations = self
+            .rebalancing_repo
+            .list_location_demand(self.config.demand_days as i32)
+            .await
+            .map_err(AppError::DatabaseError)?;
 
-        let reservation = self
-            .reservation_repo
-            .confirm_in_uow(&mut uow, reservation_id)
+        let proposals = rebalancing::propose(&locations, &self.config);
+        let stored = self
+            .rebalancing_repo
+            .replace_proposals(&proposals)
             .await
-            .map_err(|e| match e {
-                ReservationError::InsufficientStock {
-                    requested,
-                    available,
-                } => AppError::InsufficientStock {
-                    requested: requested as u32,
-                    available: available as u32,
-                },
-                ReservationError::CarNotFound => AppError::NotFound,
-                ReservationError::ReservationNotFound => AppError::ReservationNotFound,
-                ReservationError::ReservationExpired => AppError::ReservationExpired,
-                ReservationError::Database(db_err) => AppError::DatabaseError(db_err),
-            })?;
+            .map_err(AppError::DatabaseError)?;
 
-        let car = self
-            .car_repo
-            .find_by_id_in_uow(&mut uow, reservation.car_id.clone())
+        info!(
+            locations = locations.len(),
+            proposals = stored.len(),
+            units = stored.iter().map(|p| p.quantity as i64).sum::<i64>(),
+            "Rebalancing proposals generated"
+        );
+
+        Ok(stored)
+    }
+
+    pub async fn list_proposals(
+        &self,
+        status: Option<RebalancingStatus>,
+    ) -> AppResult<Vec<RebalancingProposal>> {
+        self.rebalancing_repo
+            .list_proposals(status)
             .await
-            .map_err(AppError::DatabaseError)?
-            .ok_or(AppError::NotFound)?;
+            .map_err(AppError::DatabaseError)
+    }
 
-        let update_data = CarUpdateData {
-            brand: car.brand.clone(),
-            model: car.model.clone(),
-            year: car.year,
-            color: car.color.unwrap_or_default(),
-            engine_type: car.engine_type.clone(),
-            transmission: car.transmission.unwrap_or_default(),
-            price: car.price.clone(),
-            quantity_in_stock: car.quantity_in_stock - reservation.quantity,
-            status: if car.quantity_in_stock - reservation.quantity == 0 {
-                CarStatus::Sold
-            } else {
-                car.status.clone()
-            },
-        };
+    /// Executes the proposal as a stock transfer. The proposal is claimed
+    /// first so concurrent accepts cannot both move stock, and released again
+    /// if the transfer fails.
+    pub async fn accept(&self, proposal_id: Uuid) -> AppResult<RebalancingAcceptance> {
+        let claimed = self
+            .transition(proposal_id, RebalancingStatus::Accepted)
+            .await?;
 
-        self.car_repo
-            .update_in_uow(&mut uow, &reservation.car_id, update_data)
+        let transfer = match self
+            .warehouse_service
+            .transfer_stock(StockTransferDto {
+                from_warehouse_id: claimed.from_warehouse_id.to_string(),
+                to_warehouse_id: claimed.to_warehouse_id.to_string(),
+                car_id: claimed.car_id.to_string(),
+                quantity: claimed.quantity,
+                reason: Some(format!("Rebalancing proposal {proposal_id}")),
+            })
             .await
-            .map_err(AppError::DatabaseError)?;
+        {
+            Ok(transfer) => transfer,
+            Err(e) => {
+                self.rebalancing_repo
+                    .set_proposal_status(
+                        proposal_id,
+                        RebalancingStatus::Accepted,
+                        RebalancingStatus::Proposed,
+                        None,
+                    )
+                    .await
+                    .map_err(AppError::DatabaseError)?;
+                return Err(e);
+            }
+        };
 
-        let sale_id = self
-            .sales_repo
-            .record_sale_in_uow(
-                &mut uow,
-                &reservation.car_id,
-                reservation.quantity,
-                &car.price,
-                Some(&customer_id),
+        let proposal = self
+            .rebalancing_repo
+            .set_proposal_status(
+                proposal_id,
+                RebalancingStatus::Accepted,
+                RebalancingStatus::Accepted,
+                Some(transfer.transfer_id),
             )
             .await
-            .map_err(AppError::DatabaseError)?;
+            .map_err(AppError::DatabaseError)?
+            .unwrap_or(claimed);
 
-        uow.commit().await?;
+        info!(
+            proposal_id = %proposal_id,
+            transfer_id = %transfer.transfer_id,
+            car_id = %proposal.car_id,
+            quantity = proposal.quantity,
+            "Rebalancing proposal accepted"
+        );
 
-        Ok(SaleReceipt {
-            sale_id,
-            reservation_id,
-            car_id: reservation.car_id,
-            quantity: reservation.quantity,
-            total_price: car.price * reservation.quantity,
-        })
+        Ok(RebalancingAcceptance { proposal, transfer })
     }
-}
 
-pub struct SaleReceipt {
-    pub sale_id: Uuid,
-    pub reservation_id: Uuid,
-    pub car_id: CarId,
-    pub quantity: i32,
-    pub total_price: BigDecimal,
+    pub async fn reject(&self, proposal_id: Uuid) -> AppResult<RebalancingProposal> {
+        let proposal = self
+            .transition(proposal_id, RebalancingStatus::Rejected)
+            .await?;
+
+        info!(proposal_id = %proposal_id, "Rebalancing proposal rejected");
+
+        Ok(proposal)
+    }
+
+    async fn transition(
+        &self,
+        proposal_id: Uuid,
+        to: RebalancingStatus,
+    ) -> AppResult<RebalancingProposal> {
+        if let Some(proposal) = self
+            .rebalancing_repo
+            .set_proposal_status(proposal_id, RebalancingStatus::Proposed, to, None)
+            .await
+            .map_err(AppError::DatabaseError)?
+        {
+            return Ok(proposal);
+        }
+
+        let current = self
+            .rebalancing_repo
+            .find_proposal(proposal_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        Err(AppError::BusinessRuleViolation(format!(
+            "Rebalancing proposal {} is {:?}, only Proposed proposals can be decided",
+            proposal_id, current.status
+        )))
+    }
+}
+
+pub struct ClassificationService {
+    classification_repo: Arc<dyn ClassificationRepository>,
+    cache: QueryCache,
+    config: ClassificationConfig,
+}
+
+impl ClassificationService {
+    pub fn new(
+        classification_repo: Arc<dyn ClassificationRepository>,
+        cache: QueryCache,
+        config: ClassificationConfig,
+    ) -> Self {
+        Self {
+            classification_repo,
+            cache,
+            config,
+        }
+    }
+
+    /// Classes computed from current sales; the stored ones may lag until
+    /// the next `inventory_classification` run.
+    pub async fn get_report(&self) -> AppResult<ClassificationReport> {
+        Ok(ClassificationReport {
+            history_weeks: self.config.history_weeks,
+            generated_at: Utc::now(),
+            cars: self.classify().await?,
+        })
+    }
+
+    /// Recomputes the classes and stores them on the cars. Returns the number
+    /// of cars classified and how many of them changed class.
+    pub async fn refresh_classes(&self) -> AppResult<(usize, u64)> {
+        let classifications = self.classify().await?;
+        let changed = self
+            .classification_repo
+            .apply_classifications(&classifications)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        // Cached cars and class-filtered lists carry the old classes.
+        if changed > 0 {
+            self.cache.invalidate_all_cars().await;
+        }
+
+        Ok((classifications.len(), changed))
+    }
+
+    pub async fn list_alert_thresholds(&self) -> AppResult<Vec<AlertThreshold>> {
+        self.classification_repo
+            .list_alert_thresholds()
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+
+    pub async fn set_alert_threshold(
+        &self,
+        abc_class: AbcClass,
+        dto: SetAlertThresholdDto,
+    ) -> AppResult<AlertThreshold> {
+        if dto.warning_ratio < dto.critical_ratio {
+            return Err(AppError::InvalidJson(
+                "warning_ratio cannot be below critical_ratio".to_string(),
+            ));
+        }
+
+        let threshold = self
+            .classification_repo
+            .set_alert_threshold(abc_class, dto.critical_ratio, dto.warning_ratio)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        info!(
+            abc_class = ?abc_class,
+            critical_ratio = threshold.critical_ratio,
+            warning_ratio = threshold.warning_ratio,
+            "Alert thresholds updated"
+        );
+
+        Ok(threshold)
+    }
+
+    async fn classify(&self) -> AppResult<Vec<CarClassification>> {
+        let inputs = self
+            .classification_repo
+            .list_classification_inputs(self.config.history_weeks as i32)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(classification::classify(inputs, &self.config))
+    }
+}
+
+pub struct AgingService {
+    aging_repo: Arc<dyn AgingRepository>,
+    config: AgingConfig,
+    holding_cost_rate: f64,
+}
+
+impl AgingService {
+    pub fn new(
+        aging_repo: Arc<dyn AgingRepository>,
+        config: AgingConfig,
+        holding_cost_rate: f64,
+    ) -> Self {
+        Self {
+            aging_repo,
+            config,
+            holding_cost_rate,
+        }
+    }
+
+    pub async fn get_report(&self) -> AppResult<InventoryAgingReport> {
+        let mut cars = self.age_inventory().await?;
+        cars.sort_by(|a, b| {
+            b.oldest_unit_days
+                .cmp(&a.oldest_unit_days)
+                .then_with(|| a.car_id.as_str().cmp(b.car_id.as_str()))
+        });
+
+        Ok(InventoryAgingReport {
+            generated_at: Utc::now(),
+            holding_cost_rate: self.holding_cost_rate,
+            buckets: aging::total_buckets(&cars),
+            cars,
+        })
+    }
+
+    pub async fn get_slow_movers(&self) -> AppResult<Vec<SlowMover>> {
+        Ok(aging::slow_movers(
+            self.age_inventory().await?,
+            &self.config,
+        ))
+    }
+
+    async fn age_inventory(&self) -> AppResult<Vec<CarAging>> {
+        let now = Utc::now();
+        let inputs = self
+            .aging_repo
+            .list_aging_inputs(self.config.sales_days as i32)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(inputs
+            .into_iter()
+            .map(|input| aging::age_car(input, now, self.holding_cost_rate, self.config.sales_days))
+            .collect())
+    }
+}
+
+pub struct PriceHistoryService {
+    price_repo: Arc<dyn PriceHistoryRepository>,
+    cache: QueryCache,
+    config: PriceHistoryConfig,
+}
+
+impl PriceHistoryService {
+    pub fn new(
+        price_repo: Arc<dyn PriceHistoryRepository>,
+        cache: QueryCache,
+        config: PriceHistoryConfig,
+    ) -> Self {
+        Self {
+            price_repo,
+            cache,
+            config,
+        }
+    }
+
+    pub async fn get_history(&self, car_id: CarId) -> AppResult<CarPriceHistory> {
+        let current_price = self.current_price(&car_id).await?;
+
+        let changes = self
+            .price_repo
+            .list_price_changes(&car_id, self.config.elasticity_window_days as i32)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let scheduled = self
+            .price_repo
+            .list_pending_changes(&car_id)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(CarPriceHistory {
+            car_id,
+            current_price,
+            changes: changes.into_iter().map(PriceChange::from).collect(),
+            scheduled,
+        })
+    }
+
+    #[instrument(skip(self))]
+    pub async fn schedule_change(
+        &self,
+        car_id: CarId,
+        dto: SchedulePriceChangeDto,
+    ) -> AppResult<ScheduledPriceChange> {
+        if dto.new_price <= 0 {
+            return Err(invalid_field(
+                "new_price",
+                "range",
+                "must be positive".into(),
+            ));
+        }
+        if dto.effective_at <= Utc::now() {
+            return Err(invalid_field(
+                "effective_at",
+                "range",
+                "must be in the future".into(),
+            ));
+        }
+        self.current_price(&car_id).await?;
+
+        let change = self
+            .price_repo
+            .schedule_change(&car_id, &dto)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        info!(
+            car_id = %car_id,
+            change_id = %change.change_id,
+            effective_at = %change.effective_at,
+            "Price change scheduled"
+        );
+
+        Ok(change)
+    }
+
+    pub async fn cancel_change(
+        &self,
+        car_id: CarId,
+        change_id: Uuid,
+    ) -> AppResult<ScheduledPriceChange> {
+        if let Some(change) = self
+            .price_repo
+            .cancel_scheduled_change(&car_id, change_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+        {
+            info!(car_id = %car_id, change_id = %change_id, "Scheduled price change cancelled");
+            return Ok(change);
+        }
+
+        let current = self
+            .price_repo
+            .find_scheduled_change(&car_id, change_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        Err(AppError::BusinessRuleViolation(format!(
+            "Price change {} is {:?}, only Pending changes can be cancelled",
+            change_id, current.status
+        )))
+    }
+
+    /// Applies every due scheduled change; returns how many were processed.
+    pub async fn apply_due_changes(&self) -> AppResult<usize> {
+        let processed = self
+            .price_repo
+            .apply_due_changes(SCHEDULED_PRICE_CHANGE_BATCH)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        for change in &processed {
+            if change.status == PriceChangeStatus::Applied {
+                self.cache.invalidate_car(change.car_id.as_str()).await;
+            }
+        }
+
+        Ok(processed.len())
+    }
+
+    async fn current_price(&self, car_id: &CarId) -> AppResult<BigDecimal> {
+        self.price_repo
+            .find_current_price(car_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)
+    }
+}
+
+pub struct JobService {
+    scheduler: Scheduler,
+}
+
+impl JobService {
+    pub fn new(scheduler: Scheduler) -> Self {
+        Self { scheduler }
+    }
+
+    pub async fn list_executions(
+        &self,
+        query: JobExecutionQuery,
+    ) -> AppResult<PaginatedResponse<JobExecution>> {
+        let pagination = query.pagination();
+        let (_, _, page, page_size) = pagination.normalize();
+
+        let (executions, total) = self
+            .scheduler
+            .executions()
+            .list(&query.filter(), &pagination)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(PaginatedResponse::new(executions, total, page, page_size))
+    }
+
+    pub fn list_schedules(&self) -> Vec<JobScheduleStatus> {
+        self.scheduler.statuses()
+    }
+
+    #[instrument(skip(self))]
+    pub async fn trigger(&self, name: &str) -> AppResult<JobExecution> {
+        let execution = self.scheduler.trigger(name).await?;
+        info!(job_id = %execution.job_id, task = name, "Job triggered manually");
+        Ok(execution)
+    }
+
+    pub fn pause(&self, name: &str) -> AppResult<JobScheduleStatus> {
+        self.scheduler.pause(name)
+    }
+
+    pub fn resume(&self, name: &str) -> AppResult<JobScheduleStatus> {
+        self.scheduler.resume(name)
+    }
+}
+
+#[derive(Clone)]
+struct StockoutForecast {
+    model: ForecastModel,
+    points: Arc<[f64]>,
+}
+
+pub struct InventoryAnalyticsService {
+    analytics_repo: Arc<dyn InventoryAnalyticsRepository>,
+    stockout_forecasts: moka::future::Cache<String, StockoutForecast>,
+}
+
+impl InventoryAnalyticsService {
+    pub fn new(analytics_repo: Arc<dyn InventoryAnalyticsRepository>) -> Self {
+        Self {
+            analytics_repo,
+            stockout_forecasts: moka::future::Cache::builder()
+                .max_capacity(STOCKOUT_FORECAST_CAPACITY)
+                .time_to_live(STOCKOUT_FORECAST_TTL)
+                .build(),
+        }
+    }
+
+    pub async fn get_stock_alerts(&self) -> AppResult<InventoryAlertSummary> {
+        let mut rows = self
+            .analytics_repo
+            .get_stock_alerts()
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let forecasts = self.stockout_forecasts(&rows).await?;
+
+        let alerts: Vec<StockAlert> = rows
+            .drain(..)
+            .map(|mut row| {
+                let forecast = forecasts.get(row.car_id.as_str());
+                if let Some(forecast) = forecast {
+                    row.days_until_stockout = forecasting::days_until_stockout(
+                        &forecast.points,
+                        row.available_stock as f64,
+                    );
+                }
+
+                let mut alert = StockAlert::from(row);
+                alert.forecast_model = forecast.map(|f| f.model);
+                alert
+            })
+            .collect();
+
+        let critical_count = alerts
+            .iter()
+            .filter(|a| matches!(a.alert_level, AlertLevel::Critical))
+            .count() as i64;
+        let warning_count = alerts
+            .iter()
+            .filter(|a| matches!(a.alert_level, AlertLevel::Warning))
+            .count() as i64;
+
+        Ok(InventoryAlertSummary {
+            critical_count,
+            warning_count,
+            total_alerts: alerts.len() as i64,
+            alerts,
+        })
+    }
+
+    /// Demand forecasts for the alerting cars, reusing cached ones and
+    /// forecasting only the cars whose entry expired.
+    async fn stockout_forecasts(
+        &self,
+        rows: &[StockAlertRow],
+    ) -> AppResult<HashMap<String, StockoutForecast>> {
+        let mut forecasts = HashMap::with_capacity(rows.len());
+        let mut missing = Vec::new();
+        for row in rows {
+            match self.stockout_forecasts.get(row.car_id.as_str()).await {
+                Some(forecast) => {
+                    forecasts.insert(row.car_id.as_str().to_string(), forecast);
+                }
+                None => missing.push(row.car_id.clone()),
+            }
+        }
+
+        for (car_id, history) in self.daily_demand(&missing, STOCKOUT_HISTORY_DAYS).await? {
+            let forecast = forecasting::forecast(&history, STOCKOUT_HORIZON_DAYS);
+            let forecast = StockoutForecast {
+                model: forecast.model,
+                points: forecast.points.into(),
+            };
+            self.stockout_forecasts
+                .insert(car_id.clone(), forecast.clone())
+                .await;
+            forecasts.insert(car_id, forecast);
+        }
+
+        Ok(forecasts)
+    }
+
+    pub async fn get_sales_velocity(&self, days: i32) -> AppResult<Vec<SalesVelocity>> {
+        if !(1..=MAX_SALES_VELOCITY_DAYS).contains(&days) {
+            return Err(invalid_field(
+                "days",
+                "range",
+                format!("must be between 1 and {MAX_SALES_VELOCITY_DAYS}"),
+            ));
+        }
+
+        self.analytics_repo
+            .get_sales_velocity(days)
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+
+    pub async fn get_inventory_metrics(&self) -> AppResult<InventoryMetrics> {
+        self.analytics_repo
+            .get_inventory_metrics()
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+
+    pub async fn get_demand_forecast(
+        &self,
+        car_id: CarId,
+        query: ForecastQuery,
+    ) -> AppResult<DemandForecast> {
+        let horizon_days = query.horizon_days.unwrap_or(DEFAULT_FORECAST_HORIZON_DAYS);
+        let history_days = query.history_days.unwrap_or(DEFAULT_FORECAST_HISTORY_DAYS);
+
+        if !(1..=MAX_FORECAST_HORIZON_DAYS).contains(&horizon_days) {
+            return Err(invalid_field(
+                "horizon_days",
+                "range",
+                format!("must be between 1 and {MAX_FORECAST_HORIZON_DAYS}"),
+            ));
+        }
+        if !(MIN_FORECAST_HISTORY_DAYS..=MAX_FORECAST_HISTORY_DAYS).contains(&history_days) {
+            return Err(invalid_field(
+                "history_days",
+                "range",
+                format!(
+                    "must be between {MIN_FORECAST_HISTORY_DAYS} and {MAX_FORECAST_HISTORY_DAYS}"
+                ),
+            ));
+        }
+
+        let history = self
+            .daily_demand(std::slice::from_ref(&car_id), history_days as i32)
+            .await?
+            .remove(car_id.as_str())
+            .ok_or(AppError::NotFound)?;
+
+        let forecast = forecasting::forecast(&history, horizon_days as usize);
+        let today = Utc::now().date_naive();
+
+        let points = forecast
+            .points
+            .iter()
+            .zip(forecast.lower.iter().zip(&forecast.upper))
+            .enumerate()
+            .map(|(step, (&point, (&lower, &upper)))| ForecastPoint {
+                date: today + chrono::Days::new(step as u64),
+                forecast: point,
+                lower,
+                upper,
+            })
+            .collect();
+
+        Ok(DemandForecast {
+            car_id,
+            model: forecast.model,
+            history_days,
+            horizon_days,
+            interval_level: forecasting::INTERVAL_LEVEL,
+            total_forecast: forecast.points.iter().sum(),
+            points,
+            candidates: forecast
+                .scores
+                .into_iter()
+                .map(|(model, backtest_mae)| ForecastModelScore {
+                    model,
+                    backtest_mae,
+                })
+                .collect(),
+        })
+    }
+
+    async fn daily_demand(
+        &self,
+        car_ids: &[CarId],
+        days: i32,
+    ) -> AppResult<HashMap<String, Vec<f64>>> {
+        if car_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows = self
+            .analytics_repo
+            .get_daily_sales(car_ids, days)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(forecasting::demand_by_car(rows))
+    }
+
+    pub async fn get_metrics_history(
+        &self,
+        query: MetricsHistoryQuery,
+    ) -> AppResult<MetricsHistoryResponse> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query
+            .from
+            .unwrap_or_else(|| to - chrono::Duration::days(DEFAULT_METRICS_HISTORY_DAYS));
+
+        if from >= to {
+            return Err(invalid_field(
+                "from",
+                "range",
+                "must be earlier than `to`".into(),
+            ));
+        }
+
+        // Without an explicit bucket, downsample to the finest one that fits.
+        let bucket = query
+            .bucket
+            .unwrap_or_else(|| MetricsBucket::fit(from, to, MAX_METRICS_HISTORY_POINTS));
+        if bucket.bucket_count(from, to) > MAX_METRICS_HISTORY_POINTS {
+            return Err(invalid_field(
+                "bucket",
+                "range",
+                format!(
+                    "range produces more than {MAX_METRICS_HISTORY_POINTS} {} buckets, use a coarser bucket",
+                    bucket.as_str()
+                ),
+            ));
+        }
+
+        let aggregation = query.aggregation.unwrap_or_default();
+        let fill = query.fill.unwrap_or_default();
+        let range = MetricsHistoryRange {
+            from,
+            to,
+            bucket,
+            aggregation,
+        };
+        let previous_from = from - (to - from);
+
+        let (points, current, previous) = tokio::try_join!(
+            self.analytics_repo.get_metrics_history(&range),
+            self.analytics_repo
+                .get_metrics_period(from, to, aggregation),
+            self.analytics_repo
+                .get_metrics_period(previous_from, from, aggregation),
+        )
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(MetricsHistoryResponse {
+            bucket,
+            aggregation,
+            fill,
+            points: fill_metric_gaps(points, fill),
+            deltas: MetricDelta::between(&current.values, &previous.values),
+            current_period: MetricsPeriod {
+                from,
+                to,
+                samples: current.samples,
+                values: current.values,
+            },
+            previous_period: MetricsPeriod {
+                from: previous_from,
+                to: from,
+                samples: previous.samples,
+                values: previous.values,
+            },
+        })
+    }
+}
+
+fn fill_metric_gaps(
+    points: Vec<MetricsHistoryPoint>,
+    fill: MetricsGapFill,
+) -> Vec<MetricsHistoryPoint> {
+    match fill {
+        MetricsGapFill::None => points.into_iter().filter(|p| p.samples > 0).collect(),
+        MetricsGapFill::Null => points,
+        MetricsGapFill::Zero => points
+            .into_iter()
+            .map(|mut p| {
+                p.values = p.values.zeroed();
+                p
+            })
+            .collect(),
+        MetricsGapFill::Previous => {
+            let mut last: Option<MetricValues> = None;
+            points
+                .into_iter()
+                .map(|mut p| {
+                    if p.samples > 0 {
+                        last = Some(p.values.clone());
+                    } else if let Some(values) = &last {
+                        p.values = values.clone();
+                    }
+                    p
+                })
+                .collect()
+        }
+    }
+}
+
+pub struct PricingService {
+    pricing_repo: Arc<dyn PricingRepository>,
+    aging_repo: Arc<dyn AgingRepository>,
+    config: PricingConfig,
+}
+
+impl PricingService {
+    pub fn new(
+        pricing_repo: Arc<dyn PricingRepository>,
+        aging_repo: Arc<dyn AgingRepository>,
+        config: PricingConfig,
+    ) -> Self {
+        Self {
+            pricing_repo,
+            aging_repo,
+            config,
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn create_rule(&self, mut dto: CreatePricingRuleDto) -> AppResult<PricingRule> {
+        if dto.discount_percent.is_some() == dto.discount_amount.is_some() {
+            return Err(invalid_field(
+                "discount_percent",
+                "required",
+                "exactly one of discount_percent and discount_amount is required".into(),
+            ));
+        }
+        if dto
+            .discount_amount
+            .as_ref()
+            .is_some_and(|amount| *amount <= 0)
+        {
+            return Err(invalid_field(
+                "discount_amount",
+                "range",
+                "must be positive".into(),
+            ));
+        }
+        if dto.floor_price.as_ref().is_some_and(|floor| *floor <= 0) {
+            return Err(invalid_field(
+                "floor_price",
+                "range",
+                "must be positive".into(),
+            ));
+        }
+        if let (Some(min_year), Some(max_year)) = (dto.min_year, dto.max_year)
+            && min_year > max_year
+        {
+            return Err(invalid_field(
+                "max_year",
+                "range",
+                "must not be before min_year".into(),
+            ));
+        }
+        if let (Some(starts_at), Some(ends_at)) = (dto.starts_at, dto.ends_at)
+            && starts_at >= ends_at
+        {
+            return Err(invalid_field(
+                "ends_at",
+                "range",
+                "must be after starts_at".into(),
+            ));
+        }
+        if let Some(bucket) = &dto.aging_bucket
+            && !aging::is_bucket_label(bucket)
+        {
+            return Err(invalid_field(
+                "aging_bucket",
+                "range",
+                format!("unknown bucket '{bucket}', expected 0-30, 31-60, 61-90 or 90+"),
+            ));
+        }
+        dto.promo_code = dto.promo_code.map(|code| normalize_promo_code(&code));
+
+        let rule = self
+            .pricing_repo
+            .create_rule(&dto)
+            .await
+            .map_err(|e| AppError::from_db(e, "Promo code"))?;
+
+        info!(rule_id = %rule.rule_id, name = %rule.name, "Pricing rule created");
+
+        Ok(rule)
+    }
+
+    pub async fn list_rules(&self) -> AppResult<Vec<PricingRule>> {
+        self.pricing_repo
+            .list_rules()
+            .await
+            .map_err(AppError::DatabaseError)
+    }
+
+    pub async fn deactivate_rule(&self, rule_id: Uuid) -> AppResult<PricingRule> {
+        let rule = self
+            .pricing_repo
+            .deactivate_rule(rule_id)
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        info!(rule_id = %rule_id, "Pricing rule deactivated");
+
+        Ok(rule)
+    }
+
+    /// Unit price of `quantity` units of `car` under the current rules. A
+    /// promo code that unlocks no rule for this sale is rejected.
+    pub async fn quote(
+        &self,
+        car_id: &CarId,
+        car: &CarEntity,
+        quantity: i32,
+        promo_code: Option<&str>,
+    ) -> AppResult<PriceQuote> {
+        let now = Utc::now();
+        let rules = self
+            .pricing_repo
+            .list_current_rules()
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let aging_bucket = if rules.iter().any(|rule| rule.aging_bucket.is_some()) {
+            self.aging_repo
+                .find_aging_input(car_id, 1)
+                .await
+                .map_err(AppError::DatabaseError)?
+                .map(|input| aging::bucket_label(aging::oldest_unit_days(&input, now)))
+        } else {
+            None
+        };
+
+        let ctx = pricing::SaleContext {
+            brand: &car.brand,
+            engine_type: &car.engine_type,
+            year: car.year,
+            aging_bucket,
+            quantity,
+            promo_code,
+            now,
+        };
+        if let Some(code) = promo_code
+            && !pricing::promo_code_applies(&rules, &ctx)
+        {
+            return Err(AppError::BusinessRuleViolation(format!(
+                "Promo code {} does not apply to this sale",
+                code
+            )));
+        }
+
+        Ok(pricing::quote(
+            &car.price,
+            &rules,
+            &ctx,
+            self.config.min_price_ratio,
+        ))
+    }
+}
+
+fn normalize_promo_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+pub struct SaleService {
+    uow_factory: Arc<dyn UnitOfWorkFactory>,
+    car_repo: Arc<dyn CarRepository>,
+    reservation_repo: Arc<dyn ReservationRepository>,
+    sales_repo: Arc<dyn SalesRepository>,
+    pricing_service: Arc<PricingService>,
+    cache: QueryCache,
+}
+
+impl SaleService {
+    pub fn new(
+        uow_factory: Arc<dyn UnitOfWorkFactory>,
+        car_repo: Arc<dyn CarRepository>,
+        reservation_repo: Arc<dyn ReservationRepository>,
+        sales_repo: Arc<dyn SalesRepository>,
+        pricing_service: Arc<PricingService>,
+    ) -> Self {
+        Self {
+            uow_factory,
+            car_repo,
+            reservation_repo,
+            sales_repo,
+            pricing_service,
+            cache: QueryCache::new(),
+        }
+    }
+
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    #[instrument(skip(self))]
+    pub async fn process_sale(
+        &self,
+        reservation_id: Uuid,
+        customer_id: String,
+        promo_code: Option<String>,
+    ) -> AppResult<SaleReceipt> {
+        let promo_code = promo_code.map(|code| normalize_promo_code(&code));
+        let mut uow = self.uow_factory.create_uow().await?;
+
+        let reservation = self
+            .reservation_repo
+            .confirm_in_uow(&mut uow, reservation_id)
+            .await
+            .map_err(|e| match e {
+                ReservationError::InsufficientStock {
+                    requested,
+                    available,
+                } => AppError::InsufficientStock {
+                    requested: requested as u32,
+                    available: available as u32,
+                },
+                ReservationError::CarNotFound => AppError::NotFound,
+                ReservationError::ReservationNotFound => AppError::ReservationNotFound,
+                ReservationError::ReservationExpired => AppError::ReservationExpired,
+                ReservationError::Database(db_err) => AppError::DatabaseError(db_err),
+            })?;
+
+        let car = self
+            .car_repo
+            .find_by_id_in_uow(&mut uow, reservation.car_id.clone())
+            .await
+            .map_err(AppError::DatabaseError)?
+            .ok_or(AppError::NotFound)?;
+
+        let pricing = self
+            .pricing_service
+            .quote(
+                &reservation.car_id,
+                &car,
+                reservation.quantity,
+                promo_code.as_deref(),
+            )
+            .await?;
+
+        let update_data = CarUpdateData {
+            brand: car.brand.clone(),
+            model: car.model.clone(),
+            year: car.year,
+            color: car.color.unwrap_or_default(),
+            engine_type: car.engine_type.clone(),
+            transmission: car.transmission.unwrap_or_default(),
+            price: car.price.clone(),
+            quantity_in_stock: car.quantity_in_stock - reservation.quantity,
+            status: if car.quantity_in_stock - reservation.quantity == 0 {
+                CarStatus::Sold
+            } else {
+                car.status.clone()
+            },
+            mileage_km: car.mileage_km,
+        };
+
+        self.car_repo
+            .update_in_uow(&mut uow, &reservation.car_id, update_data)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let recorded = self
+            .sales_repo
+            .record_sale_in_uow(
+                &mut uow,
+                &reservation.car_id,
+                reservation.quantity,
+                &pricing,
+                Some(&customer_id),
+                promo_code.as_deref(),
+            )
+            .await;
+        let sale_id = match recorded {
+            Ok(sale_id) => sale_id,
+            Err(e) => {
+                uow.rollback().await?;
+                return Err(match e {
+                    ReservationError::InsufficientStock {
+                        requested,
+                        available,
+                    } => AppError::InsufficientStock {
+                        requested: requested as u32,
+                        available: available as u32,
+                    },
+                    ReservationError::CarNotFound => AppError::NotFound,
+                    ReservationError::ReservationNotFound => AppError::ReservationNotFound,
+                    ReservationError::ReservationExpired => AppError::ReservationExpired,
+                    ReservationError::Database(db_err) => AppError::DatabaseError(db_err),
+                });
+            }
+        };
+
+        uow.commit().await?;
+
+        self.cache.invalidate_car(reservation.car_id.as_str()).await;
+
+        info!(
+            sale_id = %sale_id,
+            car_id = %reservation.car_id,
+            unit_price = %pricing.unit_price,
+            rules = pricing.applied_rules.len(),
+            "Sale processed"
+        );
+
+        Ok(SaleReceipt {
+            sale_id,
+            reservation_id,
+            car_id: reservation.car_id,
+            quantity: reservation.quantity,
+            promo_code,
+            total_price: &pricing.unit_price * BigDecimal::from(reservation.quantity),
+            pricing,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone};
+
+    use super::*;
+
+    fn day(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, 0, 0, 0).unwrap()
+    }
+
+    fn point(day_of_month: u32, total_cars: Option<f64>) -> MetricsHistoryPoint {
+        MetricsHistoryPoint {
+            bucket_start: day(day_of_month),
+            samples: if total_cars.is_some() { 24 } else { 0 },
+            values: MetricValues {
+                total_cars,
+                ..MetricValues::default()
+            },
+        }
+    }
+
+    /// Empty buckets at the start, in the middle and at the end.
+    fn gappy_series() -> Vec<MetricsHistoryPoint> {
+        vec![
+            point(1, None),
+            point(2, Some(10.0)),
+            point(3, None),
+            point(4, Some(12.0)),
+            point(5, None),
+        ]
+    }
+
+    fn total_cars(points: &[MetricsHistoryPoint]) -> Vec<Option<f64>> {
+        points.iter().map(|p| p.values.total_cars).collect()
+    }
+
+    #[test]
+    fn bucket_count_covers_partial_buckets_at_both_ends() {
+        let from = Utc.with_ymd_and_hms(2026, 3, 1, 6, 30, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 3, 3, 6, 30, 0).unwrap();
+
+        // 06:00 on the 1st through 06:00 on the 3rd, and the 1st, 2nd and 3rd.
+        assert_eq!(MetricsBucket::Hour.bucket_count(from, to), 49);
+        assert_eq!(MetricsBucket::Day.bucket_count(from, to), 3);
+        assert_eq!(MetricsBucket::fit(from, to, 49), MetricsBucket::Hour);
+        assert_eq!(MetricsBucket::fit(from, to, 48), MetricsBucket::Day);
+        assert_eq!(MetricsBucket::fit(from, to, 1), MetricsBucket::Month);
+    }
+
+    #[test]
+    fn gap_fill_none_drops_empty_buckets() {
+        let points = fill_metric_gaps(gappy_series(), MetricsGapFill::None);
+
+        let starts: Vec<_> = points.iter().map(|p| p.bucket_start).collect();
+        assert_eq!(starts, [day(2), day(4)]);
+    }
+
+    #[test]
+    fn gap_fill_null_keeps_empty_buckets_aligned() {
+        let points = fill_metric_gaps(gappy_series(), MetricsGapFill::Null);
+
+        let starts: Vec<_> = points.iter().map(|p| p.bucket_start).collect();
+        assert_eq!(starts, (1..=5).map(day).collect::<Vec<_>>());
+        assert_eq!(
+            total_cars(&points),
+            [None, Some(10.0), None, Some(12.0), None]
+        );
+    }
+
+    #[test]
+    fn gap_fill_zero_fills_every_empty_bucket() {
+        let points = fill_metric_gaps(gappy_series(), MetricsGapFill::Zero);
+
+        assert_eq!(
+            total_cars(&points),
+            [Some(0.0), Some(10.0), Some(0.0), Some(12.0), Some(0.0)]
+        );
+        assert!(points.iter().all(|p| p.values.total_value == Some(0.0)));
+        assert_eq!(points[0].samples, 0);
+    }
+
+    #[test]
+    fn gap_fill_previous_carries_values_forward() {
+        let points = fill_metric_gaps(gappy_series(), MetricsGapFill::Previous);
+
+        // Nothing to carry into the leading gap.
+        assert_eq!(
+            total_cars(&points),
+            [None, Some(10.0), Some(10.0), Some(12.0), Some(12.0)]
+        );
+    }
 }
@@ -1,11 +1,17 @@
 use std::sync::Arc;
 use std::time::Instant;
 
-use crate::circuit_breaker::CircuitBreaker;
+use metrics_exporter_prometheus::PrometheusHandle;
+
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerRegistry};
 use crate::config::AppConfig;
+use crate::leader::LeaderElection;
+use crate::lifecycle::Lifecycle;
 use crate::pool_manager::PoolManager;
 use crate::services::{
-    CarService, HealthCheckService, InventoryAnalyticsService, ReservationService, WarehouseService,
+    AgingService, CarService, ClassificationService, HealthCheckService, InventoryAnalyticsService,
+    JobService, PriceHistoryService, PricingService, PurchasingService, RebalancingService,
+    ReorderService, ReservationService, SaleService, WarehouseService,
 };
 
 #[derive(Clone)]
@@ -15,8 +21,21 @@ pub struct AppState {
     pub reservation_service: Arc<ReservationService>,
     pub warehouse_service: Arc<WarehouseService>,
     pub inventory_analytics_service: Arc<InventoryAnalyticsService>,
+    pub job_service: Arc<JobService>,
+    pub reorder_service: Arc<ReorderService>,
+    pub purchasing_service: Arc<PurchasingService>,
+    pub rebalancing_service: Arc<RebalancingService>,
+    pub classification_service: Arc<ClassificationService>,
+    pub aging_service: Arc<AgingService>,
+    pub price_history_service: Arc<PriceHistoryService>,
+    pub pricing_service: Arc<PricingService>,
+    pub sale_service: Arc<SaleService>,
     pub config: AppConfig,
     pub start_time: Instant,
     pub db_circuit_breaker: Arc<CircuitBreaker>,
+    pub circuit_breakers: Arc<CircuitBreakerRegistry>,
     pub pool_manager: Option<Arc<PoolManager>>,
+    pub metrics_handle: Option<PrometheusHandle>,
+    pub lifecycle: Arc<Lifecycle>,
+    pub leader_election: Option<Arc<LeaderElection>>,
 }
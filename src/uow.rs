@@ -3,6 +3,7 @@ use std::ops::{Deref, DerefMut};
 use sqlx::{PgConnection, Postgres, Transaction};
 
 use crate::error::AppError;
+use crate::pool_manager::PoolHandle;
 
 pub struct UnitOfWork<'a> {
     tx: Option<Transaction<'a, Postgres>>,
@@ -95,11 +96,11 @@ pub trait UnitOfWorkFactory: Send + Sync {
 }
 
 pub struct PgUnitOfWorkFactory {
-    pool: sqlx::PgPool,
+    pool: PoolHandle,
 }
 
 impl PgUnitOfWorkFactory {
-    pub fn new(pool: sqlx::PgPool) -> Self {
+    pub fn new(pool: PoolHandle) -> Self {
         Self { pool }
     }
 }
@@ -107,6 +108,6 @@ impl PgUnitOfWorkFactory {
 #[async_trait::async_trait]
 impl UnitOfWorkFactory for PgUnitOfWorkFactory {
     async fn create_uow(&self) -> Result<UnitOfWork<'_>, AppError> {
-        UnitOfWork::begin(&self.pool).await
+        UnitOfWork::begin(&self.pool.get()).await
     }
 }